@@ -4,14 +4,24 @@
 #[cfg(test)]
 mod tests;
 
-use qsc_codegen::qir::fir_to_qir;
+use qsc_codegen::{qasm3::generate_qasm3, qir::fir_to_qir, qir_base::generate_qir_from_fir};
 use qsc_data_structures::{language_features::LanguageFeatures, target::TargetCapabilityFlags};
-use qsc_frontend::compile::{PackageStore, SourceMap};
+use qsc_fir::fir;
+use qsc_frontend::{
+    compile::{PackageStore, SourceMap},
+    error::WithSource,
+};
+use qsc_hir::hir::PackageId;
+use qsc_lowerer::map_hir_package_to_fir;
 use qsc_partial_eval::ProgramEntry;
 use qsc_passes::{PackageType, PassContext};
 use qsc_rca::Analyzer;
+use std::fmt::Write;
 
-use crate::compile;
+use crate::{
+    compile,
+    interpret::{eval_error, Error},
+};
 
 pub fn get_qir(
     sources: SourceMap,
@@ -76,3 +86,126 @@ pub fn get_qir(
     fir_to_qir(&fir_store, capabilities, Some(compute_properties), &entry)
         .map_err(|e| e.to_string())
 }
+
+/// Exports the entry point of `sources` to an `OpenQASM` 3 program.
+///
+/// The sources are compiled for the Base profile, so only programs that describe
+/// a static circuit, without classical control depending on measurement results,
+/// can be exported.
+pub fn get_qasm3(
+    sources: SourceMap,
+    language_features: LanguageFeatures,
+) -> Result<String, String> {
+    let capabilities = TargetCapabilityFlags::empty();
+    let core = compile::core();
+    let mut package_store = PackageStore::new(core);
+    let std = compile::std(&package_store, capabilities);
+    let std = package_store.insert(std);
+
+    let (unit, errors) = crate::compile::compile(
+        &package_store,
+        &[std],
+        sources,
+        PackageType::Exe,
+        capabilities,
+        language_features,
+    );
+
+    if !errors.is_empty() {
+        let mut error_message =
+            String::from("Failed to generate OpenQASM 3. Could not compile sources.:\n");
+        for error in errors {
+            writeln!(error_message, "{error}").expect("writing to string should succeed");
+        }
+
+        return Err(error_message);
+    }
+
+    let package_id = package_store.insert(unit);
+    generate_qasm3(&package_store, package_id).map_err(|errors| {
+        let mut error_message = String::from("Failed to generate OpenQASM 3.:\n");
+        for error in errors {
+            writeln!(error_message, "{error}").expect("writing to string should succeed");
+        }
+        error_message
+    })
+}
+
+/// Generates QIR for the entry expression of a package that has already been
+/// compiled without errors and added to `package_store`.
+pub fn get_qir_from_package(
+    package_store: &PackageStore,
+    package_id: PackageId,
+    capabilities: TargetCapabilityFlags,
+) -> Result<String, Vec<Error>> {
+    if capabilities == TargetCapabilityFlags::all() {
+        return Err(vec![Error::UnsupportedRuntimeCapabilities]);
+    }
+
+    let unit = package_store
+        .get(package_id)
+        .expect("package should exist in the package store");
+    if unit.package.entry.is_none() {
+        return Err(vec![Error::NoEntryPoint]);
+    }
+
+    let (fir_store, _) = qsc_passes::lower_hir_to_fir(package_store, package_id);
+    get_qir_from_fir(package_store, &fir_store, package_id, capabilities)
+}
+
+/// Like [`get_qir_from_package`], but reuses `fir_store`, the result of lowering
+/// `package_store` to FIR, instead of lowering it again.
+pub fn get_qir_from_fir(
+    package_store: &PackageStore,
+    fir_store: &fir::PackageStore,
+    package_id: PackageId,
+    capabilities: TargetCapabilityFlags,
+) -> Result<String, Vec<Error>> {
+    if capabilities == TargetCapabilityFlags::all() {
+        return Err(vec![Error::UnsupportedRuntimeCapabilities]);
+    }
+
+    let unit = package_store
+        .get(package_id)
+        .expect("package should exist in the package store");
+    if unit.package.entry.is_none() {
+        return Err(vec![Error::NoEntryPoint]);
+    }
+
+    let fir_package_id = map_hir_package_to_fir(package_id);
+    if capabilities == TargetCapabilityFlags::empty() {
+        // The base profile is generated by evaluating the program against a QIR-emitting backend.
+        return generate_qir_from_fir(fir_store, fir_package_id).map_err(|(error, call_stack)| {
+            eval_error(package_store, fir_store, call_stack, error)
+        });
+    }
+
+    let package = fir_store.get(fir_package_id);
+    let entry = ProgramEntry {
+        exec_graph: package.entry_exec_graph.clone(),
+        expr: (
+            fir_package_id,
+            package
+                .entry
+                .expect("package must have an entry expression"),
+        )
+            .into(),
+    };
+
+    let compute_properties =
+        PassContext::run_fir_passes_on_fir(fir_store, fir_package_id, capabilities).map_err(
+            |errors| {
+                errors
+                    .into_iter()
+                    .map(|error| Error::Pass(WithSource::from_map(&unit.sources, error)))
+                    .collect::<Vec<_>>()
+            },
+        )?;
+
+    fir_to_qir(fir_store, capabilities, Some(compute_properties), &entry).map_err(|error| {
+        vec![Error::PartialEvaluation(WithSource::from_map(
+            &unit.sources,
+            error,
+        ))]
+    })
+}
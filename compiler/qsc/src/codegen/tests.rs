@@ -659,3 +659,60 @@ mod quantinuum_profile {
         "#]].assert_eq(&qir);
     }
 }
+
+mod qasm3 {
+    use expect_test::expect;
+    use qsc_data_structures::language_features::LanguageFeatures;
+    use qsc_frontend::compile::SourceMap;
+
+    use crate::codegen::get_qasm3;
+
+    #[test]
+    fn entry_point_is_exported() {
+        let source = "namespace Test {
+            @EntryPoint()
+            operation Main() : Result {
+                use q = Qubit();
+                X(q);
+                MResetZ(q)
+            }
+        }";
+        let sources = SourceMap::new([("test.qs".into(), source.into())], None);
+
+        let qasm =
+            get_qasm3(sources, LanguageFeatures::default()).expect("Failed to generate OpenQASM 3");
+        expect![[r#"
+            OPENQASM 3.0;
+            include "stdgates.inc";
+            qubit[1] q;
+            bit[1] c;
+            x q[0];
+            c[0] = measure q[0];
+            reset q[0];
+        "#]]
+        .assert_eq(&qasm);
+    }
+
+    #[test]
+    fn classical_control_on_measurement_is_rejected() {
+        let source = "namespace Test {
+            @EntryPoint()
+            operation Main() : Result {
+                use q = Qubit();
+                H(q);
+                if MResetZ(q) == One {
+                    X(q);
+                }
+                MResetZ(q)
+            }
+        }";
+        let sources = SourceMap::new([("test.qs".into(), source.into())], None);
+
+        expect![[r#"
+            Err(
+                "Failed to generate OpenQASM 3. Could not compile sources.:\ncannot compare measurement results\nresult literals are not supported\n",
+            )
+        "#]]
+        .assert_debug_eq(&get_qasm3(sources, LanguageFeatures::default()));
+    }
+}
@@ -1,7 +1,8 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
-use miette::{Diagnostic, Report};
+use crate::target::Profile;
+use miette::{Diagnostic, LabeledSpan, Report, Severity, SourceCode};
 use qsc_data_structures::{language_features::LanguageFeatures, target::TargetCapabilityFlags};
 use qsc_frontend::{
     compile::{CompileUnit, PackageStore, SourceMap},
@@ -9,6 +10,7 @@ use qsc_frontend::{
 };
 use qsc_hir::hir::PackageId;
 use qsc_passes::{run_core_passes, run_default_passes, PackageType};
+use std::fmt::Display;
 use thiserror::Error;
 
 pub type Error = WithSource<ErrorKind>;
@@ -26,13 +28,118 @@ pub enum ErrorKind {
     /// `Pass` variant represents errors that occur during the `qsc_passes` stage of the compiler.
     /// These errors are typically related to optimization, transformation, code generation, passes,
     /// and static analysis passes.
-    Pass(#[from] qsc_passes::Error),
+    Pass(#[from] PassError),
 
     /// `Lint` variant represents lints generated during the linting stage. These diagnostics are
     /// typically emited from the language server and happens after all other compilation passes.
     Lint(#[from] qsc_linter::Lint),
 }
 
+impl From<qsc_passes::Error> for ErrorKind {
+    fn from(error: qsc_passes::Error) -> Self {
+        Self::Pass(error.into())
+    }
+}
+
+impl ErrorKind {
+    /// Returns the least capable target profile that supports the construct this error
+    /// reports, or `None` if the error is not about a construct the target does not support.
+    #[must_use]
+    pub fn required_profile(&self) -> Option<Profile> {
+        match self {
+            Self::Pass(error) => error.required_profile(),
+            Self::Frontend(_) | Self::Lint(_) => None,
+        }
+    }
+}
+
+/// An error from the `qsc_passes` stage of the compiler. Errors about constructs that
+/// the target does not support also name the least capable target profile that does,
+/// so that tools can offer to switch to it.
+#[derive(Clone, Debug)]
+pub struct PassError {
+    error: qsc_passes::Error,
+    required_profile: Option<Profile>,
+}
+
+impl PassError {
+    /// The error reported by the pass.
+    #[must_use]
+    pub fn error(&self) -> &qsc_passes::Error {
+        &self.error
+    }
+
+    /// The least capable target profile that supports the construct this error reports,
+    /// or `None` if the error is not about a construct the target does not support.
+    #[must_use]
+    pub fn required_profile(&self) -> Option<Profile> {
+        self.required_profile
+    }
+}
+
+impl From<qsc_passes::Error> for PassError {
+    fn from(error: qsc_passes::Error) -> Self {
+        let required_profile = error.required_capabilities().map(Profile::minimal_for);
+        Self {
+            error,
+            required_profile,
+        }
+    }
+}
+
+impl Display for PassError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+impl std::error::Error for PassError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.error.source()
+    }
+}
+
+impl Diagnostic for PassError {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        self.error.code()
+    }
+
+    fn severity(&self) -> Option<Severity> {
+        self.error.severity()
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        let Some(profile) = self.required_profile else {
+            return self.error.help();
+        };
+        let requirement = format!("this requires at least the {profile} target profile");
+        Some(Box::new(match self.error.help() {
+            Some(help) => format!("{help}\n{requirement}"),
+            None => requirement,
+        }))
+    }
+
+    fn url<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        self.error.url()
+    }
+
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        self.error.source_code()
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        self.error.labels()
+    }
+
+    fn related<'a>(&'a self) -> Option<Box<dyn Iterator<Item = &'a dyn Diagnostic> + 'a>> {
+        self.error.related()
+    }
+
+    fn diagnostic_source(&self) -> Option<&dyn Diagnostic> {
+        self.error.diagnostic_source()
+    }
+}
+
 #[must_use]
 #[allow(clippy::module_name_repetitions)]
 pub fn compile_ast(
@@ -73,6 +180,23 @@ pub fn compile(
     process_compile_unit(store, package_type, capabilities, unit)
 }
 
+/// Parses the sources without running any later compilation stage.
+/// Only syntax errors are reported.
+#[must_use]
+pub fn parse(
+    sources: &SourceMap,
+    language_features: LanguageFeatures,
+) -> (qsc_ast::ast::Package, Vec<Error>) {
+    let (package, errors) = qsc_frontend::compile::parse(sources, language_features);
+    (
+        package,
+        errors
+            .into_iter()
+            .map(|error| WithSource::from_map(sources, error.into()))
+            .collect(),
+    )
+}
+
 #[must_use]
 #[allow(clippy::module_name_repetitions)]
 fn process_compile_unit(
@@ -0,0 +1,309 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use qsc_eval::{
+    backend::SparseSim,
+    output::{self, Receiver},
+    val::Value,
+    Env,
+};
+use qsc_fir::fir::{
+    self, Block, Expr, Global, PackageLookup, PackageStoreLookup, Pat, Stmt, StoreBlockId,
+    StoreExprId, StoreItemId, StorePatId, StoreStmtId,
+};
+use qsc_frontend::compile::PackageStore;
+use qsc_hir::{
+    hir::{self, CallableKind, ExprKind, ItemKind, Mutability, NodeId, PatKind, Res, StmtKind},
+    mut_visit::{self, MutVisitor},
+    ty::Ty,
+    visit::{self, Visitor},
+};
+use qsc_lowerer::Lowerer;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+/// Evaluates `expr`, an expression from the package `package`, if its value is known
+/// at compile time. Locals bound by an immutable `let` outside of `expr` are replaced by
+/// their initializers.
+///
+/// Only expressions that cannot observe or affect anything outside of themselves are
+/// evaluated: they must not call or reference operations, allocate qubits, loop, recurse,
+/// or depend on any other local. Returns `None` for any other expression, and for
+/// expressions that fail or produce output when evaluated.
+#[must_use]
+pub fn try_eval_expr(
+    package_store: &PackageStore,
+    fir_store: &fir::PackageStore,
+    package: hir::PackageId,
+    expr: &hir::Expr,
+) -> Option<Value> {
+    let unit = package_store.get(package)?;
+    let mut lets = LetCollector::default();
+    lets.visit_package(&unit.package);
+
+    let mut expr = expr.clone();
+    Inliner {
+        package,
+        lets: &lets.initializers,
+    }
+    .visit_expr(&mut expr);
+
+    let mut checker = PurityChecker {
+        package_store,
+        packages: vec![package],
+        bound: FxHashSet::default(),
+        in_progress: FxHashSet::default(),
+        checked: FxHashSet::default(),
+        is_pure: true,
+    };
+    checker.visit_expr(&expr);
+    if !checker.is_pure {
+        return None;
+    }
+
+    let hir_package = hir::Package {
+        entry: Some(expr),
+        ..hir::Package::default()
+    };
+    let fir_package = Lowerer::new().lower_package(&hir_package);
+    let id = fir_store
+        .iter()
+        .map(|(id, _)| id)
+        .max()
+        .map_or(fir::PackageId::CORE, fir::PackageId::successor);
+    let globals = WithPackage {
+        store: fir_store,
+        id,
+        package: &fir_package,
+    };
+
+    qsc_eval::eval(
+        id,
+        None,
+        fir_package.entry_exec_graph.clone(),
+        &globals,
+        &mut Env::default(),
+        &mut SparseSim::new(),
+        &mut NoOutput,
+    )
+    .ok()
+}
+
+/// Collects the initializers of immutable bindings to a single name.
+#[derive(Default)]
+struct LetCollector<'a> {
+    initializers: FxHashMap<NodeId, &'a hir::Expr>,
+}
+
+impl<'a> Visitor<'a> for LetCollector<'a> {
+    fn visit_stmt(&mut self, stmt: &'a hir::Stmt) {
+        if let StmtKind::Local(Mutability::Immutable, pat, init) = &stmt.kind {
+            if let PatKind::Bind(ident) = &pat.kind {
+                self.initializers.insert(ident.id, init);
+            }
+        }
+        visit::walk_stmt(self, stmt);
+    }
+}
+
+/// Replaces references to immutable bindings with their initializers, and qualifies
+/// references to items of the package so that the expression can be evaluated on its own.
+struct Inliner<'a> {
+    package: hir::PackageId,
+    lets: &'a FxHashMap<NodeId, &'a hir::Expr>,
+}
+
+impl MutVisitor for Inliner<'_> {
+    fn visit_expr(&mut self, expr: &mut hir::Expr) {
+        while let ExprKind::Var(Res::Local(id), _) = &expr.kind {
+            match self.lets.get(id) {
+                Some(&init) => *expr = init.clone(),
+                None => break,
+            }
+        }
+        if let ExprKind::Var(res, _) = &mut expr.kind {
+            *res = res.with_package(self.package);
+        }
+        mut_visit::walk_expr(self, expr);
+    }
+}
+
+/// Checks that an expression, and every callable it can reach, is free of effects.
+struct PurityChecker<'a> {
+    package_store: &'a PackageStore,
+    /// The packages of the callables being checked, innermost last. The first entry
+    /// is the package of the expression itself.
+    packages: Vec<hir::PackageId>,
+    /// The locals bound within the expression itself.
+    bound: FxHashSet<NodeId>,
+    in_progress: FxHashSet<(hir::PackageId, hir::LocalItemId)>,
+    checked: FxHashSet<(hir::PackageId, hir::LocalItemId)>,
+    is_pure: bool,
+}
+
+impl PurityChecker<'_> {
+    fn in_expr(&self) -> bool {
+        self.packages.len() == 1
+    }
+
+    fn current_package(&self) -> hir::PackageId {
+        *self
+            .packages
+            .last()
+            .expect("package stack should not be empty")
+    }
+
+    fn check_item(&mut self, package: hir::PackageId, item: hir::LocalItemId) {
+        if self.checked.contains(&(package, item)) {
+            return;
+        }
+        if !self.in_progress.insert((package, item)) {
+            // Recursion is not guaranteed to terminate.
+            self.is_pure = false;
+            return;
+        }
+
+        let package_store = self.package_store;
+        match package_store
+            .get(package)
+            .and_then(|unit| unit.package.items.get(item))
+            .map(|item| &item.kind)
+        {
+            Some(ItemKind::Ty(..)) => {}
+            Some(ItemKind::Callable(decl)) if decl.kind == CallableKind::Function => {
+                self.packages.push(package);
+                self.visit_callable_decl(decl);
+                self.packages.pop();
+            }
+            _ => self.is_pure = false,
+        }
+
+        self.in_progress.remove(&(package, item));
+        self.checked.insert((package, item));
+    }
+}
+
+impl<'a> Visitor<'a> for PurityChecker<'a> {
+    fn visit_stmt(&mut self, stmt: &'a hir::Stmt) {
+        if let StmtKind::Qubit(..) = stmt.kind {
+            self.is_pure = false;
+        }
+        if self.is_pure {
+            visit::walk_stmt(self, stmt);
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &'a hir::Expr) {
+        match &expr.kind {
+            ExprKind::Conjugate(..)
+            | ExprKind::Err
+            | ExprKind::For(..)
+            | ExprKind::Hole
+            | ExprKind::Repeat(..)
+            | ExprKind::While(..)
+            | ExprKind::Var(Res::Err, _) => self.is_pure = false,
+            ExprKind::Call(callee, _) => {
+                if let Ty::Arrow(arrow) = &callee.ty {
+                    if arrow.kind == CallableKind::Operation {
+                        self.is_pure = false;
+                    }
+                }
+            }
+            ExprKind::Closure(_, item) => {
+                if self.in_expr() {
+                    // The lambda belongs to the package of the expression, which
+                    // cannot be referred to once the expression is evaluated on its own.
+                    self.is_pure = false;
+                } else {
+                    self.check_item(self.current_package(), *item);
+                }
+            }
+            ExprKind::Return(_) if self.in_expr() => self.is_pure = false,
+            ExprKind::Var(Res::Local(id), _) if self.in_expr() && !self.bound.contains(id) => {
+                self.is_pure = false;
+            }
+            ExprKind::Var(Res::Item(item), _) => {
+                let package = item.package.unwrap_or(self.current_package());
+                self.check_item(package, item.item);
+            }
+            _ => {}
+        }
+        if self.is_pure {
+            visit::walk_expr(self, expr);
+        }
+    }
+
+    fn visit_pat(&mut self, pat: &'a hir::Pat) {
+        if let PatKind::Bind(ident) = &pat.kind {
+            if self.in_expr() {
+                self.bound.insert(ident.id);
+            }
+        }
+        visit::walk_pat(self, pat);
+    }
+}
+
+/// A package store with one extra package that is not part of the store.
+struct WithPackage<'a> {
+    store: &'a fir::PackageStore,
+    id: fir::PackageId,
+    package: &'a fir::Package,
+}
+
+impl PackageStoreLookup for WithPackage<'_> {
+    fn get_block(&self, id: StoreBlockId) -> &Block {
+        if id.package == self.id {
+            self.package.get_block(id.block)
+        } else {
+            self.store.get_block(id)
+        }
+    }
+
+    fn get_expr(&self, id: StoreExprId) -> &Expr {
+        if id.package == self.id {
+            self.package.get_expr(id.expr)
+        } else {
+            self.store.get_expr(id)
+        }
+    }
+
+    fn get_global(&self, id: StoreItemId) -> Option<Global<'_>> {
+        if id.package == self.id {
+            self.package.get_global(id.item)
+        } else {
+            self.store.get_global(id)
+        }
+    }
+
+    fn get_pat(&self, id: StorePatId) -> &Pat {
+        if id.package == self.id {
+            self.package.get_pat(id.pat)
+        } else {
+            self.store.get_pat(id)
+        }
+    }
+
+    fn get_stmt(&self, id: StoreStmtId) -> &Stmt {
+        if id.package == self.id {
+            self.package.get_stmt(id.stmt)
+        } else {
+            self.store.get_stmt(id)
+        }
+    }
+}
+
+/// A receiver that rejects all output, which stops evaluation with an error.
+struct NoOutput;
+
+impl Receiver for NoOutput {
+    fn state(
+        &mut self,
+        _: Vec<(num_bigint::BigUint, num_complex::Complex64)>,
+        _: usize,
+    ) -> Result<(), output::Error> {
+        Err(output::Error)
+    }
+
+    fn message(&mut self, _: &str) -> Result<(), output::Error> {
+        Err(output::Error)
+    }
+}
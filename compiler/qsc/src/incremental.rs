@@ -14,6 +14,7 @@ use qsc_frontend::{
 };
 use qsc_hir::hir::PackageId;
 use qsc_passes::{PackageType, PassContext};
+use std::fmt::{self, Debug, Formatter};
 
 /// An incremental Q# compiler.
 pub struct Compiler {
@@ -29,6 +30,23 @@ pub struct Compiler {
     frontend: qsc_frontend::incremental::Compiler,
 }
 
+/// The state of an incremental compiler other than its package store,
+/// returned by [`Compiler::into_package_store_and_state`] so that compilation
+/// can later continue with [`Compiler::resume`].
+pub struct CompilerState {
+    source_package_id: PackageId,
+    passes: PassContext,
+    frontend: qsc_frontend::incremental::Compiler,
+}
+
+impl Debug for CompilerState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CompilerState")
+            .field("source_package_id", &self.source_package_id)
+            .finish_non_exhaustive()
+    }
+}
+
 /// An incremental compiler error.
 pub type Errors = Vec<compile::Error>;
 
@@ -292,6 +310,34 @@ impl Compiler {
     pub fn into_package_store(self) -> (PackageStore, PackageId) {
         self.store.into_package_store()
     }
+
+    /// Consumes the incremental compiler and returns an immutable package store,
+    /// like [`Compiler::into_package_store`], along with the rest of the compiler's
+    /// state. Passing both to [`Compiler::resume`] continues the compilation, with
+    /// the items and top-level bindings of all previous fragments still in scope.
+    #[must_use]
+    pub fn into_package_store_and_state(self) -> (PackageStore, PackageId, CompilerState) {
+        let (store, package_id) = self.store.into_package_store();
+        let state = CompilerState {
+            source_package_id: self.source_package_id,
+            passes: self.passes,
+            frontend: self.frontend,
+        };
+        (store, package_id, state)
+    }
+
+    /// Continues an incremental compilation that was finalized with
+    /// [`Compiler::into_package_store_and_state`]. `store` and `package_id` must be
+    /// the package store and package returned along with `state`.
+    #[must_use]
+    pub fn resume(store: PackageStore, package_id: PackageId, state: CompilerState) -> Self {
+        Self {
+            store: store.reopen(package_id),
+            source_package_id: state.source_package_id,
+            passes: state.passes,
+            frontend: state.frontend,
+        }
+    }
 }
 
 fn into_errors_with_source<T>(errors: Vec<T>, sources: &SourceMap) -> Errors
@@ -2,6 +2,8 @@
 // Licensed under the MIT License.
 
 mod debug;
+mod json;
+mod state_dump;
 
 #[cfg(test)]
 mod tests;
@@ -12,8 +14,12 @@ mod debugger_tests;
 #[cfg(test)]
 mod circuit_tests;
 
-use std::rc::Rc;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
+pub use json::{value_from_json, value_to_json, JsonError};
 pub use qsc_eval::{
     debug::Frame,
     output::{self, GenericReceiver},
@@ -21,11 +27,13 @@ pub use qsc_eval::{
     val::Range as ValueRange,
     val::Result,
     val::Value,
+    val::ValueConversionError,
     StepAction, StepResult,
 };
 use qsc_lowerer::{map_fir_package_to_hir, map_hir_package_to_fir};
 use qsc_partial_eval::ProgramEntry;
 use qsc_rca::PackageStoreComputeProperties;
+pub use state_dump::{state_to_json, StateDumpFormat};
 
 use crate::{
     error::{self, WithStack},
@@ -64,7 +72,8 @@ use qsc_frontend::{
     incremental::Increment,
 };
 use qsc_passes::{PackageType, PassContext};
-use rustc_hash::FxHashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
+use state_dump::StateDumpReceiver;
 use thiserror::Error;
 
 impl Error {
@@ -137,6 +146,10 @@ pub struct Interpreter {
     classical_seed: Option<u64>,
     /// The evaluator environment.
     env: Env,
+    /// The maximum number of nested callable calls allowed during evaluation.
+    max_recursion_depth: usize,
+    /// The format used to report state dumps to the receiver.
+    state_dump_format: StateDumpFormat,
 }
 
 pub type InterpretResult = std::result::Result<Value, Vec<Error>>;
@@ -215,6 +228,8 @@ impl Interpreter {
             sim: sim_circuit_backend(),
             quantum_seed: None,
             classical_seed: None,
+            max_recursion_depth: qsc_eval::DEFAULT_MAX_RECURSION_DEPTH,
+            state_dump_format: StateDumpFormat::default(),
             package: map_hir_package_to_fir(package_id),
             source_package: map_hir_package_to_fir(source_package_id),
         })
@@ -251,6 +266,8 @@ impl Interpreter {
             sim: sim_circuit_backend(),
             quantum_seed: None,
             classical_seed: None,
+            max_recursion_depth: qsc_eval::DEFAULT_MAX_RECURSION_DEPTH,
+            state_dump_format: StateDumpFormat::default(),
             package: map_hir_package_to_fir(package_id),
             source_package: map_hir_package_to_fir(source_package_id),
         })
@@ -264,6 +281,31 @@ impl Interpreter {
     pub fn set_classical_seed(&mut self, seed: Option<u64>) {
         self.classical_seed = seed;
     }
+
+    /// Sets the maximum number of nested callable calls allowed during evaluation.
+    /// Deeper recursion stops evaluation with a `RecursionLimitExceeded` error.
+    /// The default is [`qsc_eval::DEFAULT_MAX_RECURSION_DEPTH`].
+    pub fn set_max_recursion_depth(&mut self, depth: usize) {
+        self.max_recursion_depth = depth;
+    }
+
+    /// Sets the format in which state dumps, such as those from `DumpMachine`, are
+    /// reported to the receiver. The default is [`StateDumpFormat::Table`].
+    pub fn set_state_dump_format(&mut self, format: StateDumpFormat) {
+        self.state_dump_format = format;
+    }
+
+    /// Seeds both the quantum simulator and the classical random number generator,
+    /// so that repeated runs of the same program with the same inputs produce the
+    /// same measurement results and random values.
+    /// Results are only reproducible with the same version of the simulator and
+    /// standard library; the same seed may produce different results across versions.
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.set_quantum_seed(Some(seed));
+        self.set_classical_seed(Some(seed));
+        self
+    }
     /// Executes the entry expression until the end of execution.
     /// # Errors
     /// Returns a vector of errors if evaluating the entry point fails.
@@ -275,12 +317,15 @@ impl Interpreter {
         eval(
             self.source_package,
             self.classical_seed,
+            self.max_recursion_depth,
+            self.state_dump_format,
             graph,
             self.compiler.package_store(),
             &self.fir_store,
             &mut Env::default(),
             &mut self.sim,
             receiver,
+            None,
         )
     }
 
@@ -298,16 +343,19 @@ impl Interpreter {
         eval(
             self.source_package,
             self.classical_seed,
+            self.max_recursion_depth,
+            self.state_dump_format,
             graph,
             self.compiler.package_store(),
             &self.fir_store,
             &mut Env::default(),
             sim,
             receiver,
+            None,
         )
     }
 
-    fn get_entry_exec_graph(&self) -> std::result::Result<Rc<[ExecGraphNode]>, Vec<Error>> {
+    fn get_entry_exec_graph(&self) -> std::result::Result<Arc<[ExecGraphNode]>, Vec<Error>> {
         let unit = self.fir_store.get(self.source_package);
         if unit.entry.is_some() {
             return Ok(unit.entry_exec_graph.clone());
@@ -374,12 +422,15 @@ impl Interpreter {
         eval(
             self.package,
             self.classical_seed,
+            self.max_recursion_depth,
+            self.state_dump_format,
             graph.into(),
             self.compiler.package_store(),
             &self.fir_store,
             &mut self.env,
             &mut self.sim,
             receiver,
+            None,
         )
     }
 
@@ -393,6 +444,22 @@ impl Interpreter {
         self.run_with_sim(&mut SparseSim::new(), receiver, expr)
     }
 
+    /// Runs the given entry expression like [`Interpreter::run`], but stops with a
+    /// [`qsc_eval::Error::TimedOut`] runtime error if evaluation is still in progress
+    /// after `timeout` has elapsed.
+    ///
+    /// Because each run uses a new instance of the environment and simulator, a timed
+    /// out run leaves the interpreter in the same state as a failed one.
+    /// This relies on `std::time::Instant`, which is unavailable on `wasm32-unknown-unknown`.
+    pub fn run_with_timeout(
+        &mut self,
+        receiver: &mut impl Receiver,
+        expr: &str,
+        timeout: Duration,
+    ) -> std::result::Result<InterpretResult, Vec<Error>> {
+        self.run_with_sim_and_timeout(&mut SparseSim::new(), receiver, expr, Some(timeout))
+    }
+
     /// Gets the current quantum state of the simulator.
     pub fn get_quantum_state(&mut self) -> (Vec<(BigUint, Complex<f64>)>, usize) {
         self.sim.capture_quantum_state()
@@ -515,6 +582,18 @@ impl Interpreter {
         sim: &mut impl Backend<ResultType = impl Into<val::Result>>,
         receiver: &mut impl Receiver,
         expr: &str,
+    ) -> std::result::Result<InterpretResult, Vec<Error>> {
+        self.run_with_sim_and_timeout(sim, receiver, expr, None)
+    }
+
+    /// Like [`Interpreter::run_with_sim`], but if `timeout` is given, evaluation stops
+    /// once that much time has passed since the entry expression was compiled.
+    fn run_with_sim_and_timeout(
+        &mut self,
+        sim: &mut impl Backend<ResultType = impl Into<val::Result>>,
+        receiver: &mut impl Receiver,
+        expr: &str,
+        timeout: Option<Duration>,
     ) -> std::result::Result<InterpretResult, Vec<Error>> {
         let (graph, _) = self.compile_entry_expr(expr)?;
 
@@ -525,15 +604,58 @@ impl Interpreter {
         Ok(eval(
             self.package,
             self.classical_seed,
+            self.max_recursion_depth,
+            self.state_dump_format,
             graph.into(),
             self.compiler.package_store(),
             &self.fir_store,
             &mut Env::default(),
             sim,
             receiver,
+            timeout.map(|timeout| Instant::now() + timeout),
         ))
     }
 
+    /// Runs the given entry expression `shots` times, each on a new instance of the environment
+    /// and simulator, and returns a histogram of the results keyed by their string representation.
+    ///
+    /// If a quantum or classical seed is set, shot `i` is run with the seed offset by `i`, so
+    /// seeded samples are reproducible without every shot producing the same outcome.
+    pub fn sample(
+        &mut self,
+        entry: &str,
+        shots: usize,
+    ) -> std::result::Result<FxHashMap<String, usize>, Vec<Error>> {
+        let graph: Arc<[ExecGraphNode]> = self.compile_entry_expr(entry)?.0.into();
+        let mut sink = std::io::sink();
+        let mut out = GenericReceiver::new(&mut sink);
+        let mut histogram = FxHashMap::default();
+
+        for shot in 0..shots as u64 {
+            let mut sim = SparseSim::new();
+            if let Some(seed) = self.quantum_seed {
+                sim.set_seed(Some(seed.wrapping_add(shot)));
+            }
+
+            let value = eval(
+                self.package,
+                self.classical_seed.map(|seed| seed.wrapping_add(shot)),
+                self.max_recursion_depth,
+                self.state_dump_format,
+                graph.clone(),
+                self.compiler.package_store(),
+                &self.fir_store,
+                &mut Env::default(),
+                &mut sim,
+                &mut out,
+                None,
+            )?;
+            *histogram.entry(value.to_string()).or_default() += 1;
+        }
+
+        Ok(histogram)
+    }
+
     fn run_with_sim_no_output(
         &mut self,
         entry_expr: Option<String>,
@@ -557,12 +679,15 @@ impl Interpreter {
         eval(
             package_id,
             self.classical_seed,
+            self.max_recursion_depth,
+            self.state_dump_format,
             graph,
             self.compiler.package_store(),
             &self.fir_store,
             &mut Env::default(),
             sim,
             &mut out,
+            None,
         )
     }
 
@@ -716,6 +841,8 @@ pub struct Debugger {
     position_encoding: Encoding,
     /// The current state of the evaluator.
     state: State,
+    /// The statements in the source package on which `step` pauses.
+    breakpoints: Vec<StmtId>,
 }
 
 impl Debugger {
@@ -739,9 +866,55 @@ impl Debugger {
             interpreter,
             position_encoding,
             state: State::new(source_package_id, entry_exec_graph, None),
+            breakpoints: Vec::new(),
         })
     }
 
+    /// Sets the breakpoints used by `step`, replacing any previously set.
+    /// Each span is matched against the spans of the statements in the user package,
+    /// and every statement contained in the span becomes a breakpoint.
+    pub fn set_breakpoints(&mut self, spans: &[Span]) {
+        let package = self
+            .interpreter
+            .fir_store
+            .get(self.interpreter.source_package);
+        self.breakpoints = package
+            .stmts
+            .iter()
+            .filter(|(_, stmt)| {
+                stmt.span != Span::default()
+                    && spans
+                        .iter()
+                        .any(|span| span.lo <= stmt.span.lo && stmt.span.hi <= span.hi)
+            })
+            .map(|(id, _)| id)
+            .collect();
+    }
+
+    /// Advances execution by one statement, pausing if the statement reached
+    /// is one of the breakpoints set with `set_breakpoints`.
+    /// # Errors
+    /// Returns a vector of errors if evaluating the entry point fails.
+    pub fn step(
+        &mut self,
+        receiver: &mut impl Receiver,
+    ) -> std::result::Result<DebugStepResult, Vec<Error>> {
+        let breakpoints = self.breakpoints.clone();
+        match self.eval_step(receiver, &breakpoints, StepAction::Next)? {
+            StepResult::BreakpointHit(id) => Ok(DebugStepResult::Paused {
+                span: self
+                    .interpreter
+                    .fir_store
+                    .get_stmt((self.interpreter.source_package, id).into())
+                    .span,
+            }),
+            StepResult::Next | StepResult::StepIn | StepResult::StepOut => {
+                Ok(DebugStepResult::Completed)
+            }
+            StepResult::Return(value) => Ok(DebugStepResult::Return(value)),
+        }
+    }
+
     /// Resumes execution with specified `StepAction`.
     /// # Errors
     /// Returns a vector of errors if evaluating the entry point fails.
@@ -751,12 +924,13 @@ impl Debugger {
         breakpoints: &[StmtId],
         step: StepAction,
     ) -> std::result::Result<StepResult, Vec<Error>> {
+        let mut receiver = StateDumpReceiver::new(receiver, self.interpreter.state_dump_format);
         self.state
             .eval(
                 &self.interpreter.fir_store,
                 &mut self.interpreter.env,
                 &mut self.interpreter.sim,
-                receiver,
+                &mut receiver,
                 breakpoints,
                 step,
             )
@@ -861,23 +1035,45 @@ impl Debugger {
 fn eval(
     package: PackageId,
     classical_seed: Option<u64>,
-    exec_graph: Rc<[ExecGraphNode]>,
+    max_recursion_depth: usize,
+    state_dump_format: StateDumpFormat,
+    exec_graph: Arc<[ExecGraphNode]>,
     package_store: &PackageStore,
     fir_store: &fir::PackageStore,
     env: &mut Env,
     sim: &mut impl Backend<ResultType = impl Into<val::Result>>,
     receiver: &mut impl Receiver,
+    deadline: Option<Instant>,
 ) -> InterpretResult {
-    qsc_eval::eval(
-        package,
-        classical_seed,
-        exec_graph,
+    let mut state = State::new(package, exec_graph, classical_seed)
+        .with_max_recursion_depth(max_recursion_depth);
+    if let Some(deadline) = deadline {
+        state = state.with_deadline(deadline);
+    }
+    let mut receiver = StateDumpReceiver::new(receiver, state_dump_format);
+    match state.eval(
         fir_store,
         env,
         sim,
-        receiver,
-    )
-    .map_err(|(error, call_stack)| eval_error(package_store, fir_store, call_stack, error))
+        &mut receiver,
+        &[],
+        StepAction::Continue,
+    ) {
+        Ok(StepResult::Return(value)) => Ok(value),
+        Ok(_) => panic!("evaluation without breakpoints should run to completion"),
+        Err((error, call_stack)) => Err(eval_error(package_store, fir_store, call_stack, error)),
+    }
+}
+
+/// The result of advancing a `Debugger` with `step`.
+#[derive(Clone, Debug)]
+pub enum DebugStepResult {
+    /// A statement was executed and execution has not yet finished.
+    Completed,
+    /// Execution paused at a breakpoint on the statement with the given span.
+    Paused { span: Span },
+    /// Execution finished, producing the given value.
+    Return(Value),
 }
 
 /// Represents a stack frame for debugging.
@@ -983,7 +1179,7 @@ impl<'a> Visitor<'a> for BreakpointCollector<'a> {
     }
 }
 
-fn eval_error(
+pub(crate) fn eval_error(
     package_store: &PackageStore,
     fir_store: &fir::PackageStore,
     call_stack: Vec<Frame>,
@@ -729,6 +729,9 @@ fn internal_operation() {
                             ),
                         ),
                     ),
+                    code: Some(
+                        "Qsc.Resolve.NotFound",
+                    ),
                 },
             ),
         ]
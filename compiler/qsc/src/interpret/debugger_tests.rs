@@ -3,9 +3,9 @@
 
 #![allow(clippy::needless_raw_string_hashes)]
 
-use crate::interpret::Debugger;
+use crate::interpret::{DebugStepResult, Debugger};
 use crate::line_column::Encoding;
-use qsc_data_structures::language_features::LanguageFeatures;
+use qsc_data_structures::{language_features::LanguageFeatures, span::Span};
 use qsc_eval::{output::CursorReceiver, StepAction, StepResult};
 use qsc_fir::fir::StmtId;
 use qsc_frontend::compile::SourceMap;
@@ -219,5 +219,62 @@ mod given_debugger {
             expect_return(debugger, expected);
             Ok(())
         }
+
+        #[test]
+        fn breakpoint_in_loop_body_pauses_each_iteration(
+        ) -> Result<(), Vec<crate::interpret::Error>> {
+            let source = r#"
+                namespace Test {
+                    @EntryPoint()
+                    operation Main() : Int {
+                        mutable sum = 0;
+                        for i in 0..2 {
+                            set sum += i;
+                        }
+                        sum
+                    }
+                }"#;
+            let sources = SourceMap::new([("test".into(), source.into())], None);
+            let mut debugger = Debugger::new(
+                sources,
+                TargetCapabilityFlags::all(),
+                Encoding::Utf8,
+                LanguageFeatures::default(),
+            )?;
+            let stmt = "set sum += i;";
+            let lo = u32::try_from(source.find(stmt).expect("statement should be in source"))
+                .expect("offset should fit in u32");
+            let span = Span {
+                lo,
+                hi: lo + u32::try_from(stmt.len()).expect("length should fit in u32"),
+            };
+            debugger.set_breakpoints(&[span]);
+
+            let mut cursor = Cursor::new(Vec::<u8>::new());
+            let mut receiver = CursorReceiver::new(&mut cursor);
+            let mut paused_locals = Vec::new();
+            let value = loop {
+                match debugger.step(&mut receiver)? {
+                    DebugStepResult::Completed => {}
+                    DebugStepResult::Paused { span: paused } => {
+                        assert_eq!(paused, span);
+                        let locals = debugger
+                            .get_locals()
+                            .into_iter()
+                            .map(|v| format!("{} = {}", v.name, v.value))
+                            .collect::<Vec<_>>();
+                        paused_locals.push(locals.join(", "));
+                    }
+                    DebugStepResult::Return(value) => break value,
+                }
+            };
+
+            assert_eq!(value.to_string(), "3");
+            assert_eq!(
+                paused_locals,
+                ["sum = 0, i = 0", "sum = 0, i = 1", "sum = 1, i = 2"]
+            );
+            Ok(())
+        }
     }
 }
@@ -0,0 +1,139 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#[cfg(test)]
+mod tests;
+
+use num_bigint::BigInt;
+use qsc_eval::val::{self, Value};
+use qsc_fir::fir::Pauli;
+use serde_json::{json, Map};
+use std::{rc::Rc, str::FromStr};
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error, PartialEq)]
+pub enum JsonError {
+    #[error("values of type {0} cannot be represented as JSON")]
+    Unsupported(&'static str),
+    #[error("invalid JSON value representation: {0}")]
+    Invalid(String),
+}
+
+/// Converts a [`Value`] into a JSON value.
+///
+/// Each value is represented as an object with a `type` field naming the Q# type and a
+/// `value` field holding its contents. `BigInt` values are written as decimal strings to
+/// avoid loss of precision, and non-finite `Double` values are written as the strings
+/// `"NaN"`, `"Infinity"`, and `"-Infinity"`. Tuples and arrays hold a JSON array of their
+/// elements.
+///
+/// # Errors
+///
+/// Returns an error if the value, or any value nested within it, is a closure, callable,
+/// qubit, range, unmeasured result or variable.
+pub fn value_to_json(value: &Value) -> Result<serde_json::Value, JsonError> {
+    let contents = match value {
+        Value::Array(arr) => {
+            serde_json::Value::Array(arr.iter().map(value_to_json).collect::<Result<_, _>>()?)
+        }
+        Value::BigInt(v) => json!(v.to_string()),
+        Value::Bool(v) => json!(v),
+        Value::Double(v) if v.is_nan() => json!("NaN"),
+        Value::Double(v) if v.is_infinite() => {
+            json!(if *v > 0.0 { "Infinity" } else { "-Infinity" })
+        }
+        Value::Double(v) => json!(v),
+        Value::Int(v) => json!(v),
+        Value::Pauli(v) => json!(match v {
+            Pauli::I => "PauliI",
+            Pauli::X => "PauliX",
+            Pauli::Y => "PauliY",
+            Pauli::Z => "PauliZ",
+        }),
+        Value::Result(val::Result::Val(v)) => json!(if *v { "One" } else { "Zero" }),
+        Value::String(v) => json!(v.as_ref()),
+        Value::Tuple(tup) => {
+            serde_json::Value::Array(tup.iter().map(value_to_json).collect::<Result<_, _>>()?)
+        }
+        Value::Closure(..)
+        | Value::Global(..)
+        | Value::Qubit(_)
+        | Value::Range(..)
+        | Value::Result(val::Result::Id(_))
+        | Value::Var(_) => return Err(JsonError::Unsupported(value.type_name())),
+    };
+
+    let mut object = Map::new();
+    object.insert("type".into(), json!(value.type_name()));
+    object.insert("value".into(), contents);
+    Ok(serde_json::Value::Object(object))
+}
+
+/// Converts a JSON value produced by [`value_to_json`] back into a [`Value`].
+///
+/// # Errors
+///
+/// Returns an error if the JSON value does not follow the representation
+/// described in [`value_to_json`].
+pub fn value_from_json(json: &serde_json::Value) -> Result<Value, JsonError> {
+    let invalid = || JsonError::Invalid(json.to_string());
+    let ty = json
+        .get("type")
+        .and_then(serde_json::Value::as_str)
+        .ok_or_else(invalid)?;
+    let contents = json.get("value").ok_or_else(invalid)?;
+
+    match ty {
+        "Array" => {
+            let items = contents
+                .as_array()
+                .ok_or_else(invalid)?
+                .iter()
+                .map(value_from_json)
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(Value::Array(Rc::new(items)))
+        }
+        "BigInt" => contents
+            .as_str()
+            .and_then(|v| BigInt::from_str(v).ok())
+            .map(Value::BigInt)
+            .ok_or_else(invalid),
+        "Bool" => contents.as_bool().map(Value::Bool).ok_or_else(invalid),
+        "Double" => match contents {
+            serde_json::Value::String(v) => match v.as_str() {
+                "NaN" => Ok(Value::Double(f64::NAN)),
+                "Infinity" => Ok(Value::Double(f64::INFINITY)),
+                "-Infinity" => Ok(Value::Double(f64::NEG_INFINITY)),
+                _ => Err(invalid()),
+            },
+            _ => contents.as_f64().map(Value::Double).ok_or_else(invalid),
+        },
+        "Int" => contents.as_i64().map(Value::Int).ok_or_else(invalid),
+        "Pauli" => match contents.as_str() {
+            Some("PauliI") => Ok(Value::Pauli(Pauli::I)),
+            Some("PauliX") => Ok(Value::Pauli(Pauli::X)),
+            Some("PauliY") => Ok(Value::Pauli(Pauli::Y)),
+            Some("PauliZ") => Ok(Value::Pauli(Pauli::Z)),
+            _ => Err(invalid()),
+        },
+        "Result" => match contents.as_str() {
+            Some("Zero") => Ok(Value::RESULT_ZERO),
+            Some("One") => Ok(Value::RESULT_ONE),
+            _ => Err(invalid()),
+        },
+        "String" => contents
+            .as_str()
+            .map(|v| Value::String(v.into()))
+            .ok_or_else(invalid),
+        "Tuple" => {
+            let items = contents
+                .as_array()
+                .ok_or_else(invalid)?
+                .iter()
+                .map(value_from_json)
+                .collect::<Result<Rc<[_]>, _>>()?;
+            Ok(Value::Tuple(items))
+        }
+        _ => Err(invalid()),
+    }
+}
@@ -0,0 +1,93 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use super::{value_from_json, value_to_json, JsonError};
+use expect_test::expect;
+use num_bigint::BigInt;
+use qsc_eval::val::{Qubit, Value};
+use qsc_fir::fir::Pauli;
+use std::{rc::Rc, str::FromStr};
+
+fn round_trip(value: &Value) -> Value {
+    let json = value_to_json(value).expect("value should convert to json");
+    let text = json.to_string();
+    let parsed: serde_json::Value = serde_json::from_str(&text).expect("json should parse");
+    value_from_json(&parsed).expect("json should convert to value")
+}
+
+#[test]
+fn compound_value_round_trips() {
+    let value = Value::Tuple(
+        vec![
+            Value::Int(42),
+            Value::Array(Rc::new(vec![Value::Double(1.5), Value::Double(-0.25)])),
+            Value::BigInt(
+                BigInt::from_str("123456789012345678901234567890")
+                    .expect("static BigInt should parse"),
+            ),
+        ]
+        .into(),
+    );
+    assert_eq!(round_trip(&value), value);
+}
+
+#[test]
+fn compound_value_json_representation() {
+    let value = Value::Tuple(
+        vec![
+            Value::Int(42),
+            Value::Array(Rc::new(vec![Value::Double(1.5)])),
+            Value::BigInt(BigInt::from(7)),
+        ]
+        .into(),
+    );
+    let json = value_to_json(&value).expect("value should convert to json");
+    expect![[r#"{"type":"Tuple","value":[{"type":"Int","value":42},{"type":"Array","value":[{"type":"Double","value":1.5}]},{"type":"BigInt","value":"7"}]}"#]]
+        .assert_eq(&json.to_string());
+}
+
+#[test]
+fn nested_arrays_and_tuples_round_trip() {
+    let value = Value::Array(Rc::new(vec![
+        Value::Tuple(vec![Value::Bool(true), Value::RESULT_ONE].into()),
+        Value::Tuple(vec![Value::Bool(false), Value::RESULT_ZERO].into()),
+        Value::Tuple(vec![Value::Pauli(Pauli::Y), Value::String("hi".into())].into()),
+        Value::unit(),
+    ]));
+    assert_eq!(round_trip(&value), value);
+}
+
+#[test]
+fn non_finite_doubles_round_trip() {
+    for value in [f64::INFINITY, f64::NEG_INFINITY] {
+        assert_eq!(round_trip(&Value::Double(value)), Value::Double(value));
+    }
+    let Value::Double(nan) = round_trip(&Value::Double(f64::NAN)) else {
+        panic!("expected a double");
+    };
+    assert!(nan.is_nan());
+    expect![[r#"{"type":"Double","value":"NaN"}"#]].assert_eq(
+        &value_to_json(&Value::Double(f64::NAN))
+            .expect("value should convert to json")
+            .to_string(),
+    );
+}
+
+#[test]
+fn unsupported_value_is_error() {
+    let value = Value::Array(Rc::new(vec![Value::Qubit(Qubit(0))]));
+    assert_eq!(value_to_json(&value), Err(JsonError::Unsupported("Qubit")));
+}
+
+#[test]
+fn malformed_json_is_error() {
+    let json = serde_json::json!({ "type": "Int", "value": "five" });
+    expect![[r#"
+        Err(
+            Invalid(
+                "{\"type\":\"Int\",\"value\":\"five\"}",
+            ),
+        )
+    "#]]
+    .assert_debug_eq(&value_from_json(&json));
+}
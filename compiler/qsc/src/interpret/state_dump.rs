@@ -0,0 +1,77 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use num_bigint::BigUint;
+use num_complex::Complex64;
+use qsc_eval::output::{Error, Receiver};
+use serde_json::json;
+
+/// The format in which `DumpMachine` and similar calls report the simulator state.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum StateDumpFormat {
+    /// The state is passed to [`Receiver::state`] unchanged, which by default renders
+    /// it as a human-readable table.
+    #[default]
+    Table,
+    /// The state is serialized as JSON and passed to [`Receiver::message`].
+    ///
+    /// The output is an object with a `qubitCount` field and an `amplitudes` array,
+    /// holding one `{ "basisState": "<index>", "re": <re>, "im": <im> }` entry per basis
+    /// state in ascending order of index. Basis state indices are written as decimal
+    /// strings to avoid loss of precision. Amplitudes whose magnitude is below
+    /// `threshold` are omitted.
+    Json { threshold: f64 },
+}
+
+/// Converts a state dump into the JSON form described by [`StateDumpFormat::Json`].
+#[must_use]
+pub fn state_to_json(
+    mut state: Vec<(BigUint, Complex64)>,
+    qubit_count: usize,
+    threshold: f64,
+) -> serde_json::Value {
+    state.retain(|(_, amplitude)| amplitude.norm() >= threshold);
+    state.sort_by(|(a, _), (b, _)| a.cmp(b));
+    let amplitudes = state
+        .into_iter()
+        .map(|(id, amplitude)| {
+            json!({
+                "basisState": id.to_string(),
+                "re": amplitude.re,
+                "im": amplitude.im,
+            })
+        })
+        .collect::<Vec<_>>();
+    json!({
+        "qubitCount": qubit_count,
+        "amplitudes": amplitudes,
+    })
+}
+
+/// A receiver that forwards all output to an inner receiver, rewriting state dumps
+/// according to the given [`StateDumpFormat`].
+pub(super) struct StateDumpReceiver<'a, R: Receiver> {
+    inner: &'a mut R,
+    format: StateDumpFormat,
+}
+
+impl<'a, R: Receiver> StateDumpReceiver<'a, R> {
+    pub(super) fn new(inner: &'a mut R, format: StateDumpFormat) -> Self {
+        Self { inner, format }
+    }
+}
+
+impl<R: Receiver> Receiver for StateDumpReceiver<'_, R> {
+    fn state(&mut self, state: Vec<(BigUint, Complex64)>, qubit_count: usize) -> Result<(), Error> {
+        match self.format {
+            StateDumpFormat::Table => self.inner.state(state, qubit_count),
+            StateDumpFormat::Json { threshold } => self
+                .inner
+                .message(&state_to_json(state, qubit_count, threshold).to_string()),
+        }
+    }
+
+    fn message(&mut self, msg: &str) -> Result<(), Error> {
+        self.inner.message(msg)
+    }
+}
@@ -1450,10 +1450,10 @@ mod given_interpreter {
 
     #[cfg(test)]
     mod with_sources {
-        use std::{sync::Arc, vec};
+        use std::{sync::Arc, time::Duration, vec};
 
         use super::*;
-        use crate::interpret::Debugger;
+        use crate::interpret::{Debugger, StateDumpFormat};
         use crate::line_column::Encoding;
         use expect_test::expect;
         use indoc::indoc;
@@ -1818,5 +1818,323 @@ mod given_interpreter {
                 entry: None,
             }
         }
+
+        fn run_seeded_coin_flips(seed: u64) -> Value {
+            let source = indoc! { r#"
+            namespace Test {
+                @EntryPoint()
+                operation Main() : (Result[], Int) {
+                    use qs = Qubit[16];
+                    ApplyToEach(H, qs);
+                    (MResetEachZ(qs), Microsoft.Quantum.Random.DrawRandomInt(0, 1000000))
+                }
+            }"#};
+
+            let sources = SourceMap::new([("test".into(), source.into())], None);
+            let mut interpreter = Interpreter::new(
+                true,
+                sources,
+                PackageType::Exe,
+                TargetCapabilityFlags::all(),
+                LanguageFeatures::default(),
+            )
+            .expect("interpreter should be created")
+            .with_seed(seed);
+
+            let (result, _) = entry(&mut interpreter);
+            result.expect("entry should run successfully")
+        }
+
+        #[test]
+        fn same_seed_produces_same_results() {
+            assert_eq!(run_seeded_coin_flips(42), run_seeded_coin_flips(42));
+        }
+
+        #[test]
+        fn different_seeds_produce_different_results() {
+            // 16 measurements and a random integer all agreeing by chance is vanishingly unlikely.
+            assert_ne!(run_seeded_coin_flips(42), run_seeded_coin_flips(43));
+        }
+
+        #[test]
+        fn sample_h_measurement_produces_balanced_histogram() {
+            let source = indoc! { r#"
+            namespace Test {
+                operation Flip() : Result {
+                    use q = Qubit();
+                    H(q);
+                    MResetZ(q)
+                }
+            }"#};
+
+            let sources = SourceMap::new([("test".into(), source.into())], None);
+            let mut interpreter = Interpreter::new(
+                true,
+                sources,
+                PackageType::Lib,
+                TargetCapabilityFlags::all(),
+                LanguageFeatures::default(),
+            )
+            .expect("interpreter should be created")
+            .with_seed(42);
+
+            let histogram = interpreter
+                .sample("Test.Flip()", 1000)
+                .expect("sampling should succeed");
+
+            assert_eq!(histogram.len(), 2, "unexpected outcomes: {histogram:?}");
+            let zeros = histogram["Zero"];
+            let ones = histogram["One"];
+            assert_eq!(zeros + ones, 1000);
+            assert!((400..=600).contains(&zeros), "unbalanced: {histogram:?}");
+        }
+
+        #[test]
+        fn sample_with_same_seed_is_reproducible() {
+            let mut interpreter = get_interpreter().with_seed(7);
+            let first = interpreter
+                .sample("{ use q = Qubit(); H(q); MResetZ(q) }", 100)
+                .expect("sampling should succeed");
+            let second = interpreter
+                .sample("{ use q = Qubit(); H(q); MResetZ(q) }", 100)
+                .expect("sampling should succeed");
+            assert_eq!(first, second);
+        }
+
+        #[test]
+        fn run_with_timeout_stops_runaway_loop() {
+            let mut interpreter = get_interpreter();
+            let mut cursor = Cursor::new(Vec::<u8>::new());
+            let mut receiver = CursorReceiver::new(&mut cursor);
+            // A timeout of zero has already elapsed when evaluation first checks it,
+            // so the test does not depend on how fast the loop runs.
+            let result = interpreter
+                .run_with_timeout(
+                    &mut receiver,
+                    "{ mutable i = 0; while true { set i += 1; } i }",
+                    Duration::ZERO,
+                )
+                .expect("compilation should succeed");
+            is_only_error(
+                &result,
+                &receiver.dump(),
+                &expect![[r#"
+                    runtime error: evaluation timed out
+                      evaluation was stopped here [<entry>] [set i += 1]
+                "#]],
+            );
+
+            // The interpreter is still usable after the timed out run.
+            let (result, output) = run(&mut interpreter, "1 + 2");
+            is_only_value(
+                &result.expect("compilation should succeed"),
+                &output,
+                &Value::Int(3),
+            );
+        }
+
+        #[test]
+        fn run_with_timeout_stops_runaway_recursion() {
+            let source = indoc! { r#"
+            namespace Test {
+                function Spin(n : Int) : Int {
+                    if n == 0 { 0 } else { Spin(n - 1) + Spin(n - 1) }
+                }
+            }"#};
+
+            let sources = SourceMap::new([("test".into(), source.into())], None);
+            let mut interpreter = Interpreter::new(
+                true,
+                sources,
+                PackageType::Lib,
+                TargetCapabilityFlags::all(),
+                LanguageFeatures::default(),
+            )
+            .expect("interpreter should be created");
+            let mut cursor = Cursor::new(Vec::<u8>::new());
+            let mut receiver = CursorReceiver::new(&mut cursor);
+            let result = interpreter
+                .run_with_timeout(&mut receiver, "Test.Spin(64)", Duration::ZERO)
+                .expect("compilation should succeed");
+            let errors = result.expect_err("evaluation should time out");
+            assert!(
+                matches!(
+                    &errors[..],
+                    [Error::Eval(error)] if matches!(error.error().error(), qsc_eval::Error::TimedOut(_))
+                ),
+                "expected a single timeout error, got {errors:?}"
+            );
+        }
+
+        #[test]
+        fn run_with_timeout_returns_value_within_budget() {
+            let mut interpreter = get_interpreter();
+            let mut cursor = Cursor::new(Vec::<u8>::new());
+            let mut receiver = CursorReceiver::new(&mut cursor);
+            let result = interpreter
+                .run_with_timeout(
+                    &mut receiver,
+                    "{ mutable sum = 0; for i in 1..10 { set sum += i; } sum }",
+                    Duration::from_secs(10),
+                )
+                .expect("compilation should succeed");
+            is_only_value(&result, &receiver.dump(), &Value::Int(55));
+        }
+
+        fn get_recursive_interpreter() -> Interpreter {
+            let source = indoc! { r#"
+            namespace Test {
+                function Forever(n : Int) : Int {
+                    Forever(n + 1)
+                }
+                function Depth(n : Int) : Int {
+                    if n == 0 { 0 } else { 1 + Depth(n - 1) }
+                }
+            }"#};
+
+            let sources = SourceMap::new([("test".into(), source.into())], None);
+            Interpreter::new(
+                true,
+                sources,
+                PackageType::Lib,
+                TargetCapabilityFlags::all(),
+                LanguageFeatures::default(),
+            )
+            .expect("interpreter should be created")
+        }
+
+        #[test]
+        fn unbounded_recursion_exceeds_recursion_limit() {
+            let mut interpreter = get_recursive_interpreter();
+            interpreter.set_max_recursion_depth(100);
+            let (result, output) = run(&mut interpreter, "Test.Forever(0)");
+            is_only_error(
+                &result.expect("compilation should succeed"),
+                &output,
+                &expect![[r#"
+                    runtime error: recursion limit of 100 nested calls exceeded
+                      this call exceeded the limit [test] [Forever(n + 1)]
+                "#]],
+            );
+
+            // The interpreter is still usable after the failed run.
+            let (result, output) = run(&mut interpreter, "Test.Depth(10)");
+            is_only_value(
+                &result.expect("compilation should succeed"),
+                &output,
+                &Value::Int(10),
+            );
+        }
+
+        #[test]
+        fn unbounded_recursion_stops_at_default_recursion_limit() {
+            let mut interpreter = get_recursive_interpreter();
+            let (result, _) = run(&mut interpreter, "Test.Forever(0)");
+            let errors = result
+                .expect("compilation should succeed")
+                .expect_err("evaluation should exceed the recursion limit");
+            assert!(
+                matches!(
+                    &errors[..],
+                    [Error::Eval(error)] if matches!(
+                        error.error().error(),
+                        qsc_eval::Error::RecursionLimitExceeded(qsc_eval::DEFAULT_MAX_RECURSION_DEPTH, _)
+                    )
+                ),
+                "expected a single recursion limit error, got {errors:?}"
+            );
+        }
+
+        #[test]
+        fn deep_finite_recursion_succeeds_under_raised_limit() {
+            let mut interpreter = get_recursive_interpreter();
+            let (result, _) = run(&mut interpreter, "Test.Depth(20000)");
+            assert!(
+                result.expect("compilation should succeed").is_err(),
+                "the default limit should be lower than the recursion depth"
+            );
+
+            interpreter.set_max_recursion_depth(50_000);
+            let (result, output) = run(&mut interpreter, "Test.Depth(20000)");
+            is_only_value(
+                &result.expect("compilation should succeed"),
+                &output,
+                &Value::Int(20000),
+            );
+        }
+
+        fn dump_state_as_json(threshold: f64, expr: &str) -> serde_json::Value {
+            let mut interpreter = Interpreter::new(
+                true,
+                SourceMap::default(),
+                PackageType::Lib,
+                TargetCapabilityFlags::all(),
+                LanguageFeatures::default(),
+            )
+            .expect("interpreter should be created");
+            interpreter.set_state_dump_format(StateDumpFormat::Json { threshold });
+            let (result, output) = run(&mut interpreter, expr);
+            let value = result
+                .expect("compilation should succeed")
+                .expect("evaluation should succeed");
+            assert_eq!(value, Value::unit());
+            serde_json::from_str(&output).expect("state dump should be valid JSON")
+        }
+
+        #[test]
+        fn dump_machine_as_json_reports_bell_state_amplitudes() {
+            let state = dump_state_as_json(
+                1e-9,
+                indoc! {"{
+                    use qs = Qubit[2];
+                    H(qs[0]);
+                    CNOT(qs[0], qs[1]);
+                    Microsoft.Quantum.Diagnostics.DumpMachine();
+                    ResetAll(qs);
+                }"},
+            );
+            assert_eq!(state["qubitCount"], 2);
+            let amplitudes = state["amplitudes"]
+                .as_array()
+                .expect("amplitudes should be an array");
+            let basis_states = amplitudes
+                .iter()
+                .map(|entry| {
+                    entry["basisState"]
+                        .as_str()
+                        .expect("basis state should be a string")
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(basis_states, ["0", "3"]);
+            for entry in amplitudes {
+                let re = entry["re"].as_f64().expect("re should be a number");
+                let im = entry["im"].as_f64().expect("im should be a number");
+                assert!((re - std::f64::consts::FRAC_1_SQRT_2).abs() < 1e-9);
+                assert!(im.abs() < 1e-9);
+            }
+        }
+
+        #[test]
+        fn dump_machine_as_json_omits_amplitudes_below_threshold() {
+            let expr = indoc! {"{
+                use q = Qubit();
+                Ry(0.01, q);
+                Microsoft.Quantum.Diagnostics.DumpMachine();
+                Reset(q);
+            }"};
+
+            let state = dump_state_as_json(1e-9, expr);
+            let amplitudes = state["amplitudes"]
+                .as_array()
+                .expect("amplitudes should be an array");
+            assert_eq!(amplitudes.len(), 2);
+
+            let state = dump_state_as_json(0.01, expr);
+            let amplitudes = state["amplitudes"]
+                .as_array()
+                .expect("amplitudes should be an array");
+            assert_eq!(amplitudes.len(), 1);
+            assert_eq!(amplitudes[0]["basisState"], "0");
+        }
     }
 }
@@ -3,6 +3,7 @@
 
 pub mod codegen;
 pub mod compile;
+pub mod const_eval;
 pub mod error;
 pub mod incremental;
 pub mod interpret;
@@ -29,6 +30,13 @@ pub mod ast {
     pub use qsc_ast::{ast::*, *};
 }
 
+pub mod lex {
+    pub use qsc_frontend::lex::{
+        concrete::{ConcreteToken, ConcreteTokenIterator, ConcreteTokenKind},
+        cooked::TokenKind,
+    };
+}
+
 pub mod project {
     pub use qsc_project::{DirEntry, EntryType, FileSystem, Manifest, ManifestDescriptor};
 }
@@ -1,7 +1,10 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
-use std::str::FromStr;
+#[cfg(test)]
+mod tests;
+
+use std::{fmt::Display, str::FromStr};
 
 use qsc_data_structures::target::TargetCapabilityFlags;
 
@@ -13,6 +16,10 @@ pub enum Profile {
 }
 
 impl Profile {
+    /// All the profiles, in the order they are listed in error messages.
+    pub const ALL: [Profile; 3] = [Profile::Unrestricted, Profile::Base, Profile::Quantinuum];
+
+    /// Returns the canonical name of the profile, which `from_str` accepts.
     #[must_use]
     pub fn to_str(&self) -> &'static str {
         match self {
@@ -21,6 +28,15 @@ impl Profile {
             Self::Quantinuum => "Quantinuum",
         }
     }
+
+    /// Returns the least capable profile that supports all of the given capabilities.
+    #[must_use]
+    pub fn minimal_for(capabilities: TargetCapabilityFlags) -> Self {
+        [Self::Base, Self::Quantinuum]
+            .into_iter()
+            .find(|profile| TargetCapabilityFlags::from(*profile).contains(capabilities))
+            .unwrap_or(Self::Unrestricted)
+    }
 }
 
 impl From<Profile> for TargetCapabilityFlags {
@@ -33,15 +49,42 @@ impl From<Profile> for TargetCapabilityFlags {
     }
 }
 
+impl Display for Profile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
+
 impl FromStr for Profile {
-    type Err = ();
+    type Err = ParseProfileError;
 
+    /// Parses a profile from its canonical name, ignoring case.
+    /// `Adaptive_RI` is accepted as an alias for `Quantinuum`,
+    /// since both name the adaptive profile with integer computations and qubit reset.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "Quantinuum" | "quantinuum" => Ok(Self::Quantinuum),
-            "Base" | "base" => Ok(Self::Base),
-            "Unrestricted" | "unrestricted" => Ok(Self::Unrestricted),
-            _ => Err(()),
+        if s.eq_ignore_ascii_case("Adaptive_RI") {
+            return Ok(Self::Quantinuum);
         }
+        Self::ALL
+            .into_iter()
+            .find(|profile| s.eq_ignore_ascii_case(profile.to_str()))
+            .ok_or_else(|| ParseProfileError(s.to_string()))
+    }
+}
+
+/// The error returned when parsing an unknown target profile name.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseProfileError(String);
+
+impl Display for ParseProfileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let valid = Profile::ALL.map(|profile| profile.to_str()).join(", ");
+        write!(
+            f,
+            "invalid target profile `{}`, expected one of: {valid}",
+            self.0
+        )
     }
 }
+
+impl std::error::Error for ParseProfileError {}
@@ -0,0 +1,59 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use super::Profile;
+use qsc_data_structures::target::TargetCapabilityFlags;
+use std::str::FromStr;
+
+#[test]
+fn canonical_names_round_trip() {
+    for profile in Profile::ALL {
+        assert_eq!(Profile::from_str(&profile.to_string()), Ok(profile));
+    }
+}
+
+#[test]
+fn names_are_case_insensitive() {
+    assert_eq!(Profile::from_str("base"), Ok(Profile::Base));
+    assert_eq!(Profile::from_str("BASE"), Ok(Profile::Base));
+    assert_eq!(Profile::from_str("unrestricted"), Ok(Profile::Unrestricted));
+    assert_eq!(Profile::from_str("QUANTINUUM"), Ok(Profile::Quantinuum));
+}
+
+#[test]
+fn adaptive_ri_is_an_alias_for_quantinuum() {
+    assert_eq!(Profile::from_str("Adaptive_RI"), Ok(Profile::Quantinuum));
+    assert_eq!(Profile::from_str("adaptive_ri"), Ok(Profile::Quantinuum));
+    assert_eq!(Profile::Quantinuum.to_string(), "Quantinuum");
+}
+
+#[test]
+fn invalid_name_lists_valid_names() {
+    let error = Profile::from_str("Adaptive_RIF").expect_err("profile should not parse");
+    assert_eq!(
+        error.to_string(),
+        "invalid target profile `Adaptive_RIF`, expected one of: Unrestricted, Base, Quantinuum"
+    );
+}
+
+#[test]
+fn minimal_profile_for_capabilities() {
+    assert_eq!(
+        Profile::minimal_for(TargetCapabilityFlags::empty()),
+        Profile::Base
+    );
+    assert_eq!(
+        Profile::minimal_for(TargetCapabilityFlags::Adaptive),
+        Profile::Quantinuum
+    );
+    assert_eq!(
+        Profile::minimal_for(
+            TargetCapabilityFlags::Adaptive | TargetCapabilityFlags::IntegerComputations
+        ),
+        Profile::Quantinuum
+    );
+    assert_eq!(
+        Profile::minimal_for(TargetCapabilityFlags::FloatingPointComputations),
+        Profile::Unrestricted
+    );
+}
@@ -1,6 +1,7 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
+pub mod qasm3;
 pub mod qir;
 pub mod qir_base;
 pub mod qsharp;
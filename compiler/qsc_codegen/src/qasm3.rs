@@ -0,0 +1,329 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#[cfg(test)]
+mod tests;
+
+use num_bigint::BigUint;
+use num_complex::Complex;
+use qsc_eval::{backend::Backend, debug::Frame, eval, output::GenericReceiver, val::Value, Env};
+use qsc_frontend::compile::PackageStore;
+use qsc_hir::hir;
+use qsc_lowerer::map_hir_package_to_fir;
+use std::fmt::{self, Display, Write};
+
+/// An error encountered while exporting a program to `OpenQASM` 3.
+#[derive(Clone, Debug)]
+pub enum Error {
+    /// The program applies a gate that has no equivalent in the `OpenQASM` 3 standard library.
+    UnsupportedGate(String),
+    /// The program failed to evaluate.
+    Eval(qsc_eval::Error, Vec<Frame>),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::UnsupportedGate(name) => {
+                write!(f, "gate `{name}` has no OpenQASM 3 equivalent")
+            }
+            Error::Eval(error, _) => write!(f, "{error}"),
+        }
+    }
+}
+
+/// Exports the entry expression of a package to an `OpenQASM` 3 program.
+///
+/// The program is evaluated against a backend that records each gate,
+/// so it must describe a static circuit, such as a program that
+/// passes the Base profile checks.
+///
+/// # Errors
+///
+/// Returns one error per gate without an `OpenQASM` 3 equivalent,
+/// or the evaluation error if execution was unable to complete.
+pub fn generate_qasm3(
+    store: &PackageStore,
+    package: hir::PackageId,
+) -> std::result::Result<String, Vec<Error>> {
+    let mut fir_store = qsc_fir::fir::PackageStore::new();
+    for (id, unit) in store {
+        fir_store.insert(
+            map_hir_package_to_fir(id),
+            qsc_lowerer::Lowerer::new().lower_package(&unit.package),
+        );
+    }
+
+    let package = map_hir_package_to_fir(package);
+    let unit = fir_store.get(package);
+
+    let mut sim = Qasm3Sim::default();
+    let mut stdout = std::io::sink();
+    let mut out = GenericReceiver::new(&mut stdout);
+    let result = eval(
+        package,
+        None,
+        unit.entry_exec_graph.clone(),
+        &fir_store,
+        &mut Env::default(),
+        &mut sim,
+        &mut out,
+    );
+    match result {
+        Ok(_) => sim.finish(),
+        Err((err, stack)) => {
+            let mut errors = sim.unsupported;
+            if errors.is_empty() {
+                // Unsupported intrinsics stop evaluation, so the evaluation error
+                // is only reported when it is not already explained by one of them.
+                errors.push(Error::Eval(err, stack));
+            }
+            Err(errors)
+        }
+    }
+}
+
+/// The definitions of the two-qubit rotations, which are not part of the
+/// `OpenQASM` 3 standard library, in terms of the gates that are.
+const RXX_DEFINITION: &str =
+    "gate rxx(theta) a, b { h a; h b; cx a, b; rz(theta) b; cx a, b; h a; h b; }";
+const RYY_DEFINITION: &str = "gate ryy(theta) a, b { rx(pi / 2) a; rx(pi / 2) b; cx a, b; rz(theta) b; cx a, b; rx(-pi / 2) a; rx(-pi / 2) b; }";
+const RZZ_DEFINITION: &str = "gate rzz(theta) a, b { cx a, b; rz(theta) b; cx a, b; }";
+
+/// A backend that records the gates applied to it as `OpenQASM` 3 statements.
+#[derive(Default)]
+pub struct Qasm3Sim {
+    instrs: String,
+    /// The definitions of the gates outside the standard library that the program applies.
+    definitions: Vec<&'static str>,
+    /// The qubits that have been released and can be reused.
+    free_qubits: Vec<usize>,
+    num_qubits: usize,
+    num_measurements: usize,
+    unsupported: Vec<Error>,
+}
+
+impl Qasm3Sim {
+    /// Returns the recorded `OpenQASM` 3 program, or an error for each
+    /// unsupported gate that was applied.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for each gate without an `OpenQASM` 3 equivalent.
+    pub fn finish(self) -> std::result::Result<String, Vec<Error>> {
+        if !self.unsupported.is_empty() {
+            return Err(self.unsupported);
+        }
+
+        let mut program = String::new();
+        writeln!(program, "OPENQASM 3.0;").expect("writing to string should succeed");
+        writeln!(program, "include \"stdgates.inc\";").expect("writing to string should succeed");
+        for definition in &self.definitions {
+            writeln!(program, "{definition}").expect("writing to string should succeed");
+        }
+        if self.num_qubits > 0 {
+            writeln!(program, "qubit[{}] q;", self.num_qubits)
+                .expect("writing to string should succeed");
+        }
+        if self.num_measurements > 0 {
+            writeln!(program, "bit[{}] c;", self.num_measurements)
+                .expect("writing to string should succeed");
+        }
+        program.push_str(&self.instrs);
+        Ok(program)
+    }
+
+    fn gate(&mut self, name: &str, qubits: &[usize]) {
+        self.gate_with_angle(name, None, qubits);
+    }
+
+    fn gate_with_angle(&mut self, name: &str, theta: Option<f64>, qubits: &[usize]) {
+        write!(self.instrs, "{name}").expect("writing to string should succeed");
+        if let Some(theta) = theta {
+            write!(self.instrs, "({})", Angle(theta)).expect("writing to string should succeed");
+        }
+        let qubits = qubits
+            .iter()
+            .map(|q| Qubit(*q).to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        writeln!(self.instrs, " {qubits};").expect("writing to string should succeed");
+    }
+
+    /// Records a gate that is not in the standard library, adding its definition
+    /// to the program the first time it is applied.
+    fn defined_gate_with_angle(
+        &mut self,
+        name: &str,
+        definition: &'static str,
+        theta: f64,
+        qubits: &[usize],
+    ) {
+        if !self.definitions.contains(&definition) {
+            self.definitions.push(definition);
+        }
+        self.gate_with_angle(name, Some(theta), qubits);
+    }
+
+    fn measure(&mut self, q: usize) -> usize {
+        let id = self.num_measurements;
+        self.num_measurements += 1;
+        writeln!(self.instrs, "c[{id}] = measure {};", Qubit(q))
+            .expect("writing to string should succeed");
+        id
+    }
+
+    fn unsupported(&mut self, name: &str) {
+        self.unsupported
+            .push(Error::UnsupportedGate(name.to_string()));
+    }
+}
+
+impl Backend for Qasm3Sim {
+    type ResultType = usize;
+
+    fn ccx(&mut self, ctl0: usize, ctl1: usize, q: usize) {
+        self.gate("ccx", &[ctl0, ctl1, q]);
+    }
+
+    fn cx(&mut self, ctl: usize, q: usize) {
+        self.gate("cx", &[ctl, q]);
+    }
+
+    fn cy(&mut self, ctl: usize, q: usize) {
+        self.gate("cy", &[ctl, q]);
+    }
+
+    fn cz(&mut self, ctl: usize, q: usize) {
+        self.gate("cz", &[ctl, q]);
+    }
+
+    fn h(&mut self, q: usize) {
+        self.gate("h", &[q]);
+    }
+
+    fn m(&mut self, q: usize) -> Self::ResultType {
+        self.measure(q)
+    }
+
+    fn mresetz(&mut self, q: usize) -> Self::ResultType {
+        let id = self.measure(q);
+        self.reset(q);
+        id
+    }
+
+    fn reset(&mut self, q: usize) {
+        self.gate("reset", &[q]);
+    }
+
+    fn rx(&mut self, theta: f64, q: usize) {
+        self.gate_with_angle("rx", Some(theta), &[q]);
+    }
+
+    fn rxx(&mut self, theta: f64, q0: usize, q1: usize) {
+        self.defined_gate_with_angle("rxx", RXX_DEFINITION, theta, &[q0, q1]);
+    }
+
+    fn ry(&mut self, theta: f64, q: usize) {
+        self.gate_with_angle("ry", Some(theta), &[q]);
+    }
+
+    fn ryy(&mut self, theta: f64, q0: usize, q1: usize) {
+        self.defined_gate_with_angle("ryy", RYY_DEFINITION, theta, &[q0, q1]);
+    }
+
+    fn rz(&mut self, theta: f64, q: usize) {
+        self.gate_with_angle("rz", Some(theta), &[q]);
+    }
+
+    fn rzz(&mut self, theta: f64, q0: usize, q1: usize) {
+        self.defined_gate_with_angle("rzz", RZZ_DEFINITION, theta, &[q0, q1]);
+    }
+
+    fn sadj(&mut self, q: usize) {
+        self.gate("sdg", &[q]);
+    }
+
+    fn s(&mut self, q: usize) {
+        self.gate("s", &[q]);
+    }
+
+    fn swap(&mut self, q0: usize, q1: usize) {
+        self.gate("swap", &[q0, q1]);
+    }
+
+    fn tadj(&mut self, q: usize) {
+        self.gate("tdg", &[q]);
+    }
+
+    fn t(&mut self, q: usize) {
+        self.gate("t", &[q]);
+    }
+
+    fn x(&mut self, q: usize) {
+        self.gate("x", &[q]);
+    }
+
+    fn y(&mut self, q: usize) {
+        self.gate("y", &[q]);
+    }
+
+    fn z(&mut self, q: usize) {
+        self.gate("z", &[q]);
+    }
+
+    fn qubit_allocate(&mut self) -> usize {
+        self.free_qubits.pop().unwrap_or_else(|| {
+            let q = self.num_qubits;
+            self.num_qubits += 1;
+            q
+        })
+    }
+
+    fn qubit_release(&mut self, q: usize) {
+        self.free_qubits.push(q);
+    }
+
+    fn capture_quantum_state(&mut self) -> (Vec<(BigUint, Complex<f64>)>, usize) {
+        (Vec::new(), 0)
+    }
+
+    fn qubit_is_zero(&mut self, _q: usize) -> bool {
+        // Because `qubit_is_zero` is called on every qubit release, this must return
+        // true to avoid a panic.
+        true
+    }
+
+    fn custom_intrinsic(
+        &mut self,
+        name: &str,
+        _arg: Value,
+    ) -> Option<std::result::Result<Value, String>> {
+        self.unsupported(name);
+        Some(Err(format!(
+            "intrinsic `{name}` has no OpenQASM 3 equivalent"
+        )))
+    }
+}
+
+struct Qubit(usize);
+
+impl Display for Qubit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "q[{}]", self.0)
+    }
+}
+
+struct Angle(f64);
+
+impl Display for Angle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let v = self.0;
+        if (v.floor() - v.ceil()).abs() < f64::EPSILON {
+            // Whole numbers are written with a decimal point so they are read as angles.
+            write!(f, "{v:.1}")
+        } else {
+            write!(f, "{v}")
+        }
+    }
+}
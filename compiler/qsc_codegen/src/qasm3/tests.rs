@@ -0,0 +1,177 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#![allow(clippy::needless_raw_string_hashes)]
+
+use std::sync::Arc;
+
+use expect_test::{expect, Expect};
+use indoc::indoc;
+use qsc_data_structures::{language_features::LanguageFeatures, target::TargetCapabilityFlags};
+use qsc_frontend::compile::{self, compile, PackageStore, SourceMap};
+use qsc_passes::{run_core_passes, run_default_passes, PackageType};
+
+use crate::qasm3::generate_qasm3;
+
+fn check(program: &str, expr: Option<&str>, expect: &Expect) {
+    let mut core = compile::core();
+    assert!(run_core_passes(&mut core).is_empty());
+    let mut store = PackageStore::new(core);
+    let mut std = compile::std(&store, TargetCapabilityFlags::empty());
+    assert!(run_default_passes(
+        store.core(),
+        &mut std,
+        PackageType::Lib,
+        TargetCapabilityFlags::empty()
+    )
+    .is_empty());
+    let std = store.insert(std);
+
+    let expr_as_arc: Option<Arc<str>> = expr.map(|s| Arc::from(s.to_string()));
+    let sources = SourceMap::new([("test".into(), program.into())], expr_as_arc);
+
+    let mut unit = compile(
+        &store,
+        &[std],
+        sources,
+        TargetCapabilityFlags::empty(),
+        LanguageFeatures::default(),
+    );
+    assert!(unit.errors.is_empty(), "{:?}", unit.errors);
+    assert!(run_default_passes(
+        store.core(),
+        &mut unit,
+        PackageType::Exe,
+        TargetCapabilityFlags::empty()
+    )
+    .is_empty());
+    let package = store.insert(unit);
+
+    match generate_qasm3(&store, package) {
+        Ok(qasm) => expect.assert_eq(&qasm),
+        Err(errors) => expect.assert_eq(
+            &errors
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("\n"),
+        ),
+    }
+}
+
+#[test]
+fn bell_state() {
+    check(
+        indoc! {r#"
+        namespace Sample {
+            @EntryPoint()
+            operation Bell() : (Result, Result) {
+                use (q0, q1) = (Qubit(), Qubit());
+                H(q0);
+                CNOT(q0, q1);
+                (MResetZ(q0), MResetZ(q1))
+            }
+        }
+        "#},
+        None,
+        &expect![[r#"
+            OPENQASM 3.0;
+            include "stdgates.inc";
+            qubit[2] q;
+            bit[2] c;
+            h q[0];
+            cx q[0], q[1];
+            c[0] = measure q[0];
+            reset q[0];
+            c[1] = measure q[1];
+            reset q[1];
+        "#]],
+    );
+}
+
+#[test]
+fn rotations_and_adjoints() {
+    check(
+        indoc! {r#"
+        namespace Sample {
+            operation Rotate() : Result {
+                use q = Qubit();
+                Rx(1.0, q);
+                Rz(0.5, q);
+                Adjoint S(q);
+                Adjoint T(q);
+                MResetZ(q)
+            }
+        }
+        "#},
+        Some("Sample.Rotate()"),
+        &expect![[r#"
+            OPENQASM 3.0;
+            include "stdgates.inc";
+            qubit[1] q;
+            bit[1] c;
+            rx(1.0) q[0];
+            rz(0.5) q[0];
+            sdg q[0];
+            tdg q[0];
+            c[0] = measure q[0];
+            reset q[0];
+        "#]],
+    );
+}
+
+#[test]
+fn two_qubit_rotations_are_defined_once() {
+    check(
+        indoc! {r#"
+        namespace Sample {
+            operation Entangle() : Result[] {
+                use (q0, q1) = (Qubit(), Qubit());
+                Rzz(1.0, q0, q1);
+                Rxx(0.5, q0, q1);
+                Rzz(2.0, q1, q0);
+                Ryy(1.5, q0, q1);
+                [MResetZ(q0), MResetZ(q1)]
+            }
+        }
+        "#},
+        Some("Sample.Entangle()"),
+        &expect![[r#"
+            OPENQASM 3.0;
+            include "stdgates.inc";
+            gate rzz(theta) a, b { cx a, b; rz(theta) b; cx a, b; }
+            gate rxx(theta) a, b { h a; h b; cx a, b; rz(theta) b; cx a, b; h a; h b; }
+            gate ryy(theta) a, b { rx(pi / 2) a; rx(pi / 2) b; cx a, b; rz(theta) b; cx a, b; rx(-pi / 2) a; rx(-pi / 2) b; }
+            qubit[2] q;
+            bit[2] c;
+            rzz(1.0) q[0], q[1];
+            rxx(0.5) q[0], q[1];
+            rzz(2.0) q[1], q[0];
+            ryy(1.5) q[0], q[1];
+            c[0] = measure q[0];
+            reset q[0];
+            c[1] = measure q[1];
+            reset q[1];
+        "#]],
+    );
+}
+
+#[test]
+fn unsupported_intrinsic_is_reported() {
+    check(
+        indoc! {r#"
+        namespace Sample {
+            operation Custom(q : Qubit) : Unit {
+                body intrinsic;
+            }
+            operation Apply() : Result {
+                use q = Qubit();
+                Custom(q);
+                MResetZ(q)
+            }
+        }
+        "#},
+        Some("Sample.Apply()"),
+        &expect!["gate `Custom` has no OpenQASM 3 equivalent"],
+    );
+}
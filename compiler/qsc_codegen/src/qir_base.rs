@@ -35,7 +35,21 @@ pub fn generate_qir(
         );
     }
 
-    let package = map_hir_package_to_fir(package);
+    generate_qir_from_fir(&fir_store, map_hir_package_to_fir(package))
+}
+
+/// Like [`generate_qir`], but for a package store that has already been lowered to FIR.
+///
+/// # Errors
+///
+/// This function will return an error if execution was unable to complete.
+/// # Panics
+///
+/// This function will panic if compiler state is invalid or in out-of-memory conditions.
+pub fn generate_qir_from_fir(
+    fir_store: &fir::PackageStore,
+    package: fir::PackageId,
+) -> std::result::Result<String, (Error, Vec<Frame>)> {
     let unit = fir_store.get(package);
 
     let mut sim = BaseProfSim::default();
@@ -45,7 +59,7 @@ pub fn generate_qir(
         package,
         None,
         unit.entry_exec_graph.clone(),
-        &fir_store,
+        fir_store,
         &mut Env::default(),
         &mut sim,
         &mut out,
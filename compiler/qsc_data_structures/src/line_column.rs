@@ -60,6 +60,34 @@ impl Position {
         positions_from_utf8_byte_offsets(encoding, contents, [utf8_byte_offset])[0]
     }
 
+    /// For a given string and utf-8 byte offsets in ascending order, returns the
+    /// [`Position`]s that correspond to those offsets, in the same order.
+    ///
+    /// The string is only iterated over once, so this should be preferred over
+    /// calling [`Position::from_utf8_byte_offset`] for each of many offsets
+    /// into the same string.
+    ///
+    /// Offsets past the end of the string and offsets that are not valid utf8 char
+    /// boundaries are treated the same way as in [`Position::from_utf8_byte_offset`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the offsets are not in ascending order.
+    #[must_use]
+    pub fn from_sorted_utf8_byte_offsets(
+        encoding: Encoding,
+        contents: &str,
+        sorted_utf8_byte_offsets: &[u32],
+    ) -> Vec<Self> {
+        assert!(
+            sorted_utf8_byte_offsets.is_sorted(),
+            "offsets should be in ascending order"
+        );
+        let mut positions = vec![Position { line: 0, column: 0 }; sorted_utf8_byte_offsets.len()];
+        fill_positions(encoding, contents, sorted_utf8_byte_offsets, &mut positions);
+        positions
+    }
+
     /// For a given string, returns the utf-8 byte offset that corresponds
     /// to this [`Position`] in that string.
     ///
@@ -137,6 +165,24 @@ fn positions_from_utf8_byte_offsets<const N: usize>(
     contents: &str,
     sorted_utf8_byte_offsets: [u32; N],
 ) -> [Position; N] {
+    let mut positions = [Position { line: 0, column: 0 }; N];
+    fill_positions(
+        encoding,
+        contents,
+        &sorted_utf8_byte_offsets,
+        &mut positions,
+    );
+    positions
+}
+
+/// Writes the [`Position`] of each of the sorted utf-8 byte offsets into the
+/// corresponding element of `positions`, iterating over the string once.
+fn fill_positions(
+    encoding: Encoding,
+    contents: &str,
+    sorted_utf8_byte_offsets: &[u32],
+    positions: &mut [Position],
+) {
     // The below example contains characters that are encoded
     // with different numbers of code units in utf-8 and utf-16,
     // to demonstrate how code unit offset will differ depending
@@ -153,13 +199,13 @@ fn positions_from_utf8_byte_offsets<const N: usize>(
     // utf-8 byte offset        | 0                 4        5                 9        12     13    15
     // utf-16 code unit offset  | 0                 2        3                 5        6      7     8
 
-    let mut positions = [Position { line: 0, column: 0 }; N];
+    let n = sorted_utf8_byte_offsets.len();
     let mut i = 0;
     let mut column: u32 = 0;
     let mut line: u32 = 0;
 
     for (char_index, c) in contents.char_indices() {
-        if i == N {
+        if i == n {
             // We've run out of offsets to look for
             break;
         }
@@ -169,7 +215,7 @@ fn positions_from_utf8_byte_offsets<const N: usize>(
             positions[i] = Position { line, column };
             i += 1;
 
-            if i == N {
+            if i == n {
                 // We've run out of offsets to look for
                 break;
             }
@@ -187,12 +233,10 @@ fn positions_from_utf8_byte_offsets<const N: usize>(
     }
 
     // If any offsets couldn't be mapped, map them to <eof>
-    while i < N {
+    while i < n {
         positions[i] = Position { line, column };
         i += 1;
     }
-
-    positions
 }
 
 fn num_code_units(encoding: Encoding, c: char) -> u32 {
@@ -396,3 +396,26 @@ fn check_all_offsets(contents: &str, expected: &expect_test::Expect) {
         }
     }
 }
+
+#[test]
+fn sorted_offsets_match_individual_offsets() {
+    let contents = "𝑓(𝑥⃗)\r\nΣ\n\nhello";
+    let offsets: Vec<u32> = (0..=u32::try_from(contents.len()).expect("length should fit in u32")
+        + 2)
+        .chain([30, 30])
+        .collect();
+    for encoding in [Encoding::Utf8, Encoding::Utf16] {
+        let positions = Position::from_sorted_utf8_byte_offsets(encoding, contents, &offsets);
+        let expected: Vec<Position> = offsets
+            .iter()
+            .map(|&offset| Position::from_utf8_byte_offset(encoding, contents, offset))
+            .collect();
+        assert_eq!(positions, expected);
+    }
+}
+
+#[test]
+#[should_panic(expected = "offsets should be in ascending order")]
+fn unsorted_offsets() {
+    let _ = Position::from_sorted_utf8_byte_offsets(Encoding::Utf8, "hello", &[3, 1]);
+}
@@ -14,7 +14,6 @@ use qsc_hir::hir::{CallableKind, Item, ItemKind, Package, PackageId, Visibility}
 use qsc_hir::{hir, ty};
 use rustc_hash::FxHashMap;
 use std::fmt::{Display, Formatter, Result};
-use std::rc::Rc;
 use std::sync::Arc;
 
 type Files = Vec<(Arc<str>, Arc<str>, Arc<str>)>;
@@ -112,7 +111,7 @@ pub fn generate_docs() -> Files {
         compilation: &compilation,
     };
 
-    let mut toc: FxHashMap<Rc<str>, Vec<String>> = FxHashMap::default();
+    let mut toc: FxHashMap<Arc<str>, Vec<String>> = FxHashMap::default();
     for (_, unit) in &compilation.package_store {
         let package = &unit.package;
         for (_, item) in &package.items {
@@ -132,7 +131,7 @@ fn generate_doc_for_item<'a>(
     item: &'a Item,
     display: &'a CodeDisplay,
     files: &mut Files,
-) -> Option<(Rc<str>, String)> {
+) -> Option<(Arc<str>, String)> {
     // Filter items
     if item.visibility == Visibility::Internal || matches!(item.kind, ItemKind::Namespace(_, _)) {
         return None;
@@ -155,7 +154,7 @@ fn generate_doc_for_item<'a>(
     Some((ns.clone(), line))
 }
 
-fn get_namespace(package: &Package, item: &Item) -> Option<Rc<str>> {
+fn get_namespace(package: &Package, item: &Item) -> Option<Arc<str>> {
     match item.parent {
         Some(local_id) => {
             let parent = package
@@ -177,7 +176,7 @@ fn get_namespace(package: &Package, item: &Item) -> Option<Rc<str>> {
     }
 }
 
-fn generate_file(ns: &Rc<str>, item: &Item, display: &CodeDisplay) -> Option<(Metadata, String)> {
+fn generate_file(ns: &Arc<str>, item: &Item, display: &CodeDisplay) -> Option<(Metadata, String)> {
     let metadata = get_metadata(ns.clone(), item, display)?;
 
     let doc = increase_header_level(&item.doc);
@@ -209,8 +208,8 @@ struct Metadata {
     title: String,
     topic: String,
     kind: MetadataKind,
-    namespace: Rc<str>,
-    name: Rc<str>,
+    namespace: Arc<str>,
+    name: Arc<str>,
     summary: String,
     signature: String,
 }
@@ -245,7 +244,7 @@ enum MetadataKind {
     Udt,
 }
 
-fn get_metadata(ns: Rc<str>, item: &Item, display: &CodeDisplay) -> Option<Metadata> {
+fn get_metadata(ns: Arc<str>, item: &Item, display: &CodeDisplay) -> Option<Metadata> {
     let (name, signature, kind) = match &item.kind {
         ItemKind::Callable(decl) => Some((
             decl.name.name.clone(),
@@ -284,7 +283,7 @@ fn get_metadata(ns: Rc<str>, item: &Item, display: &CodeDisplay) -> Option<Metad
 }
 
 /// Generates the Table of Contents file, toc.yml
-fn generate_toc(map: &mut FxHashMap<Rc<str>, Vec<String>>, files: &mut Files) {
+fn generate_toc(map: &mut FxHashMap<Arc<str>, Vec<String>>, files: &mut Files) {
     let header = "
 # This file is automatically generated.
 # Please do not modify this file manually, or your changes will be lost when
@@ -94,7 +94,14 @@ pub(crate) fn call(
             let [lo, hi] = unwrap_tuple(arg);
             let lo = lo.unwrap_double();
             let hi = hi.unwrap_double();
-            if lo > hi {
+            if !(hi - lo).is_finite() {
+                // Covers NaN and infinite bounds as well as intervals too wide to sample from.
+                Err(Error::IntrinsicFail(
+                    name.to_string(),
+                    "the interval between `min` and `max` must be finite".to_string(),
+                    arg_span,
+                ))
+            } else if lo > hi {
                 Err(Error::EmptyRange(arg_span))
             } else {
                 Ok(Value::Double(rng.gen_range(lo..=hi)))
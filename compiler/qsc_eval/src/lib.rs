@@ -48,6 +48,8 @@ use std::{
     iter,
     ops::Neg,
     rc::Rc,
+    sync::Arc,
+    time::Instant,
 };
 use thiserror::Error;
 
@@ -136,6 +138,16 @@ pub enum Error {
     #[diagnostic(code("Qsc.Eval.UnsupportedIntrinsicType"))]
     UnsupportedIntrinsicType(String, #[label] PackageSpan),
 
+    #[error("evaluation timed out")]
+    #[diagnostic(code("Qsc.Eval.TimedOut"))]
+    #[diagnostic(help("the program did not finish within the time allowed for evaluation"))]
+    TimedOut(#[label("evaluation was stopped here")] PackageSpan),
+
+    #[error("recursion limit of {0} nested calls exceeded")]
+    #[diagnostic(code("Qsc.Eval.RecursionLimitExceeded"))]
+    #[diagnostic(help("check for unbounded recursion, or raise the limit if the recursion is expected to be this deep"))]
+    RecursionLimitExceeded(usize, #[label("this call exceeded the limit")] PackageSpan),
+
     #[error("program failed: {0}")]
     #[diagnostic(code("Qsc.Eval.UserFail"))]
     UserFail(String, #[label("explicit fail")] PackageSpan),
@@ -158,8 +170,10 @@ impl Error {
             | Error::QubitUniqueness(span)
             | Error::QubitsNotSeparable(span)
             | Error::RangeStepZero(span)
+            | Error::RecursionLimitExceeded(_, span)
             | Error::ReleasedQubitNotZero(_, span)
             | Error::ResultComparisonUnsupported(span)
+            | Error::TimedOut(span)
             | Error::UnboundName(span)
             | Error::UnknownIntrinsic(_, span)
             | Error::UnsupportedIntrinsicType(_, span)
@@ -169,6 +183,11 @@ impl Error {
     }
 }
 
+/// The default maximum number of nested callable calls during evaluation.
+/// Generous enough for recursive algorithms over realistic inputs, while
+/// still stopping runaway recursion well before it exhausts memory.
+pub const DEFAULT_MAX_RECURSION_DEPTH: usize = 10_000;
+
 /// A specialization that may be implemented for an operation.
 enum Spec {
     /// The default specialization.
@@ -196,9 +215,9 @@ impl Display for Spec {
 /// range.
 #[must_use]
 pub fn exec_graph_section(
-    graph: &Rc<[ExecGraphNode]>,
+    graph: &Arc<[ExecGraphNode]>,
     range: ops::Range<usize>,
-) -> Rc<[ExecGraphNode]> {
+) -> Arc<[ExecGraphNode]> {
     let start: u32 = range
         .start
         .try_into()
@@ -223,7 +242,7 @@ pub fn exec_graph_section(
 pub fn eval(
     package: PackageId,
     seed: Option<u64>,
-    exec_graph: Rc<[ExecGraphNode]>,
+    exec_graph: Arc<[ExecGraphNode]>,
     globals: &impl PackageStoreLookup,
     env: &mut Env,
     sim: &mut impl Backend<ResultType = impl Into<val::Result>>,
@@ -275,7 +294,7 @@ impl AsIndex for i64 {
 
 #[derive(Debug, Clone)]
 pub struct Variable {
-    pub name: Rc<str>,
+    pub name: Arc<str>,
     pub value: Value,
     pub span: Span,
 }
@@ -283,7 +302,7 @@ pub struct Variable {
 #[derive(Debug, Clone)]
 pub struct VariableInfo {
     pub value: Value,
-    pub name: Rc<str>,
+    pub name: Arc<str>,
     pub type_name: String,
     pub span: Span,
 }
@@ -409,7 +428,7 @@ struct Scope {
 }
 
 pub struct State {
-    exec_graph_stack: Vec<Rc<[ExecGraphNode]>>,
+    exec_graph_stack: Vec<Arc<[ExecGraphNode]>>,
     idx: u32,
     idx_stack: Vec<u32>,
     val_register: Option<Value>,
@@ -419,13 +438,15 @@ pub struct State {
     call_stack: CallStack,
     current_span: Span,
     rng: RefCell<StdRng>,
+    deadline: Option<Instant>,
+    max_recursion_depth: usize,
 }
 
 impl State {
     #[must_use]
     pub fn new(
         package: PackageId,
-        exec_graph: Rc<[ExecGraphNode]>,
+        exec_graph: Arc<[ExecGraphNode]>,
         classical_seed: Option<u64>,
     ) -> Self {
         let rng = match classical_seed {
@@ -443,12 +464,50 @@ impl State {
             call_stack: CallStack::default(),
             current_span: Span::default(),
             rng,
+            deadline: None,
+            max_recursion_depth: DEFAULT_MAX_RECURSION_DEPTH,
+        }
+    }
+
+    /// Sets the maximum number of nested callable calls. A call that would go
+    /// deeper stops evaluation with [`Error::RecursionLimitExceeded`].
+    #[must_use]
+    pub fn with_max_recursion_depth(mut self, depth: usize) -> Self {
+        self.max_recursion_depth = depth;
+        self
+    }
+
+    /// Sets a wall-clock deadline for evaluation. Once it has passed, evaluation
+    /// stops with [`Error::TimedOut`] at the next loop iteration or callable call.
+    #[must_use]
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    fn check_deadline(&self) -> Result<(), Error> {
+        match self.deadline {
+            Some(deadline) if Instant::now() >= deadline => {
+                Err(Error::TimedOut(self.to_global_span(self.current_span)))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn check_recursion_depth(&self) -> Result<(), Error> {
+        if self.call_stack.len() >= self.max_recursion_depth {
+            Err(Error::RecursionLimitExceeded(
+                self.max_recursion_depth,
+                self.to_global_span(self.current_span),
+            ))
+        } else {
+            Ok(())
         }
     }
 
     fn push_frame(
         &mut self,
-        exec_graph: Rc<[ExecGraphNode]>,
+        exec_graph: Arc<[ExecGraphNode]>,
         id: StoreItemId,
         functor: FunctorApp,
     ) {
@@ -582,6 +641,9 @@ impl State {
                 }
                 Some(ExecGraphNode::Jump(idx)) => {
                     self.idx = *idx;
+                    // Loops are lowered to jumps, so this bounds the time spent looping.
+                    self.check_deadline()
+                        .map_err(|e| (e, self.get_stack_frames()))?;
                     continue;
                 }
                 Some(ExecGraphNode::JumpIf(idx)) => {
@@ -755,7 +817,7 @@ impl State {
 
     fn collect_string(&mut self, components: &[StringComponent]) {
         if let [StringComponent::Lit(str)] = components {
-            self.set_val_register(Value::String(Rc::clone(str)));
+            self.set_val_register(Value::String(Arc::clone(str)));
             return;
         }
 
@@ -771,7 +833,7 @@ impl State {
                 }
             }
         }
-        self.set_val_register(Value::String(Rc::from(string)));
+        self.set_val_register(Value::String(Arc::from(string)));
     }
 
     fn eval_arr(&mut self, len: usize) {
@@ -947,6 +1009,8 @@ impl State {
                     Spec::CtlAdj => specialized_implementation.ctl_adj.as_ref(),
                 }
                 .expect("missing specialization should be a compilation error");
+                self.check_deadline()?;
+                self.check_recursion_depth()?;
                 self.push_frame(spec_decl.exec_graph.clone(), callee_id, functor);
                 self.push_scope(env);
 
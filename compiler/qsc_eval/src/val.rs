@@ -1,13 +1,18 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
+#[cfg(test)]
+mod tests;
+
 use num_bigint::BigInt;
 use qsc_data_structures::{display::join, functors::FunctorApp};
 use qsc_fir::fir::{Pauli, StoreItemId};
 use std::{
     fmt::{self, Display, Formatter},
     rc::Rc,
+    sync::Arc,
 };
+use thiserror::Error;
 
 pub(super) const DEFAULT_RANGE_STEP: i64 = 1;
 
@@ -24,7 +29,7 @@ pub enum Value {
     Qubit(Qubit),
     Range(Box<Range>),
     Result(Result),
-    String(Rc<str>),
+    String(Arc<str>),
     Tuple(Rc<[Value]>),
     Var(Var),
 }
@@ -152,6 +157,63 @@ impl Display for Value {
     }
 }
 
+/// Displays a [`Value`] with `Double` values, including those nested in
+/// tuples and arrays, formatted to a fixed number of significant digits.
+struct PrecisionDisplay<'a> {
+    value: &'a Value,
+    precision: usize,
+}
+
+impl Display for PrecisionDisplay<'_> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let with_precision = |value| PrecisionDisplay {
+            value,
+            precision: self.precision,
+        };
+        match self.value {
+            Value::Array(arr) => {
+                write!(f, "[")?;
+                join(f, arr.iter().map(with_precision), ", ")?;
+                write!(f, "]")
+            }
+            Value::Double(v) if v.is_finite() => {
+                let precision = self.precision.max(1);
+                // Rounding to the requested digits first gives the exponent of the
+                // displayed value, which may be one more than that of `v` itself.
+                let scientific = format!("{v:.digits$e}", digits = precision - 1);
+                let exponent = scientific
+                    .rsplit_once('e')
+                    .and_then(|(_, exponent)| exponent.parse::<i64>().ok())
+                    .unwrap_or_default();
+                let precision = i64::try_from(precision).unwrap_or(i64::MAX);
+                if exponent < -4 || exponent >= precision {
+                    // Values too large or too small to show all significant digits
+                    // in positional form are displayed in exponent form.
+                    f.write_str(&scientific)
+                } else {
+                    let decimals = usize::try_from(precision - 1 - exponent).unwrap_or_default();
+                    if decimals == 0 {
+                        // By convention doubles are displayed with a decimal point
+                        // to differentiate them from integer values.
+                        write!(f, "{v:.0}.0")
+                    } else {
+                        write!(f, "{v:.decimals$}")
+                    }
+                }
+            }
+            Value::Tuple(tup) => {
+                write!(f, "(")?;
+                join(f, tup.iter().map(with_precision), ", ")?;
+                if tup.len() == 1 {
+                    write!(f, ",")?;
+                }
+                write!(f, ")")
+            }
+            value => value.fmt(f),
+        }
+    }
+}
+
 thread_local! {
     static UNIT: Rc<[Value; 0]> = Rc::new([]);
 }
@@ -308,7 +370,7 @@ impl Value {
     /// # Panics
     /// This will panic if the [Value] is not a [`Value::String`].
     #[must_use]
-    pub fn unwrap_string(self) -> Rc<str> {
+    pub fn unwrap_string(self) -> Arc<str> {
         let Value::String(v) = self else {
             panic!("value should be String, got {}", self.type_name());
         };
@@ -337,6 +399,44 @@ impl Value {
         v
     }
 
+    /// Formats the value like its `Display` implementation, except that
+    /// `Double` values, including those nested in tuples and arrays, are
+    /// written with `precision` significant digits.
+    #[must_use]
+    pub fn display_with_precision(&self, precision: usize) -> String {
+        PrecisionDisplay {
+            value: self,
+            precision,
+        }
+        .to_string()
+    }
+
+    /// Compares two values for equality, treating `Double` values within
+    /// `tolerance` of each other as equal and `NaN` as equal to itself.
+    /// Tuples and arrays are compared element-wise, and all other values
+    /// use exact equality.
+    #[must_use]
+    pub fn approx_eq(&self, other: &Self, tolerance: f64) -> bool {
+        match (self, other) {
+            (Value::Double(a), Value::Double(b)) => {
+                (a.is_nan() && b.is_nan()) || a == b || (a - b).abs() <= tolerance
+            }
+            (Value::Array(a), Value::Array(b)) => {
+                a.len() == b.len()
+                    && a.iter()
+                        .zip(b.iter())
+                        .all(|(a, b)| a.approx_eq(b, tolerance))
+            }
+            (Value::Tuple(a), Value::Tuple(b)) => {
+                a.len() == b.len()
+                    && a.iter()
+                        .zip(b.iter())
+                        .all(|(a, b)| a.approx_eq(b, tolerance))
+            }
+            _ => self == other,
+        }
+    }
+
     #[must_use]
     pub fn type_name(&self) -> &'static str {
         match self {
@@ -357,3 +457,106 @@ impl Value {
         }
     }
 }
+
+/// An error produced when converting a [`Value`] into a native Rust type.
+#[derive(Clone, Debug, Error, PartialEq)]
+pub enum ValueConversionError {
+    #[error("expected value of type {expected}, found {found}")]
+    TypeMismatch {
+        expected: &'static str,
+        found: &'static str,
+    },
+    #[error("invalid array element at index {index}: {error}")]
+    ArrayElement {
+        index: usize,
+        error: Box<ValueConversionError>,
+    },
+}
+
+macro_rules! impl_try_from_value {
+    ($ty:ty, $variant:ident, $expected:literal) => {
+        impl TryFrom<Value> for $ty {
+            type Error = ValueConversionError;
+
+            fn try_from(value: Value) -> core::result::Result<Self, Self::Error> {
+                match value {
+                    Value::$variant(v) => Ok(v),
+                    _ => Err(ValueConversionError::TypeMismatch {
+                        expected: $expected,
+                        found: value.type_name(),
+                    }),
+                }
+            }
+        }
+
+        impl TryFrom<&Value> for $ty {
+            type Error = ValueConversionError;
+
+            fn try_from(value: &Value) -> core::result::Result<Self, Self::Error> {
+                match value {
+                    Value::$variant(v) => Ok(v.clone()),
+                    _ => Err(ValueConversionError::TypeMismatch {
+                        expected: $expected,
+                        found: value.type_name(),
+                    }),
+                }
+            }
+        }
+    };
+}
+
+impl_try_from_value!(i64, Int, "Int");
+impl_try_from_value!(f64, Double, "Double");
+impl_try_from_value!(bool, Bool, "Bool");
+impl_try_from_value!(BigInt, BigInt, "BigInt");
+
+impl<T> TryFrom<Value> for Vec<T>
+where
+    T: TryFrom<Value, Error = ValueConversionError>,
+{
+    type Error = ValueConversionError;
+
+    fn try_from(value: Value) -> core::result::Result<Self, Self::Error> {
+        let Value::Array(arr) = value else {
+            return Err(ValueConversionError::TypeMismatch {
+                expected: "Array",
+                found: value.type_name(),
+            });
+        };
+        let arr = Rc::try_unwrap(arr).unwrap_or_else(|arr| arr.as_ref().clone());
+        arr.into_iter()
+            .enumerate()
+            .map(|(index, item)| {
+                T::try_from(item).map_err(|error| ValueConversionError::ArrayElement {
+                    index,
+                    error: Box::new(error),
+                })
+            })
+            .collect()
+    }
+}
+
+impl<T> TryFrom<&Value> for Vec<T>
+where
+    T: for<'a> TryFrom<&'a Value, Error = ValueConversionError>,
+{
+    type Error = ValueConversionError;
+
+    fn try_from(value: &Value) -> core::result::Result<Self, Self::Error> {
+        let Value::Array(arr) = value else {
+            return Err(ValueConversionError::TypeMismatch {
+                expected: "Array",
+                found: value.type_name(),
+            });
+        };
+        arr.iter()
+            .enumerate()
+            .map(|(index, item)| {
+                T::try_from(item).map_err(|error| ValueConversionError::ArrayElement {
+                    index,
+                    error: Box::new(error),
+                })
+            })
+            .collect()
+    }
+}
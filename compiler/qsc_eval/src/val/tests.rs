@@ -0,0 +1,165 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use super::{Value, ValueConversionError};
+use expect_test::expect;
+use num_bigint::BigInt;
+use std::rc::Rc;
+
+#[test]
+fn scalar_values_convert_to_native_types() {
+    assert_eq!(i64::try_from(Value::Int(42)), Ok(42));
+    assert_eq!(f64::try_from(&Value::Double(1.5)), Ok(1.5));
+    assert_eq!(bool::try_from(Value::Bool(true)), Ok(true));
+    assert_eq!(
+        BigInt::try_from(&Value::BigInt(BigInt::from(7))),
+        Ok(BigInt::from(7))
+    );
+}
+
+#[test]
+fn mismatched_variant_is_error() {
+    let error = i64::try_from(Value::Double(1.0)).expect_err("conversion should fail");
+    assert_eq!(
+        error,
+        ValueConversionError::TypeMismatch {
+            expected: "Int",
+            found: "Double",
+        }
+    );
+    expect!["expected value of type Int, found Double"].assert_eq(&error.to_string());
+}
+
+#[test]
+fn array_converts_to_vec() {
+    let value = Value::Array(Rc::new(vec![Value::Int(1), Value::Int(2), Value::Int(3)]));
+    let ints: Vec<i64> = (&value).try_into().expect("conversion should succeed");
+    assert_eq!(ints, vec![1, 2, 3]);
+    let ints: Vec<i64> = value.try_into().expect("conversion should succeed");
+    assert_eq!(ints, vec![1, 2, 3]);
+}
+
+#[test]
+fn nested_array_converts_to_nested_vec() {
+    let value = Value::Array(Rc::new(vec![
+        Value::Array(Rc::new(vec![Value::Int(1)])),
+        Value::Array(Rc::new(vec![])),
+        Value::Array(Rc::new(vec![Value::Int(2), Value::Int(3)])),
+    ]));
+    let ints: Vec<Vec<i64>> = value.try_into().expect("conversion should succeed");
+    assert_eq!(ints, vec![vec![1], vec![], vec![2, 3]]);
+}
+
+#[test]
+fn array_element_error_reports_index() {
+    let value = Value::Array(Rc::new(vec![
+        Value::Array(Rc::new(vec![Value::Int(1)])),
+        Value::Array(Rc::new(vec![Value::Int(2), Value::Bool(false)])),
+    ]));
+    let error = Vec::<Vec<i64>>::try_from(&value).expect_err("conversion should fail");
+    expect![
+        "invalid array element at index 1: invalid array element at index 1: expected value of type Int, found Bool"
+    ]
+    .assert_eq(&error.to_string());
+}
+
+#[test]
+fn non_array_to_vec_is_error() {
+    assert_eq!(
+        Vec::<i64>::try_from(Value::Int(1)),
+        Err(ValueConversionError::TypeMismatch {
+            expected: "Array",
+            found: "Int",
+        })
+    );
+}
+
+#[test]
+fn approx_eq_tuple_of_doubles_within_tolerance() {
+    let expected = Value::Tuple(vec![Value::Double(1.0), Value::Double(2.0)].into());
+    let actual = Value::Tuple(vec![Value::Double(1.0 + 0.9e-6), Value::Double(2.0)].into());
+    assert!(expected.approx_eq(&actual, 1e-6));
+}
+
+#[test]
+fn approx_eq_tuple_of_doubles_outside_tolerance() {
+    let expected = Value::Tuple(vec![Value::Double(1.0), Value::Double(2.0)].into());
+    let actual = Value::Tuple(vec![Value::Double(1.0), Value::Double(2.0 - 1.1e-6)].into());
+    assert!(!expected.approx_eq(&actual, 1e-6));
+}
+
+#[test]
+fn approx_eq_nan_equals_nan() {
+    assert!(Value::Double(f64::NAN).approx_eq(&Value::Double(f64::NAN), 0.0));
+    assert!(!Value::Double(f64::NAN).approx_eq(&Value::Double(0.0), 1.0));
+}
+
+#[test]
+fn approx_eq_nested_arrays() {
+    let expected = Value::Array(Rc::new(vec![Value::Tuple(
+        vec![Value::Int(1), Value::Double(0.5)].into(),
+    )]));
+    let actual = Value::Array(Rc::new(vec![Value::Tuple(
+        vec![Value::Int(1), Value::Double(0.5 + 1e-12)].into(),
+    )]));
+    assert!(expected.approx_eq(&actual, 1e-9));
+    assert!(!expected.approx_eq(&Value::Array(Rc::new(vec![])), 1e-9));
+}
+
+#[test]
+fn approx_eq_other_values_are_exact() {
+    assert!(Value::Int(1).approx_eq(&Value::Int(1), 1.0));
+    assert!(!Value::Int(1).approx_eq(&Value::Int(2), 1.0));
+    assert!(!Value::Int(1).approx_eq(&Value::Double(1.0), 1.0));
+}
+
+#[test]
+fn display_with_precision_rounds_double() {
+    expect!["0.333"].assert_eq(&Value::Double(1.0 / 3.0).display_with_precision(3));
+    expect!["-12.35"].assert_eq(&Value::Double(-12.345_67).display_with_precision(4));
+    expect!["0.000123"].assert_eq(&Value::Double(0.000_123_4).display_with_precision(3));
+    expect!["123.0"].assert_eq(&Value::Double(123.4).display_with_precision(3));
+    expect!["NaN"].assert_eq(&Value::Double(f64::NAN).display_with_precision(3));
+}
+
+#[test]
+fn display_with_precision_uses_exponent_for_large_magnitudes() {
+    expect!["1.23e3"].assert_eq(&Value::Double(1234.7).display_with_precision(3));
+    expect!["1.00e3"].assert_eq(&Value::Double(999.7).display_with_precision(3));
+    expect!["-6.022e23"].assert_eq(&Value::Double(-6.022_140_76e23).display_with_precision(4));
+}
+
+#[test]
+fn display_with_precision_uses_exponent_for_small_magnitudes() {
+    expect!["0.500"].assert_eq(&Value::Double(0.5).display_with_precision(3));
+    expect!["0.0123"].assert_eq(&Value::Double(0.012_345).display_with_precision(3));
+    expect!["1.23e-5"].assert_eq(&Value::Double(0.000_012_345).display_with_precision(3));
+    expect!["-1.6e-19"].assert_eq(&Value::Double(-1.602e-19).display_with_precision(2));
+}
+
+#[test]
+fn display_with_precision_formats_array_uniformly() {
+    let value = Value::Array(Rc::new(vec![
+        Value::Double(0.5),
+        Value::Double(1.0 / 3.0),
+        Value::Double(2.0),
+        Value::Double(0.0),
+    ]));
+    expect!["[0.500, 0.333, 2.00, 0.00]"].assert_eq(&value.display_with_precision(3));
+}
+
+#[test]
+fn display_with_precision_preserves_other_values() {
+    let value = Value::Tuple(
+        vec![
+            Value::Int(1),
+            Value::Double(0.123_456_7),
+            Value::RESULT_ONE,
+            Value::Pauli(qsc_fir::fir::Pauli::X),
+            Value::String("text".into()),
+            Value::Tuple(vec![Value::Double(2.0 / 3.0)].into()),
+        ]
+        .into(),
+    );
+    expect!["(1, 0.123, One, PauliX, text, (0.667,))"].assert_eq(&value.display_with_precision(3));
+}
@@ -213,6 +213,25 @@ impl PackageStore {
             open: id,
         }
     }
+
+    /// Opens an existing package in the package store, such as one returned by
+    /// [`OpenPackageStore::into_package_store`], so it can be incrementally updated again.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the package is not in the store.
+    #[must_use]
+    pub fn reopen(self, id: PackageId) -> OpenPackageStore {
+        assert!(
+            self.units.contains_key(id),
+            "reopened package should be in the package store"
+        );
+
+        OpenPackageStore {
+            store: self,
+            open: id,
+        }
+    }
 }
 impl<'a> IntoIterator for &'a PackageStore {
     type IntoIter = Iter<'a>;
@@ -305,6 +324,23 @@ pub fn compile(
     )
 }
 
+/// Parses the sources without resolving names, type checking or lowering them.
+/// The returned AST has no node IDs assigned.
+#[must_use]
+pub fn parse(
+    sources: &SourceMap,
+    language_features: LanguageFeatures,
+) -> (ast::Package, Vec<Error>) {
+    let (package, errors) = parse_all(sources, language_features);
+    (
+        package,
+        errors
+            .into_iter()
+            .map(|error| Error(ErrorKind::Parse(error)))
+            .collect(),
+    )
+}
+
 #[allow(clippy::module_name_repetitions)]
 pub fn compile_ast(
     store: &PackageStore,
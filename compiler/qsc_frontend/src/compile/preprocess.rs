@@ -7,7 +7,7 @@ use qsc_ast::{
     mut_visit::MutVisitor,
 };
 use qsc_hir::hir;
-use std::rc::Rc;
+use std::sync::Arc;
 
 use super::TargetCapabilityFlags;
 
@@ -16,8 +16,8 @@ mod tests;
 
 #[derive(PartialEq, Hash, Clone, Debug)]
 pub struct TrackedName {
-    pub name: Rc<str>,
-    pub namespace: Rc<str>,
+    pub name: Arc<str>,
+    pub namespace: Arc<str>,
 }
 
 pub(crate) struct Conditional {
@@ -92,12 +92,12 @@ impl MutVisitor for Conditional {
                     ItemKind::Callable(callable) => {
                         self.included_names.push(TrackedName {
                             name: callable.name.name.clone(),
-                            namespace: Rc::from(""),
+                            namespace: Arc::from(""),
                         });
                     }
                     ItemKind::Ty(ident, _) => self.included_names.push(TrackedName {
                         name: ident.name.clone(),
-                        namespace: Rc::from(""),
+                        namespace: Arc::from(""),
                     }),
                     _ => {}
                 }
@@ -106,12 +106,12 @@ impl MutVisitor for Conditional {
                     ItemKind::Callable(callable) => {
                         self.dropped_names.push(TrackedName {
                             name: callable.name.name.clone(),
-                            namespace: Rc::from(""),
+                            namespace: Arc::from(""),
                         });
                     }
                     ItemKind::Ty(ident, _) => self.dropped_names.push(TrackedName {
                         name: ident.name.clone(),
-                        namespace: Rc::from(""),
+                        namespace: Arc::from(""),
                     }),
                     _ => {}
                 }
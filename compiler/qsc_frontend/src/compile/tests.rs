@@ -3,13 +3,13 @@
 
 #![allow(clippy::needless_raw_string_hashes)]
 
-use crate::compile::TargetCapabilityFlags;
+use crate::{compile::TargetCapabilityFlags, error::WithSource};
 
 use super::{compile, CompileUnit, Error, PackageStore, SourceMap};
 use expect_test::expect;
 use indoc::indoc;
 use miette::Diagnostic;
-use qsc_data_structures::{language_features::LanguageFeatures, span::Span};
+use qsc_data_structures::{language_features::LanguageFeatures, line_column::Encoding, span::Span};
 use qsc_hir::{
     global,
     hir::{
@@ -222,6 +222,65 @@ fn two_files_error() {
     );
 }
 
+#[test]
+fn two_files_redefinition_related_information() {
+    let sources = SourceMap::new(
+        [
+            (
+                "test1".into(),
+                indoc! {"
+                    namespace Foo {
+                        function A() : Unit {}
+                    }
+                "}
+                .into(),
+            ),
+            (
+                "test2".into(),
+                indoc! {"
+                    namespace Foo {
+                        function A() : Unit {}
+                    }
+                "}
+                .into(),
+            ),
+        ],
+        None,
+    );
+
+    let unit = default_compile(sources);
+    let [error] = unit.errors.as_slice() else {
+        panic!("expected a single error, got {:?}", unit.errors);
+    };
+    assert_eq!(
+        ("test2", Span { lo: 29, hi: 30 }),
+        source_span(&unit.sources, error)
+    );
+    expect![[r#"
+        [
+            RelatedSpan {
+                source: "test1",
+                range: Range {
+                    start: Position {
+                        line: 1,
+                        column: 13,
+                    },
+                    end: Position {
+                        line: 1,
+                        column: 14,
+                    },
+                },
+                message: Some(
+                    "first declared here",
+                ),
+            },
+        ]
+    "#]]
+    .assert_debug_eq(
+        &WithSource::from_map(&unit.sources, error.clone()).related_information(Encoding::Utf8),
+    );
+}
+
 #[test]
 fn entry_call_operation() {
     let sources = SourceMap::new(
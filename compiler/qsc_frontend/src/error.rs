@@ -4,17 +4,36 @@
 #[cfg(test)]
 mod tests;
 
-use crate::compile::{Source, SourceMap};
-use miette::{Diagnostic, MietteError, MietteSpanContents, SourceCode, SourceSpan, SpanContents};
+use crate::compile::{Source, SourceMap, SourceName};
+use miette::{
+    Diagnostic, LabeledSpan, MietteError, MietteSpanContents, SourceCode, SourceSpan, SpanContents,
+};
+use qsc_data_structures::{
+    line_column::{Encoding, Range},
+    span::Span,
+};
 use std::{
     error::Error,
     fmt::{self, Debug, Display, Formatter},
 };
 
+/// A secondary label of a diagnostic, resolved to the source it points into.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RelatedSpan {
+    /// The name of the source the label points into.
+    pub source: SourceName,
+    /// The range of the label within its source.
+    pub range: Range,
+    /// The message of the label, if any.
+    pub message: Option<String>,
+}
+
 #[derive(Clone, Debug)]
 pub struct WithSource<E> {
     sources: Vec<Source>,
     error: E,
+    /// The diagnostic code of `error`, kept so that it can be borrowed as a string.
+    code: Option<String>,
 }
 
 impl<E: Diagnostic + Send + Sync> WithSource<E> {
@@ -26,6 +45,16 @@ impl<E: Diagnostic + Send + Sync> WithSource<E> {
         self.error
     }
 
+    /// The diagnostic code of the error, such as `Qsc.TypeCk.TyMismatch` or
+    /// `Qsc.Lint.DivisionByZero`. The code identifies the kind of error and,
+    /// unlike the message, does not depend on the details of this occurrence.
+    ///
+    /// This is the same code as [`Diagnostic::code`], borrowed as a string.
+    #[must_use]
+    pub fn diagnostic_code(&self) -> Option<&str> {
+        self.code.as_deref()
+    }
+
     /// Construct a diagnostic with source information from a source map.
     /// Since errors may contain labeled spans from any source file in the
     /// compilation, the entire source map is needed to resolve offsets.
@@ -51,19 +80,24 @@ impl<E: Diagnostic + Send + Sync> WithSource<E> {
             }
         }
 
+        let code = error.code().map(|code| code.to_string());
         Self {
             sources: filtered,
             error,
+            code,
         }
     }
 
     pub fn into_with_source<T>(self) -> WithSource<T>
     where
-        T: From<E>,
+        T: From<E> + Diagnostic,
     {
+        let error: T = self.error.into();
+        let code = error.code().map(|code| code.to_string());
         WithSource {
             sources: self.sources,
-            error: self.error.into(),
+            error,
+            code,
         }
     }
 
@@ -80,6 +114,33 @@ impl<E: Diagnostic + Send + Sync> WithSource<E> {
             .expect("expected to find source at span");
         (source, with_offset(span, |o| o - (source.offset as usize)))
     }
+
+    /// The secondary labels of the error, each resolved to the source it points into.
+    ///
+    /// The first label of an error marks its primary location, so it is not included.
+    /// The remaining labels point at related code, such as a conflicting declaration,
+    /// which may be in a different source than the primary location.
+    #[must_use]
+    pub fn related_information(&self, encoding: Encoding) -> Vec<RelatedSpan> {
+        self.error
+            .labels()
+            .into_iter()
+            .flatten()
+            .skip(1)
+            .map(|label| self.resolve_label(&label, encoding))
+            .collect()
+    }
+
+    fn resolve_label(&self, label: &LabeledSpan, encoding: Encoding) -> RelatedSpan {
+        let (source, span) = self.resolve_span(label.inner());
+        let lo = u32::try_from(span.offset()).expect("offset should fit into u32");
+        let len = u32::try_from(span.len()).expect("length should fit into u32");
+        RelatedSpan {
+            source: source.name.clone(),
+            range: Range::from_span(encoding, &source.contents, &Span { lo, hi: lo + len }),
+            message: label.label().map(ToString::to_string),
+        }
+    }
 }
 
 impl<E: Diagnostic> Error for WithSource<E> {
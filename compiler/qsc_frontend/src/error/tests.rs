@@ -164,6 +164,17 @@ fn resolve_spans() {
     .assert_debug_eq(&resolved_spans);
 }
 
+#[test]
+fn diagnostic_code_does_not_depend_on_message() {
+    let sources = SourceMap::default();
+    let first = WithSource::from_map(&sources, TestError::NoSpans("first".into()));
+    let second = WithSource::from_map(&sources, TestError::NoSpans("second".into()));
+
+    assert_ne!(first.to_string(), second.to_string());
+    assert_eq!(first.diagnostic_code(), Some("Qsc.Test.Error.NoSpans"));
+    assert_eq!(second.diagnostic_code(), Some("Qsc.Test.Error.NoSpans"));
+}
+
 fn span_with_offset(offset: u32, lo: u32, hi: u32) -> Span {
     Span {
         lo: lo + offset,
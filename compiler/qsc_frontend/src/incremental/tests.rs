@@ -100,6 +100,7 @@ fn one_callable() {
                         ty_vars: {},
                     },
                 ],
+                opens_used: {},
             }
             hir:
             Package:
@@ -174,6 +175,7 @@ fn one_statement() {
                         ty_vars: {},
                     },
                 ],
+                opens_used: {},
             }
             hir:
             Package:
@@ -223,6 +225,9 @@ fn parse_error() {
                         ),
                     ),
                 ),
+                code: Some(
+                    "Qsc.Parse.Token",
+                ),
             },
         ]
     "#]]
@@ -378,6 +383,9 @@ fn continue_after_parse_error() {
                         ),
                     ),
                 ),
+                code: Some(
+                    "Qsc.Parse.Token",
+                ),
             },
             WithSource {
                 sources: [
@@ -398,6 +406,9 @@ fn continue_after_parse_error() {
                         ),
                     ),
                 ),
+                code: Some(
+                    "Qsc.Resolve.NotFound",
+                ),
             },
         ]
     "#]]
@@ -451,6 +462,9 @@ fn continue_after_lower_error() {
                         ),
                     ),
                 ),
+                code: Some(
+                    "Qsc.LowerAst.MissingBody",
+                ),
             },
         ]
     "#]].assert_debug_eq(&errors);
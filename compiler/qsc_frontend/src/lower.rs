@@ -18,7 +18,7 @@ use qsc_hir::{
     mut_visit::MutVisitor,
     ty::{Arrow, FunctorSetValue, Ty},
 };
-use std::{clone::Clone, rc::Rc, str::FromStr, vec};
+use std::{clone::Clone, str::FromStr, sync::Arc, vec};
 use thiserror::Error;
 
 #[derive(Clone, Debug, Diagnostic, Error)]
@@ -141,7 +141,7 @@ impl With<'_> {
             id,
             span: namespace.span,
             parent: None,
-            doc: Rc::clone(&namespace.doc),
+            doc: Arc::clone(&namespace.doc),
             attrs: Vec::new(),
             visibility: hir::Visibility::Public,
             kind: hir::ItemKind::Namespace(name, items),
@@ -196,7 +196,7 @@ impl With<'_> {
             id: id.item,
             span: item.span,
             parent: self.lowerer.parent,
-            doc: Rc::clone(&item.doc),
+            doc: Arc::clone(&item.doc),
             attrs,
             visibility,
             kind,
@@ -225,6 +225,28 @@ impl With<'_> {
                     None
                 }
             },
+            Ok(hir::Attr::Test) => match &*attr.arg.kind {
+                ast::ExprKind::Tuple(args) if args.is_empty() => Some(hir::Attr::Test),
+                _ => {
+                    self.lowerer
+                        .errors
+                        .push(Error::InvalidAttrArgs("()".to_string(), attr.arg.span));
+                    None
+                }
+            },
+            Ok(hir::Attr::Deprecated(_)) => match &*attr.arg.kind {
+                ast::ExprKind::Tuple(args) if args.is_empty() => Some(hir::Attr::Deprecated(None)),
+                ast::ExprKind::Paren(inner) if string_lit(inner).is_some() => {
+                    Some(hir::Attr::Deprecated(string_lit(inner).cloned()))
+                }
+                _ => {
+                    self.lowerer.errors.push(Error::InvalidAttrArgs(
+                        "() or a replacement name string".to_string(),
+                        attr.arg.span,
+                    ));
+                    None
+                }
+            },
             Ok(hir::Attr::Config) => {
                 if !matches!(attr.arg.kind.as_ref(), ast::ExprKind::Paren(inner)
                     if matches!(inner.kind.as_ref(), ast::ExprKind::Path(path)
@@ -641,7 +663,7 @@ impl With<'_> {
     fn lower_string_component(&mut self, component: &ast::StringComponent) -> hir::StringComponent {
         match component {
             ast::StringComponent::Expr(expr) => hir::StringComponent::Expr(self.lower_expr(expr)),
-            ast::StringComponent::Lit(str) => hir::StringComponent::Lit(Rc::clone(str)),
+            ast::StringComponent::Lit(str) => hir::StringComponent::Lit(Arc::clone(str)),
         }
     }
 
@@ -811,7 +833,7 @@ fn lower_lit(lit: &ast::Lit) -> hir::ExprKind {
             hir::ExprKind::Lit(hir::Lit::Result(hir::Result::Zero))
         }
         ast::Lit::String(value) => {
-            hir::ExprKind::String(vec![hir::StringComponent::Lit(Rc::clone(value))])
+            hir::ExprKind::String(vec![hir::StringComponent::Lit(Arc::clone(value))])
         }
     }
 }
@@ -831,3 +853,13 @@ fn is_partial_app(arg: &ast::Expr) -> bool {
         _ => false,
     }
 }
+
+fn string_lit(expr: &ast::Expr) -> Option<&Arc<str>> {
+    match expr.kind.as_ref() {
+        ast::ExprKind::Lit(lit) => match lit.as_ref() {
+            ast::Lit::String(s) => Some(s),
+            _ => None,
+        },
+        _ => None,
+    }
+}
@@ -89,6 +89,88 @@ fn test_entrypoint_attr_wrong_args() {
     );
 }
 
+#[test]
+fn test_test_attr_allowed() {
+    check_errors(
+        indoc! {"
+            namespace input {
+                @Test()
+                operation Foo() : Unit {
+                    body ... {}
+                }
+            }
+        "},
+        &expect![[r#"
+            []
+        "#]],
+    );
+}
+
+#[test]
+fn test_test_attr_wrong_args() {
+    check_errors(
+        indoc! {r#"
+            namespace input {
+                @Test("Bar")
+                operation Foo() : Unit {
+                    body ... {}
+                }
+            }
+        "#},
+        &expect![[r#"
+            [
+                InvalidAttrArgs(
+                    "()",
+                    Span {
+                        lo: 27,
+                        hi: 34,
+                    },
+                ),
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn test_deprecated_attr_allowed() {
+    check_errors(
+        indoc! {r#"
+            namespace input {
+                @Deprecated()
+                function Foo() : Unit {}
+                @Deprecated("input.Foo")
+                function Bar() : Unit {}
+            }
+        "#},
+        &expect![[r#"
+            []
+        "#]],
+    );
+}
+
+#[test]
+fn test_deprecated_attr_wrong_args() {
+    check_errors(
+        indoc! {"
+            namespace input {
+                @Deprecated(Bar)
+                function Foo() : Unit {}
+            }
+        "},
+        &expect![[r#"
+            [
+                InvalidAttrArgs(
+                    "() or a replacement name string",
+                    Span {
+                        lo: 33,
+                        hi: 38,
+                    },
+                ),
+            ]
+        "#]],
+    );
+}
+
 #[test]
 fn test_target_profile_base_attr_allowed() {
     check_errors(
@@ -17,7 +17,7 @@ use qsc_hir::{
     ty::{ParamId, Prim},
 };
 use rustc_hash::{FxHashMap, FxHashSet};
-use std::{collections::hash_map::Entry, rc::Rc, str::FromStr, vec};
+use std::{collections::hash_map::Entry, str::FromStr, sync::Arc, vec};
 use thiserror::Error;
 
 use crate::compile::preprocess::TrackedName;
@@ -78,7 +78,12 @@ pub(super) enum Error {
 
     #[error("duplicate declaration of `{0}` in namespace `{1}`")]
     #[diagnostic(code("Qsc.Resolve.Duplicate"))]
-    Duplicate(String, String, #[label] Span),
+    Duplicate(
+        String,
+        String,
+        #[label] Span,
+        #[label("first declared here")] Option<Span>,
+    ),
 
     #[error("duplicate name `{0}` in pattern")]
     #[diagnostic(help("a name cannot shadow another name in the same pattern"))]
@@ -116,11 +121,11 @@ pub struct Scope {
     span: Span,
     kind: ScopeKind,
     /// Open statements. The key is the namespace name or alias.
-    opens: FxHashMap<Rc<str>, Vec<Open>>,
+    opens: FxHashMap<Arc<str>, Vec<Open>>,
     /// Local newtype declarations.
-    tys: FxHashMap<Rc<str>, ItemId>,
+    tys: FxHashMap<Arc<str>, ItemId>,
     /// Local callable and newtype declarations.
-    terms: FxHashMap<Rc<str>, ItemId>,
+    terms: FxHashMap<Arc<str>, ItemId>,
     /// Local variables, including callable parameters, for loop bindings, etc.
     /// The u32 is the `valid_at` offset - the lowest offset at which the variable name is available.
     /// It's used to determine which variables are visible at a specific offset in the scope.
@@ -128,9 +133,9 @@ pub struct Scope {
     /// Bug: Because we keep track of only one `valid_at` offset per name,
     /// when a variable is later shadowed in the same scope,
     /// it is missed in the list. https://github.com/microsoft/qsharp/issues/897
-    vars: FxHashMap<Rc<str>, (u32, NodeId)>,
+    vars: FxHashMap<Arc<str>, (u32, NodeId)>,
     /// Type parameters.
-    ty_vars: FxHashMap<Rc<str>, ParamId>,
+    ty_vars: FxHashMap<Arc<str>, ParamId>,
 }
 
 impl Scope {
@@ -161,6 +166,9 @@ type ScopeId = usize;
 pub struct Locals {
     // order is ascending by span (outermost -> innermost)
     scopes: Vec<Scope>,
+    /// Whether each bound open statement, identified by the span of its namespace name,
+    /// was used to resolve at least one name.
+    opens_used: FxHashMap<Span, bool>,
 }
 
 impl Locals {
@@ -185,6 +193,13 @@ impl Locals {
             .unwrap_or_else(|| panic!("scope with id {id:?} should exist"))
     }
 
+    /// Returns true if the open statement whose namespace name is at `namespace_span`
+    /// refers to an existing namespace, but no name was resolved through it.
+    #[must_use]
+    pub fn is_unused_open(&self, namespace_span: Span) -> bool {
+        self.opens_used.get(&namespace_span) == Some(&false)
+    }
+
     #[must_use]
     pub fn get_all_at_offset(&self, offset: u32) -> Vec<Local> {
         let mut vars = true;
@@ -222,7 +237,7 @@ impl Locals {
 
 #[derive(Debug)]
 pub struct Local {
-    pub name: Rc<str>,
+    pub name: Arc<str>,
     pub kind: LocalKind,
 }
 
@@ -238,10 +253,13 @@ pub enum LocalKind {
 
 #[derive(Debug, Clone, Default)]
 pub struct GlobalScope {
-    tys: FxHashMap<Rc<str>, FxHashMap<Rc<str>, Res>>,
-    terms: FxHashMap<Rc<str>, FxHashMap<Rc<str>, Res>>,
-    namespaces: FxHashSet<Rc<str>>,
-    intrinsics: FxHashSet<Rc<str>>,
+    tys: FxHashMap<Arc<str>, FxHashMap<Arc<str>, Res>>,
+    terms: FxHashMap<Arc<str>, FxHashMap<Arc<str>, Res>>,
+    namespaces: FxHashSet<Arc<str>>,
+    intrinsics: FxHashSet<Arc<str>>,
+    /// The name spans of the items declared in the local package, used to point
+    /// duplicate declarations back at the first one.
+    decl_spans: FxHashMap<ItemId, Span>,
 }
 
 impl GlobalScope {
@@ -256,7 +274,7 @@ impl GlobalScope {
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 enum ScopeKind {
-    Namespace(Rc<str>),
+    Namespace(Arc<str>),
     Callable,
     Block,
 }
@@ -269,14 +287,14 @@ enum NameKind {
 
 #[derive(Debug, Clone)]
 struct Open {
-    namespace: Rc<str>,
+    namespace: Arc<str>,
     span: Span,
 }
 
 pub(super) struct Resolver {
     names: Names,
     dropped_names: Vec<TrackedName>,
-    curr_params: Option<FxHashSet<Rc<str>>>,
+    curr_params: Option<FxHashSet<Arc<str>>>,
     curr_scope_chain: Vec<ScopeId>,
     globals: GlobalScope,
     locals: Locals,
@@ -383,7 +401,8 @@ impl Resolver {
             name,
             &namespace,
         ) {
-            Ok(res) => {
+            Ok((res, open)) => {
+                self.use_open(open);
                 self.check_item_status(res, name.name.to_string(), name.span);
                 self.names.insert(name.id, res);
             }
@@ -402,7 +421,8 @@ impl Resolver {
             name,
             namespace,
         ) {
-            Ok(res) => {
+            Ok((res, open)) => {
+                self.use_open(open);
                 self.check_item_status(res, path.name.name.to_string(), path.span);
                 self.names.insert(path.id, res);
             }
@@ -442,18 +462,18 @@ impl Resolver {
         &mut self,
         pat: &ast::Pat,
         valid_at: u32,
-        bindings: &mut FxHashSet<Rc<str>>,
+        bindings: &mut FxHashSet<Arc<str>>,
     ) {
         match &*pat.kind {
             ast::PatKind::Bind(name, _) => {
-                if !bindings.insert(Rc::clone(&name.name)) {
+                if !bindings.insert(Arc::clone(&name.name)) {
                     self.errors
                         .push(Error::DuplicateBinding(name.name.to_string(), name.span));
                 }
                 self.names.insert(name.id, Res::Local(name.id));
                 self.current_scope_mut()
                     .vars
-                    .insert(Rc::clone(&name.name), (valid_at, name.id));
+                    .insert(Arc::clone(&name.name), (valid_at, name.id));
             }
             ast::PatKind::Discard(_) | ast::PatKind::Elided | ast::PatKind::Err => {}
             ast::PatKind::Paren(pat) => self.bind_pat_recursive(pat, valid_at, bindings),
@@ -464,14 +484,15 @@ impl Resolver {
     }
 
     fn bind_open(&mut self, name: &ast::Ident, alias: &Option<Box<ast::Ident>>) {
-        let alias = alias.as_ref().map_or("".into(), |a| Rc::clone(&a.name));
+        let alias = alias.as_ref().map_or("".into(), |a| Arc::clone(&a.name));
         if self.globals.namespaces.contains(&name.name) {
+            self.locals.opens_used.entry(name.span).or_insert(false);
             self.current_scope_mut()
                 .opens
                 .entry(alias)
                 .or_default()
                 .push(Open {
-                    namespace: Rc::clone(&name.name),
+                    namespace: Arc::clone(&name.name),
                     span: name.span,
                 });
         } else {
@@ -480,6 +501,12 @@ impl Resolver {
         }
     }
 
+    fn use_open(&mut self, open: Option<Span>) {
+        if let Some(span) = open {
+            self.locals.opens_used.insert(span, true);
+        }
+    }
+
     pub(super) fn bind_local_item(&mut self, assigner: &mut Assigner, item: &ast::Item) {
         match &*item.kind {
             ast::ItemKind::Open(name, alias) => self.bind_open(name, alias),
@@ -494,7 +521,7 @@ impl Resolver {
                 );
                 self.current_scope_mut()
                     .terms
-                    .insert(Rc::clone(&decl.name.name), id);
+                    .insert(Arc::clone(&decl.name.name), id);
             }
             ast::ItemKind::Ty(name, _) => {
                 let id = intrapackage(assigner.next_item());
@@ -506,8 +533,8 @@ impl Resolver {
                     ),
                 );
                 let scope = self.current_scope_mut();
-                scope.tys.insert(Rc::clone(&name.name), id);
-                scope.terms.insert(Rc::clone(&name.name), id);
+                scope.tys.insert(Arc::clone(&name.name), id);
+                scope.terms.insert(Arc::clone(&name.name), id);
             }
             ast::ItemKind::Err => {}
         }
@@ -517,7 +544,7 @@ impl Resolver {
         decl.generics.iter().enumerate().for_each(|(ix, ident)| {
             self.current_scope_mut()
                 .ty_vars
-                .insert(Rc::clone(&ident.name), ix.into());
+                .insert(Arc::clone(&ident.name), ix.into());
             self.names.insert(ident.id, Res::Param(ix.into()));
         });
     }
@@ -593,7 +620,7 @@ impl With<'_> {
 
 impl AstVisitor<'_> for With<'_> {
     fn visit_namespace(&mut self, namespace: &ast::Namespace) {
-        let kind = ScopeKind::Namespace(Rc::clone(&namespace.name.name));
+        let kind = ScopeKind::Namespace(Arc::clone(&namespace.name.name));
         self.with_scope(namespace.span, kind, |visitor| {
             for item in &*namespace.items {
                 if let ast::ItemKind::Open(name, alias) = &*item.kind {
@@ -613,10 +640,10 @@ impl AstVisitor<'_> for With<'_> {
     }
 
     fn visit_callable_decl(&mut self, decl: &ast::CallableDecl) {
-        fn collect_param_names(pat: &ast::Pat, names: &mut FxHashSet<Rc<str>>) {
+        fn collect_param_names(pat: &ast::Pat, names: &mut FxHashSet<Arc<str>>) {
             match &*pat.kind {
                 ast::PatKind::Bind(name, _) => {
-                    names.insert(Rc::clone(&name.name));
+                    names.insert(Arc::clone(&name.name));
                 }
                 ast::PatKind::Discard(_) | ast::PatKind::Elided | ast::PatKind::Err => {}
                 ast::PatKind::Paren(pat) => collect_param_names(pat, names),
@@ -740,7 +767,7 @@ pub(super) struct GlobalTable {
 
 impl GlobalTable {
     pub(super) fn new() -> Self {
-        let builtins: [(Rc<str>, Res); 10] = [
+        let builtins: [(Arc<str>, Res); 10] = [
             ("BigInt".into(), Res::PrimTy(Prim::BigInt)),
             ("Bool".into(), Res::PrimTy(Prim::Bool)),
             ("Double".into(), Res::PrimTy(Prim::Double)),
@@ -752,11 +779,11 @@ impl GlobalTable {
             ("String".into(), Res::PrimTy(Prim::String)),
             ("Unit".into(), Res::UnitTy),
         ];
-        let mut core: FxHashMap<Rc<str>, Res> = FxHashMap::default();
+        let mut core: FxHashMap<Arc<str>, Res> = FxHashMap::default();
         for (name, res) in builtins {
             core.insert(name, res);
         }
-        let mut tys: FxHashMap<Rc<str>, FxHashMap<Rc<str>, Res>> = FxHashMap::default();
+        let mut tys: FxHashMap<Arc<str>, FxHashMap<Arc<str>, Res>> = FxHashMap::default();
         tys.insert("Microsoft.Quantum.Core".into(), core);
 
         Self {
@@ -766,6 +793,7 @@ impl GlobalTable {
                 terms: FxHashMap::default(),
                 namespaces: FxHashSet::default(),
                 intrinsics: FxHashSet::default(),
+                decl_spans: FxHashMap::default(),
             },
         }
     }
@@ -840,7 +868,7 @@ fn bind_global_items(
         namespace.name.id,
         Res::Item(intrapackage(assigner.next_item()), ItemStatus::Available),
     );
-    scope.namespaces.insert(Rc::clone(&namespace.name.name));
+    scope.namespaces.insert(Arc::clone(&namespace.name.name));
 
     for item in &*namespace.items {
         match bind_global_item(
@@ -859,7 +887,7 @@ fn bind_global_items(
 /// Tries to extract a field name from an expression in cases where it is syntactically ambiguous
 /// whether the expression is a field name or a variable name. This applies to the index operand in
 /// a ternary update operator.
-pub(super) fn extract_field_name<'a>(names: &Names, expr: &'a ast::Expr) -> Option<&'a Rc<str>> {
+pub(super) fn extract_field_name<'a>(names: &Names, expr: &'a ast::Expr) -> Option<&'a Arc<str>> {
     // Follow the same reasoning as `is_field_update`.
     match &*expr.kind {
         ast::ExprKind::Path(path)
@@ -885,7 +913,7 @@ fn is_field_update<'a>(
                 let namespace = &path.namespace;
                 resolve(NameKind::Term, globals, scopes, name, namespace)
             },
-            Ok(Res::Local(_))
+            Ok((Res::Local(_), _))
         ),
         _ => false,
     }
@@ -901,7 +929,7 @@ fn ast_attrs_as_hir_attrs(attrs: &[Box<ast::Attr>]) -> Vec<hir::Attr> {
 fn bind_global_item(
     names: &mut Names,
     scope: &mut GlobalScope,
-    namespace: &Rc<str>,
+    namespace: &Arc<str>,
     next_id: impl FnOnce() -> ItemId,
     item: &ast::Item,
 ) -> Result<(), Vec<Error>> {
@@ -914,21 +942,23 @@ fn bind_global_item(
             let mut errors = Vec::new();
             match scope
                 .terms
-                .entry(Rc::clone(namespace))
+                .entry(Arc::clone(namespace))
                 .or_default()
-                .entry(Rc::clone(&decl.name.name))
+                .entry(Arc::clone(&decl.name.name))
             {
-                Entry::Occupied(_) => errors.push(Error::Duplicate(
+                Entry::Occupied(entry) => errors.push(Error::Duplicate(
                     decl.name.name.to_string(),
                     namespace.to_string(),
                     decl.name.span,
+                    first_decl_span(&scope.decl_spans, entry.get()),
                 )),
                 Entry::Vacant(entry) => {
                     entry.insert(res);
+                    scope.decl_spans.insert(item_id, decl.name.span);
                 }
             }
 
-            if decl_is_intrinsic(decl) && !scope.intrinsics.insert(Rc::clone(&decl.name.name)) {
+            if decl_is_intrinsic(decl) && !scope.intrinsics.insert(Arc::clone(&decl.name.name)) {
                 errors.push(Error::DuplicateIntrinsic(
                     decl.name.name.to_string(),
                     decl.name.span,
@@ -949,23 +979,27 @@ fn bind_global_item(
             match (
                 scope
                     .terms
-                    .entry(Rc::clone(namespace))
+                    .entry(Arc::clone(namespace))
                     .or_default()
-                    .entry(Rc::clone(&name.name)),
+                    .entry(Arc::clone(&name.name)),
                 scope
                     .tys
-                    .entry(Rc::clone(namespace))
+                    .entry(Arc::clone(namespace))
                     .or_default()
-                    .entry(Rc::clone(&name.name)),
+                    .entry(Arc::clone(&name.name)),
             ) {
-                (Entry::Occupied(_), _) | (_, Entry::Occupied(_)) => Err(vec![Error::Duplicate(
-                    name.name.to_string(),
-                    namespace.to_string(),
-                    name.span,
-                )]),
+                (Entry::Occupied(entry), _) | (_, Entry::Occupied(entry)) => {
+                    Err(vec![Error::Duplicate(
+                        name.name.to_string(),
+                        namespace.to_string(),
+                        name.span,
+                        first_decl_span(&scope.decl_spans, entry.get()),
+                    )])
+                }
                 (Entry::Vacant(term_entry), Entry::Vacant(ty_entry)) => {
                     term_entry.insert(res);
                     ty_entry.insert(res);
+                    scope.decl_spans.insert(item_id, name.span);
                     Ok(())
                 }
             }
@@ -974,6 +1008,15 @@ fn bind_global_item(
     }
 }
 
+/// Returns the name span of the declaration that `res` refers to, if it was declared in
+/// the local package.
+fn first_decl_span(decl_spans: &FxHashMap<ItemId, Span>, res: &Res) -> Option<Span> {
+    match res {
+        Res::Item(id, _) => decl_spans.get(id).copied(),
+        _ => None,
+    }
+}
+
 fn decl_is_intrinsic(decl: &ast::CallableDecl) -> bool {
     if let CallableBody::Specs(specs) = decl.body.as_ref() {
         specs
@@ -984,13 +1027,15 @@ fn decl_is_intrinsic(decl: &ast::CallableDecl) -> bool {
     }
 }
 
+/// Resolves a name, returning the resolution together with the span of the explicit open
+/// it was found through, if any.
 fn resolve<'a>(
     kind: NameKind,
     globals: &GlobalScope,
     scopes: impl Iterator<Item = &'a Scope>,
     name: &Ident,
     namespace: &Option<Box<Ident>>,
-) -> Result<Res, Error> {
+) -> Result<(Res, Option<Span>), Error> {
     let scopes = scopes.collect::<Vec<_>>();
     let mut candidates = FxHashMap::default();
     let mut vars = true;
@@ -1000,7 +1045,7 @@ fn resolve<'a>(
         if namespace.is_empty() {
             if let Some(res) = resolve_scope_locals(kind, globals, scope, vars, name_str) {
                 // Local declarations shadow everything.
-                return Ok(res);
+                return Ok((res, None));
             }
         }
 
@@ -1041,14 +1086,14 @@ fn resolve<'a>(
             });
         }
         if let Some((res, _)) = single(candidates) {
-            return Ok(res);
+            return Ok((res, None));
         }
     }
 
     if candidates.is_empty() {
         if let Some(&res) = globals.get(kind, namespace, name_str) {
             // An unopened global is the last resort.
-            return Ok(res);
+            return Ok((res, None));
         }
     }
 
@@ -1079,7 +1124,8 @@ fn resolve<'a>(
             second_open_span: opens[1].span,
         })
     } else {
-        single(candidates.into_keys())
+        single(candidates)
+            .map(|(res, open)| (res, Some(open.span)))
             .ok_or_else(|| Error::NotFound(name_str.to_string(), name.span))
     }
 }
@@ -202,7 +202,7 @@ fn global_callable_duplicate_error() {
                 operation item2() : Unit {}
             }
 
-            // Duplicate("A", "Foo", Span { lo: 57, hi: 58 })
+            // Duplicate("A", "Foo", Span { lo: 57, hi: 58 }, Some(Span { lo: 29, hi: 30 }))
         "#]],
     );
 }
@@ -854,7 +854,7 @@ fn ty_decl_duplicate_error() {
                 newtype item2 = Bool;
             }
 
-            // Duplicate("A", "Foo", Span { lo: 50, hi: 51 })
+            // Duplicate("A", "Foo", Span { lo: 50, hi: 51 }, Some(Span { lo: 28, hi: 29 }))
         "#]],
     );
 }
@@ -872,7 +872,7 @@ fn ty_decl_duplicate_error_on_built_in_ty() {
                 newtype item1 = Unit;
             }
 
-            // Duplicate("Pauli", "Microsoft.Quantum.Core", Span { lo: 47, hi: 52 })
+            // Duplicate("Pauli", "Microsoft.Quantum.Core", Span { lo: 47, hi: 52 }, None)
         "#]],
     );
 }
@@ -2096,7 +2096,7 @@ fn disallow_duplicate_intrinsic_and_non_intrinsic_collision() {
                 }
             }
 
-            // Duplicate("C", "B", Span { lo: 154, hi: 155 })
+            // Duplicate("C", "B", Span { lo: 154, hi: 155 }, Some(Span { lo: 110, hi: 111 }))
             // DuplicateIntrinsic("C", Span { lo: 154, hi: 155 })
         "#]],
     );
@@ -1,7 +1,7 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
-use std::rc::Rc;
+use std::sync::Arc;
 
 use crate::resolve::{self, Names};
 use qsc_ast::ast::{
@@ -85,7 +85,7 @@ pub(crate) fn ty_from_ast(names: &Names, ty: &ast::Ty) -> (Ty, Vec<MissingTyErro
 
 pub(super) fn ast_ty_def_cons(
     names: &Names,
-    ty_name: &Rc<str>,
+    ty_name: &Arc<str>,
     id: hir::ItemId,
     def: &TyDef,
 ) -> (Scheme, Vec<MissingTyError>) {
@@ -3475,7 +3475,7 @@ fn duplicate_callable_decls_inferred_and_ignored() {
             }
         "},
         "",
-        &expect![[r#"
+        &expect![[r##"
             #6 33-35 "()" : Unit
             #10 43-51 "{ true }" : Bool
             #12 45-49 "true" : Bool
@@ -3487,8 +3487,8 @@ fn duplicate_callable_decls_inferred_and_ignored() {
             #32 127-132 "Foo()" : Bool
             #33 127-130 "Foo" : (Unit -> Bool)
             #36 130-132 "()" : Unit
-            Error(Resolve(Duplicate("Foo", "Test", Span { lo: 65, hi: 68 })))
-        "#]],
+            Error(Resolve(Duplicate("Foo", "Test", Span { lo: 65, hi: 68 }, Some(Span { lo: 30, hi: 33 }))))
+        "##]],
     );
 }
 
@@ -3505,7 +3505,7 @@ fn duplicate_type_decls_inferred_and_ignored() {
             }
         "},
         "",
-        &expect![[r#"
+        &expect![[r##"
             #18 81-83 "()" : Unit
             #22 91-127 "{\n        let val = Foo(true);\n    }" : Unit
             #24 105-108 "val" : UDT<"Foo": Item 1>
@@ -3513,8 +3513,8 @@ fn duplicate_type_decls_inferred_and_ignored() {
             #27 111-114 "Foo" : (Bool -> UDT<"Foo": Item 1>)
             #30 114-120 "(true)" : Bool
             #31 115-119 "true" : Bool
-            Error(Resolve(Duplicate("Foo", "Test", Span { lo: 53, hi: 56 })))
-        "#]],
+            Error(Resolve(Duplicate("Foo", "Test", Span { lo: 53, hi: 56 }, Some(Span { lo: 29, hi: 32 }))))
+        "##]],
     );
 }
 
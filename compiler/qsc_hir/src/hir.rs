@@ -13,9 +13,9 @@ use std::{
     cmp::Ordering,
     fmt::{self, Debug, Display, Formatter, Write},
     hash::{Hash, Hasher},
-    rc::Rc,
     result,
     str::FromStr,
+    sync::Arc,
 };
 
 fn set_indentation<'a, 'b>(
@@ -288,7 +288,7 @@ pub struct Item {
     /// The parent item.
     pub parent: Option<LocalItemId>,
     /// The documentation.
-    pub doc: Rc<str>,
+    pub doc: Arc<str>,
     /// The attributes.
     pub attrs: Vec<Attr>,
     /// The visibility.
@@ -1008,7 +1008,7 @@ pub enum StringComponent {
     /// An expression.
     Expr(Expr),
     /// A string literal.
-    Lit(Rc<str>),
+    Lit(Arc<str>),
 }
 
 /// A pattern.
@@ -1143,7 +1143,7 @@ pub struct Ident {
     /// The span.
     pub span: Span,
     /// The identifier name.
-    pub name: Rc<str>,
+    pub name: Arc<str>,
 }
 
 impl Display for Ident {
@@ -1161,6 +1161,10 @@ pub enum Attr {
     EntryPoint,
     /// Indicates that an item does not have an implementation available for use.
     Unimplemented,
+    /// Indicates that a callable is a test case.
+    Test,
+    /// Indicates that an item is deprecated, with the name of its replacement, if any.
+    Deprecated(Option<Arc<str>>),
 }
 
 impl FromStr for Attr {
@@ -1171,6 +1175,9 @@ impl FromStr for Attr {
             "Config" => Ok(Self::Config),
             "EntryPoint" => Ok(Self::EntryPoint),
             "Unimplemented" => Ok(Self::Unimplemented),
+            "Test" => Ok(Self::Test),
+            // The replacement is an argument of the attribute, so it is filled in during lowering.
+            "Deprecated" => Ok(Self::Deprecated(None)),
             _ => Err(()),
         }
     }
@@ -8,7 +8,7 @@ use rustc_hash::FxHashMap;
 use crate::hir::{CallableKind, FieldPath, Functor, ItemId, PackageId, Res};
 use std::{
     fmt::{self, Debug, Display, Formatter, Write},
-    rc::Rc,
+    sync::Arc,
 };
 
 fn set_indentation<'a, 'b>(
@@ -33,13 +33,13 @@ pub enum Ty {
     /// A placeholder type variable used during type inference.
     Infer(InferTyId),
     /// A type parameter.
-    Param(Rc<str>, ParamId),
+    Param(Arc<str>, ParamId),
     /// A primitive type.
     Prim(Prim),
     /// A tuple type.
     Tuple(Vec<Ty>),
     /// A user-defined type.
-    Udt(Rc<str>, Res),
+    Udt(Arc<str>, Res),
     /// An invalid type.
     #[default]
     Err,
@@ -279,7 +279,7 @@ pub struct TypeParamName {
     /// The span.
     pub span: Span,
     /// The name.
-    pub name: Rc<str>,
+    pub name: Arc<str>,
 }
 
 impl Display for TypeParamName {
@@ -528,7 +528,7 @@ pub struct Udt {
     /// The span.
     pub span: Span,
     /// The name.
-    pub name: Rc<str>,
+    pub name: Arc<str>,
     // The definition.
     pub definition: UdtDef,
 }
@@ -704,7 +704,7 @@ pub struct UdtField {
     /// The span of the field name.
     pub name_span: Option<Span>,
     /// The field name.
-    pub name: Option<Rc<str>>,
+    pub name: Option<Arc<str>>,
     // The field type.
     pub ty: Ty,
 }
@@ -10,7 +10,8 @@
 //! # Usage
 //!
 //! The entry points to the linter is the `run_lints` function, which takes
-//! a [`qsc_frontend::compile::CompileUnit`] as input and outputs a [`Vec<Lint>`](Lint).
+//! a [`qsc_frontend::compile::CompileUnit`] and the package store holding its
+//! dependencies as input and outputs a [`Vec<Lint>`](Lint).
 //!
 //! ## Example
 //!
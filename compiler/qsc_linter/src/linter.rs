@@ -8,29 +8,93 @@ use self::{ast::run_ast_lints, hir::run_hir_lints};
 use crate::lints::{ast::AstLint, hir::HirLint};
 use miette::{Diagnostic, LabeledSpan};
 use qsc_data_structures::span::Span;
-use qsc_frontend::compile::CompileUnit;
+use qsc_frontend::compile::{CompileUnit, PackageStore};
 use serde::{Deserialize, Serialize};
-use std::fmt::Display;
+use std::{borrow::Cow, fmt::Display};
 
 /// The entry point to the linter. It takes a [`qsc_frontend::compile::CompileUnit`]
-/// as input and outputs a [`Vec<Lint>`](Lint).
+/// and the [`PackageStore`] holding its dependencies as input
+/// and outputs a [`Vec<Lint>`](Lint), sorted by span.
 #[must_use]
-pub fn run_lints(compile_unit: &CompileUnit, config: Option<&[LintConfig]>) -> Vec<Lint> {
-    let mut ast_lints = run_ast_lints(&compile_unit.ast.package, config);
-    let mut hir_lints = run_hir_lints(&compile_unit.package, config);
+pub fn run_lints(
+    package_store: &PackageStore,
+    compile_unit: &CompileUnit,
+    config: Option<&[LintConfig]>,
+) -> Vec<Lint> {
+    let (ast_lints, hir_lints) = run_passes(package_store, compile_unit, config);
+    merge_lints(ast_lints, hir_lints)
+}
+
+/// Runs the AST and HIR lint passes in parallel, since each only reads the package.
+#[cfg(not(target_family = "wasm"))]
+fn run_passes(
+    package_store: &PackageStore,
+    compile_unit: &CompileUnit,
+    config: Option<&[LintConfig]>,
+) -> (Vec<Lint>, Vec<Lint>) {
+    std::thread::scope(|scope| {
+        let hir_lints = scope.spawn(|| run_hir_lints(&compile_unit.package, package_store, config));
+        let ast_lints = run_ast_lints(
+            &compile_unit.ast.package,
+            &compile_unit.ast.locals,
+            &compile_unit.ast.tys,
+            config,
+        );
+        (
+            ast_lints,
+            hir_lints.join().expect("HIR lint pass should not panic"),
+        )
+    })
+}
+
+/// Runs the AST and HIR lint passes one after the other, on targets without threads.
+#[cfg(target_family = "wasm")]
+fn run_passes(
+    package_store: &PackageStore,
+    compile_unit: &CompileUnit,
+    config: Option<&[LintConfig]>,
+) -> (Vec<Lint>, Vec<Lint>) {
+    run_passes_sequentially(package_store, compile_unit, config)
+}
 
+#[cfg(any(target_family = "wasm", test))]
+pub(crate) fn run_passes_sequentially(
+    package_store: &PackageStore,
+    compile_unit: &CompileUnit,
+    config: Option<&[LintConfig]>,
+) -> (Vec<Lint>, Vec<Lint>) {
+    (
+        run_ast_lints(
+            &compile_unit.ast.package,
+            &compile_unit.ast.locals,
+            &compile_unit.ast.tys,
+            config,
+        ),
+        run_hir_lints(&compile_unit.package, package_store, config),
+    )
+}
+
+/// Combines the lints found by the passes, dropping the allowed ones.
+pub(crate) fn merge_lints(mut ast_lints: Vec<Lint>, mut hir_lints: Vec<Lint>) -> Vec<Lint> {
     let mut lints = Vec::new();
     lints.append(&mut ast_lints);
     lints.append(&mut hir_lints);
-    lints
+    let mut lints: Vec<Lint> = lints
         .into_iter()
         .filter(|lint| !matches!(lint.level, LintLevel::Allow))
-        .collect()
+        .collect();
+
+    // Report lints in source order rather than in the order the passes found them,
+    // so the output does not depend on which pass finishes first.
+    lints.sort_by_key(|lint| (lint.span.lo, lint.span.hi));
+    lints
 }
 
 /// A lint emited by the linter.
 #[derive(Debug, Clone, thiserror::Error)]
 pub struct Lint {
+    /// The lint that was reported.
+    pub kind: LintKind,
     /// A span indicating where the diagnostic is in the source code.
     pub span: Span,
     /// The lint level: allow, warning, error.
@@ -38,7 +102,10 @@ pub struct Lint {
     /// The message the user will see in the code editor.
     pub message: &'static str,
     /// The help text the user will see in the code editor.
-    pub help: &'static str,
+    pub help: Cow<'static, str>,
+    /// Edits that fix the lint when applied together, each replacing the
+    /// text at a span with new text. Empty if the lint has no automatic fix.
+    pub code_action_edits: Vec<(Span, String)>,
 }
 
 impl std::fmt::Display for Lint {
@@ -48,6 +115,10 @@ impl std::fmt::Display for Lint {
 }
 
 impl Diagnostic for Lint {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        Some(Box::new(self.kind.code()))
+    }
+
     fn severity(&self) -> Option<miette::Severity> {
         match self.level {
             LintLevel::Allow => None,
@@ -66,14 +137,14 @@ impl Diagnostic for Lint {
         if self.help.is_empty() {
             None
         } else {
-            Some(Box::new(self.help))
+            Some(Box::new(self.help.as_ref()))
         }
     }
 }
 
 /// A lint level. This defines if a lint will be treated as a warning or an error,
 /// and if the lint level can be overriden by the user.
-#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub enum LintLevel {
     /// The lint is effectively disabled.
@@ -101,17 +172,21 @@ impl Display for LintLevel {
 }
 
 /// End-user configuration for each lint level.
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub struct LintConfig {
     #[serde(rename = "lint")]
     /// Represents the lint name.
     pub kind: LintKind,
     /// The lint level.
     pub level: LintLevel,
+    /// The maximum number of parameters a callable can declare before
+    /// the `longParameterList` lint fires. Ignored by other lints.
+    #[serde(default, rename = "maxParams", skip_serializing_if = "Option::is_none")]
+    pub max_params: Option<usize>,
 }
 
 /// Represents a lint name.
-#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(untagged)]
 pub enum LintKind {
     /// AST lint name.
@@ -119,3 +194,16 @@ pub enum LintKind {
     /// HIR lint name.
     Hir(HirLint),
 }
+
+impl LintKind {
+    /// The diagnostic code of the lint, `Qsc.Lint.` followed by the lint name
+    /// in `PascalCase`, e.g. `Qsc.Lint.DivisionByZero`. Unlike the message,
+    /// the code does not change between releases.
+    #[must_use]
+    pub fn code(self) -> &'static str {
+        match self {
+            LintKind::Ast(lint) => lint.code(),
+            LintKind::Hir(lint) => lint.code(),
+        }
+    }
+}
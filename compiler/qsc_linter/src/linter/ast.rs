@@ -1,10 +1,7 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
-use crate::{
-    lints::ast::{AstLint, CombinedAstLints},
-    Lint, LintConfig, LintLevel,
-};
+use crate::{lints::ast::CombinedAstLints, Lint, LintConfig};
 use qsc_ast::{
     ast::{
         Attr, Block, CallableDecl, Expr, FunctorExpr, Ident, Item, Namespace, Package, Pat, Path,
@@ -12,24 +9,19 @@ use qsc_ast::{
     },
     visit::Visitor,
 };
+use qsc_frontend::{resolve::Locals, typeck::Table};
 
-/// The entry point to the AST linter. It takes a [`qsc_ast::ast::Package`]
-/// as input and outputs a [`Vec<Lint>`](Lint).
+/// The entry point to the AST linter. It takes a [`qsc_ast::ast::Package`],
+/// the [`Locals`] produced when resolving its names and the [`Table`] of types
+/// produced when type checking it as input, and outputs a [`Vec<Lint>`](Lint).
 #[must_use]
-pub fn run_ast_lints(package: &qsc_ast::ast::Package, config: Option<&[LintConfig]>) -> Vec<Lint> {
-    let config: Vec<(AstLint, LintLevel)> = config
-        .unwrap_or(&[])
-        .iter()
-        .filter_map(|lint_config| {
-            if let LintKind::Ast(kind) = lint_config.kind {
-                Some((kind, lint_config.level))
-            } else {
-                None
-            }
-        })
-        .collect();
-
-    let mut lints = CombinedAstLints::from_config(config);
+pub fn run_ast_lints(
+    package: &qsc_ast::ast::Package,
+    locals: &Locals,
+    tys: &Table,
+    config: Option<&[LintConfig]>,
+) -> Vec<Lint> {
+    let mut lints = CombinedAstLints::from_config(config.unwrap_or(&[]), locals, tys);
 
     for node in package.nodes.iter() {
         match node {
@@ -46,14 +38,21 @@ pub fn run_ast_lints(package: &qsc_ast::ast::Package, config: Option<&[LintConfi
 /// The trait provides default empty implementations for the rest of the methods,
 /// which will be optimized to a no-op by the rust compiler.
 pub(crate) trait AstLintPass {
+    /// Reads any lint-specific settings from the user's configuration for this lint.
+    fn configure(&mut self, _config: &LintConfig) {}
     fn check_attr(&self, _attr: &Attr, _buffer: &mut Vec<Lint>) {}
     fn check_block(&self, _block: &Block, _buffer: &mut Vec<Lint>) {}
     fn check_callable_decl(&self, _callable_decl: &CallableDecl, _buffer: &mut Vec<Lint>) {}
     fn check_expr(&self, _expr: &Expr, _buffer: &mut Vec<Lint>) {}
+    /// Called for each expression, with the types type checking gave to the package's nodes.
+    /// The types are available even when type checking reports errors.
+    fn check_expr_with_tys(&self, _expr: &Expr, _tys: &Table, _buffer: &mut Vec<Lint>) {}
     fn check_functor_expr(&self, _functor_expr: &FunctorExpr, _buffer: &mut Vec<Lint>) {}
     fn check_ident(&self, _ident: &Ident, _buffer: &mut Vec<Lint>) {}
     fn check_item(&self, _item: &Item, _buffer: &mut Vec<Lint>) {}
     fn check_namespace(&self, _namespace: &Namespace, _buffer: &mut Vec<Lint>) {}
+    /// Called for each `open` item, with whether name resolution found it unused.
+    fn check_open(&self, _item: &Item, _is_unused: bool, _buffer: &mut Vec<Lint>) {}
     fn check_package(&self, _package: &Package, _buffer: &mut Vec<Lint>) {}
     fn check_pat(&self, _pat: &Pat, _buffer: &mut Vec<Lint>) {}
     fn check_path(&self, _path: &Path, _buffer: &mut Vec<Lint>) {}
@@ -66,7 +65,8 @@ pub(crate) trait AstLintPass {
 }
 
 /// This macro allow us to declare lints while avoiding boilerplate. It does three things:
-///  1. Declares the lint structs with their default [`LintLevel`] and message.
+///  1. Declares the lint structs with their default [`LintLevel`] and message, and any
+///     settings the lint reads in [`AstLintPass::configure`], given as `name: Type = default`.
 ///  2. Declares & Implements the [`AstLintsConfig`] struct.
 ///  3. Declares & Implements the [`CombinedAstLints`] struct.
 ///
@@ -74,22 +74,23 @@ pub(crate) trait AstLintPass {
 /// declarations and implementations of [`AstLintsConfig`] and [`CombinedAstLints`] for
 /// the lint to be integrated with the our linting infrastructure.
 macro_rules! declare_ast_lints {
-    ($( ($lint_name:ident, $default_level:expr, $msg:expr, $help:expr) ),* $(,)?) => {
+    ($( ($lint_name:ident, $default_level:expr, $msg:expr, $help:expr $(, $field:ident: $field_ty:ty = $field_default:expr)*) ),* $(,)?) => {
         // Declare the structs representing each lint.
         use crate::{Lint, LintLevel, linter::ast::AstLintPass};
-        $(declare_ast_lints!{ @LINT_STRUCT $lint_name, $default_level, $msg, $help})*
+        $(declare_ast_lints!{ @LINT_STRUCT $lint_name, $default_level, $msg, $help $(, $field: $field_ty = $field_default)*})*
 
         // This is a silly wrapper module to avoid contaminating the environment
         // calling the macro with unwanted imports.
         mod _ast_macro_expansion {
-            use crate::{linter::ast::{declare_ast_lints, AstLintPass}, Lint, LintLevel};
+            use crate::{linter::ast::{declare_ast_lints, AstLintPass}, Lint, LintConfig, LintKind};
             use qsc_ast::{
                 ast::{
-                    Attr, Block, CallableDecl, Expr, FunctorExpr, Ident, Item, Namespace, Package, Pat, Path,
+                    Attr, Block, CallableDecl, Expr, FunctorExpr, Ident, Item, ItemKind, Namespace, Package, Pat, Path,
                     QubitInit, SpecDecl, Stmt, Ty, TyDef, Visibility,
                 },
                 visit::{self, Visitor},
             };
+            use qsc_frontend::{resolve::Locals, typeck::Table};
             use super::{$($lint_name),*};
 
             // Declare & implement the `AstLintsConfig` and CombinedAstLints structs.
@@ -105,27 +106,33 @@ macro_rules! declare_ast_lints {
     };
 
     // Declare & implement a struct representing a lint.
-    (@LINT_STRUCT $lint_name:ident, $default_level:expr, $msg:expr, $help:expr) => {
+    (@LINT_STRUCT $lint_name:ident, $default_level:expr, $msg:expr, $help:expr $(, $field:ident: $field_ty:ty = $field_default:expr)*) => {
         pub(crate) struct $lint_name {
             level: LintLevel,
             message: &'static str,
             help: &'static str,
+            $($field: $field_ty),*
         }
 
         impl Default for $lint_name {
             fn default() -> Self {
-                Self { level: Self::DEFAULT_LEVEL, message: $msg, help: $help }
+                Self { level: Self::DEFAULT_LEVEL, message: $msg, help: $help, $($field: $field_default),* }
             }
         }
 
         impl From<LintLevel> for $lint_name {
             fn from(value: LintLevel) -> Self {
-                Self { level: value, message: $msg, help: $help }
+                Self { level: value, message: $msg, help: $help, $($field: $field_default),* }
             }
         }
 
         impl $lint_name {
             const DEFAULT_LEVEL: LintLevel = $default_level;
+
+            #[allow(clippy::unused_self)]
+            fn kind(&self) -> crate::LintKind {
+                crate::LintKind::Ast(AstLint::$lint_name)
+            }
         }
     };
 
@@ -133,11 +140,21 @@ macro_rules! declare_ast_lints {
     (@CONFIG_ENUM $($lint_name:ident),*) => {
         use serde::{Deserialize, Serialize};
 
-        #[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
         #[serde(rename_all = "camelCase")]
         pub enum AstLint {
             $($lint_name),*
         }
+
+        impl AstLint {
+            /// The diagnostic code of the lint, see [`crate::LintKind::code`].
+            #[must_use]
+            pub fn code(self) -> &'static str {
+                match self {
+                    $(AstLint::$lint_name => concat!("Qsc.Lint.", stringify!($lint_name))),*
+                }
+            }
+        }
     };
 
     // Declare & implement the `CombinedAstLints` structure.
@@ -150,27 +167,30 @@ macro_rules! declare_ast_lints {
         /// Combined AST lints for speed. This combined lint allow us to
         /// evaluate all the lints in a single AST pass, instead of doing
         /// an individual pass for each lint in the linter.
-        pub(crate) struct CombinedAstLints {
+        pub(crate) struct CombinedAstLints<'a> {
             pub buffer: Vec<Lint>,
+            locals: &'a Locals,
+            tys: &'a Table,
             $($lint_name: $lint_name),*
         }
 
-        impl Default for CombinedAstLints {
-            fn default() -> Self {
-                Self {
+        // Most of the calls here are empty methods and they get optimized at compile time to a no-op.
+        impl<'a> CombinedAstLints<'a> {
+            pub fn from_config(config: &[LintConfig], locals: &'a Locals, tys: &'a Table) -> Self {
+                let mut combined_ast_lints = Self {
                     buffer: Vec::default(),
+                    locals,
+                    tys,
                     $($lint_name: <$lint_name>::default()),*
-                }
-            }
-        }
-
-        // Most of the calls here are empty methods and they get optimized at compile time to a no-op.
-        impl CombinedAstLints {
-            pub fn from_config(config: Vec<(AstLint, LintLevel)>) -> Self {
-                let mut combined_ast_lints = Self::default();
-                for (lint, level) in config {
-                    match lint {
-                        $(AstLint::$lint_name => combined_ast_lints.$lint_name.level = level),*
+                };
+                for lint_config in config {
+                    if let LintKind::Ast(lint) = lint_config.kind {
+                        match lint {
+                            $(AstLint::$lint_name => {
+                                combined_ast_lints.$lint_name.level = lint_config.level;
+                                combined_ast_lints.$lint_name.configure(lint_config);
+                            }),*
+                        }
                     }
                 }
                 combined_ast_lints
@@ -179,6 +199,7 @@ macro_rules! declare_ast_lints {
             fn check_package(&mut self, package: &Package) { $(self.$lint_name.check_package(package, &mut self.buffer));*; }
             fn check_namespace(&mut self, namespace: &Namespace) { $(self.$lint_name.check_namespace(namespace, &mut self.buffer));*; }
             fn check_item(&mut self, item: &Item) { $(self.$lint_name.check_item(item, &mut self.buffer));*; }
+            fn check_open(&mut self, item: &Item, is_unused: bool) { $(self.$lint_name.check_open(item, is_unused, &mut self.buffer));*; }
             fn check_attr(&mut self, attr: &Attr) { $(self.$lint_name.check_attr(attr, &mut self.buffer));*; }
             fn check_visibility(&mut self, visibility: &Visibility) { $(self.$lint_name.check_visibility(visibility, &mut self.buffer));*; }
             fn check_ty_def(&mut self, def: &TyDef) { $(self.$lint_name.check_ty_def(def, &mut self.buffer));*; }
@@ -189,13 +210,14 @@ macro_rules! declare_ast_lints {
             fn check_block(&mut self, block: &Block) { $(self.$lint_name.check_block(block, &mut self.buffer));*; }
             fn check_stmt(&mut self, stmt: &Stmt) { $(self.$lint_name.check_stmt(stmt, &mut self.buffer));*; }
             fn check_expr(&mut self, expr: &Expr) { $(self.$lint_name.check_expr(expr, &mut self.buffer));*; }
+            fn check_expr_with_tys(&mut self, expr: &Expr) { $(self.$lint_name.check_expr_with_tys(expr, self.tys, &mut self.buffer));*; }
             fn check_pat(&mut self, pat: &Pat) { $(self.$lint_name.check_pat(pat, &mut self.buffer));*; }
             fn check_qubit_init(&mut self, init: &QubitInit) { $(self.$lint_name.check_qubit_init(init, &mut self.buffer));*; }
             fn check_path(&mut self, path: &Path) { $(self.$lint_name.check_path(path, &mut self.buffer));*; }
             fn check_ident(&mut self, ident: &Ident) { $(self.$lint_name.check_ident(ident, &mut self.buffer));*; }
         }
 
-        impl<'a> Visitor<'a> for CombinedAstLints {
+        impl<'a> Visitor<'a> for CombinedAstLints<'_> {
             fn visit_package(&mut self, package: &'a Package) {
                 self.check_package(package);
                 visit::walk_package(self, package);
@@ -208,6 +230,9 @@ macro_rules! declare_ast_lints {
 
             fn visit_item(&mut self, item: &'a Item) {
                 self.check_item(item);
+                if let ItemKind::Open(name, _) = &*item.kind {
+                    self.check_open(item, self.locals.is_unused_open(name.span));
+                }
                 visit::walk_item(self, item);
             }
 
@@ -257,6 +282,7 @@ macro_rules! declare_ast_lints {
 
             fn visit_expr(&mut self, expr: &'a Expr) {
                 self.check_expr(expr);
+                self.check_expr_with_tys(expr);
                 visit::walk_expr(self, expr);
             }
 
@@ -283,5 +309,3 @@ macro_rules! declare_ast_lints {
 }
 
 pub(crate) use declare_ast_lints;
-
-use super::LintKind;
@@ -5,15 +5,24 @@ use crate::{
     lints::hir::{CombinedHirLints, HirLint},
     Lint, LintConfig, LintLevel,
 };
+use qsc_frontend::compile::PackageStore;
 use qsc_hir::{
-    hir::{Block, CallableDecl, Expr, Ident, Item, Package, Pat, QubitInit, SpecDecl, Stmt},
+    hir::{
+        Attr, Block, CallableDecl, Expr, Ident, Item, ItemId, Package, Pat, QubitInit, SpecDecl,
+        Stmt,
+    },
     visit::Visitor,
 };
 
 /// The entry point to the HIR linter. It takes a [`qsc_hir::hir::Package`]
-/// as input and outputs a [`Vec<Lint>`](Lint).
+/// and the [`PackageStore`] holding its dependencies as input
+/// and outputs a [`Vec<Lint>`](Lint).
 #[must_use]
-pub fn run_hir_lints(package: &Package, config: Option<&[LintConfig]>) -> Vec<Lint> {
+pub fn run_hir_lints(
+    package: &Package,
+    store: &PackageStore,
+    config: Option<&[LintConfig]>,
+) -> Vec<Lint> {
     let config: Vec<(HirLint, LintLevel)> = config
         .unwrap_or(&[])
         .iter()
@@ -26,7 +35,7 @@ pub fn run_hir_lints(package: &Package, config: Option<&[LintConfig]>) -> Vec<Li
         })
         .collect();
 
-    let mut lints = CombinedHirLints::from_config(config);
+    let mut lints = CombinedHirLints::from_config(config, package, store);
 
     for (_, item) in &package.items {
         lints.visit_item(item);
@@ -46,6 +55,15 @@ pub fn run_hir_lints(package: &Package, config: Option<&[LintConfig]>) -> Vec<Li
 pub(crate) trait HirLintPass {
     fn check_block(&self, _block: &Block, _buffer: &mut Vec<Lint>) {}
     fn check_callable_decl(&self, _callable_decl: &CallableDecl, _buffer: &mut Vec<Lint>) {}
+    /// Called for each expression that refers to an item marked `@Deprecated`,
+    /// with the replacement named by the attribute, if any.
+    fn check_deprecated_use(
+        &self,
+        _expr: &Expr,
+        _replacement: Option<&str>,
+        _buffer: &mut Vec<Lint>,
+    ) {
+    }
     fn check_expr(&self, _expr: &Expr, _buffer: &mut Vec<Lint>) {}
     fn check_ident(&self, _ident: &Ident, _buffer: &mut Vec<Lint>) {}
     fn check_item(&self, _item: &Item, _buffer: &mut Vec<Lint>) {}
@@ -73,9 +91,10 @@ macro_rules! declare_hir_lints {
         // This is a silly wrapper module to avoid contaminating the environment
         // calling the macro with unwanted imports.
         mod _hir_macro_expansion {
-            use crate::{linter::hir::{declare_hir_lints, HirLintPass}, Lint, LintLevel};
+            use crate::{linter::hir::{declare_hir_lints, deprecated_attr, HirLintPass}, Lint, LintLevel};
+            use qsc_frontend::compile::PackageStore;
             use qsc_hir::{
-                hir::{Block, CallableDecl, Expr, Ident, Item, Package, Pat, QubitInit, SpecDecl, Stmt},
+                hir::{Attr, Block, CallableDecl, Expr, ExprKind, Ident, Item, Package, Pat, QubitInit, Res, SpecDecl, Stmt},
                 visit::{self, Visitor},
             };
             use super::{$($lint_name),*};
@@ -114,6 +133,11 @@ macro_rules! declare_hir_lints {
 
         impl $lint_name {
             const DEFAULT_LEVEL: LintLevel = $default_level;
+
+            #[allow(clippy::unused_self)]
+            fn kind(&self) -> crate::LintKind {
+                crate::LintKind::Hir(HirLint::$lint_name)
+            }
         }
     };
 
@@ -121,11 +145,21 @@ macro_rules! declare_hir_lints {
     (@CONFIG_ENUM $($lint_name:ident),*) => {
         use serde::{Deserialize, Serialize};
 
-        #[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
         #[serde(rename_all = "camelCase")]
         pub enum HirLint {
             $($lint_name),*
         }
+
+        impl HirLint {
+            /// The diagnostic code of the lint, see [`crate::LintKind::code`].
+            #[must_use]
+            pub fn code(self) -> &'static str {
+                match self {
+                    $(HirLint::$lint_name => concat!("Qsc.Lint.", stringify!($lint_name))),*
+                }
+            }
+        }
     };
 
     // Declare & implement the `CombinedAstLints` structure.
@@ -138,24 +172,22 @@ macro_rules! declare_hir_lints {
         /// Combined HIR lints for speed. This combined lint allow us to
         /// evaluate all the lints in a single HIR pass, instead of doing
         /// an individual pass for each lint in the linter.
-        pub(crate) struct CombinedHirLints {
+        pub(crate) struct CombinedHirLints<'a> {
             pub buffer: Vec<Lint>,
+            package: &'a Package,
+            store: &'a PackageStore,
             $($lint_name: $lint_name),*
         }
 
-        impl Default for CombinedHirLints {
-            fn default() -> Self {
-                Self {
+        // Most of the calls here are empty methods and they get optimized at compile time to a no-op.
+        impl<'a> CombinedHirLints<'a> {
+            pub fn from_config(config: Vec<(HirLint, LintLevel)>, package: &'a Package, store: &'a PackageStore) -> Self {
+                let mut combined_hir_lints = Self {
                     buffer: Vec::default(),
+                    package,
+                    store,
                     $($lint_name: <$lint_name>::default()),*
-                }
-            }
-        }
-
-        // Most of the calls here are empty methods and they get optimized at compile time to a no-op.
-        impl CombinedHirLints {
-            pub fn from_config(config: Vec<(HirLint, LintLevel)>) -> Self {
-                let mut combined_hir_lints = Self::default();
+                };
                 for (lint, level) in config {
                     match lint {
                         $(HirLint::$lint_name => combined_hir_lints.$lint_name.level = level),*
@@ -166,6 +198,7 @@ macro_rules! declare_hir_lints {
 
             fn check_block(&mut self, block: &Block) { $(self.$lint_name.check_block(block, &mut self.buffer));* }
             fn check_callable_decl(&mut self, decl: &CallableDecl) { $(self.$lint_name.check_callable_decl(decl, &mut self.buffer));* }
+            fn check_deprecated_use(&mut self, expr: &Expr, replacement: Option<&str>) { $(self.$lint_name.check_deprecated_use(expr, replacement, &mut self.buffer));* }
             fn check_expr(&mut self, expr: &Expr) { $(self.$lint_name.check_expr(expr, &mut self.buffer));* }
             fn check_ident(&mut self, ident: &Ident) { $(self.$lint_name.check_ident(ident, &mut self.buffer));* }
             fn check_item(&mut self, item: &Item) { $(self.$lint_name.check_item(item, &mut self.buffer));* }
@@ -176,7 +209,7 @@ macro_rules! declare_hir_lints {
             fn check_stmt(&mut self, stmt: &Stmt) { $(self.$lint_name.check_stmt(stmt, &mut self.buffer));* }
         }
 
-        impl<'a> Visitor<'a> for CombinedHirLints {
+        impl<'a> Visitor<'a> for CombinedHirLints<'_> {
             fn visit_block(&mut self, block: &'a Block) {
                 self.check_block(block);
                 visit::walk_block(self, block);
@@ -189,6 +222,11 @@ macro_rules! declare_hir_lints {
 
             fn visit_expr(&mut self, expr: &'a Expr) {
                 self.check_expr(expr);
+                if let ExprKind::Var(Res::Item(item_id), _) = &expr.kind {
+                    if let Some(Attr::Deprecated(replacement)) = deprecated_attr(self.package, self.store, item_id) {
+                        self.check_deprecated_use(expr, replacement.as_deref());
+                    }
+                }
                 visit::walk_expr(self, expr);
             }
 
@@ -231,4 +269,23 @@ macro_rules! declare_hir_lints {
 
 pub(crate) use declare_hir_lints;
 
+/// Returns the `@Deprecated` attribute of the item, if it has one.
+/// Items without a package id are in `package`, and the rest are looked up in `store`.
+pub(crate) fn deprecated_attr<'a>(
+    package: &'a Package,
+    store: &'a PackageStore,
+    item_id: &ItemId,
+) -> Option<&'a Attr> {
+    let package = match item_id.package {
+        Some(id) => &store.get(id)?.package,
+        None => package,
+    };
+    package
+        .items
+        .get(item_id.item)?
+        .attrs
+        .iter()
+        .find(|attr| matches!(attr, Attr::Deprecated(_)))
+}
+
 use super::LintKind;
@@ -6,11 +6,16 @@ pub(super) mod hir;
 
 macro_rules! lint {
     ($lint:expr, $span:expr) => {
+        lint!($lint, $span, Vec::new())
+    };
+    ($lint:expr, $span:expr, $code_action_edits:expr) => {
         Lint {
+            kind: $lint.kind(),
             span: $span,
             level: $lint.level,
             message: $lint.message,
-            help: $lint.help,
+            help: $lint.help.into(),
+            code_action_edits: $code_action_edits,
         }
     };
 }
@@ -2,14 +2,20 @@
 // Licensed under the MIT License.
 
 use super::lint;
-use crate::linter::ast::declare_ast_lints;
-use qsc_ast::ast::{BinOp, ExprKind, Lit, StmtKind};
+use crate::{linter::ast::declare_ast_lints, LintConfig};
+use qsc_ast::ast::{BinOp, CallableDecl, ExprKind, Item, Lit, PatKind, StmtKind};
 use qsc_data_structures::span::Span;
+use qsc_frontend::typeck::Table;
+use qsc_hir::ty::{Prim, Ty};
 
 declare_ast_lints! {
     (DivisionByZero, LintLevel::Warn, "attempt to divide by zero", "division by zero is not allowed"),
+    (LongParameterList, LintLevel::Allow, "callable has too many parameters", "consider grouping related parameters into a tuple or a struct", max_params: usize = 4),
     (NeedlessParens, LintLevel::Allow, "unnecessary parentheses", "remove the extra parentheses for clarity"),
     (RedundantSemicolons, LintLevel::Warn, "redundant semicolons", "remove the redundant semicolons"),
+    (ResultBoolComparison, LintLevel::Allow, "comparison between `Result` and `Bool`", "compare measurement results against the `Result` constants `One` and `Zero` instead of `true` and `false`"),
+    (UnreachableBranch, LintLevel::Allow, "unreachable branch", "an earlier condition in this `if` chain is always `true`, so this branch never runs"),
+    (UnusedOpens, LintLevel::Warn, "unused open", "remove the open statement, since no names are resolved through it"),
 }
 
 impl AstLintPass for DivisionByZero {
@@ -24,6 +30,26 @@ impl AstLintPass for DivisionByZero {
     }
 }
 
+impl AstLintPass for LongParameterList {
+    fn configure(&mut self, config: &LintConfig) {
+        if let Some(max_params) = config.max_params {
+            self.max_params = max_params;
+        }
+    }
+
+    /// Checks the number of top-level parameters of a callable against
+    /// the configured maximum.
+    fn check_callable_decl(&self, decl: &CallableDecl, buffer: &mut Vec<Lint>) {
+        let arity = match &*decl.input.kind {
+            PatKind::Tuple(params) => params.len(),
+            _ => 1,
+        };
+        if arity > self.max_params {
+            buffer.push(lint!(self, decl.name.span));
+        }
+    }
+}
+
 impl NeedlessParens {
     /// The idea is that if we find a expr of the form:
     /// a + (expr)
@@ -38,7 +64,7 @@ impl NeedlessParens {
     ) {
         if let ExprKind::Paren(expr) = &*child.kind {
             if precedence(parent) < precedence(expr) {
-                buffer.push(lint!(self, child.span));
+                buffer.push(lint!(self, child.span, remove_parens(child.span)));
             }
         }
     }
@@ -62,7 +88,7 @@ impl AstLintPass for NeedlessParens {
     fn check_stmt(&self, stmt: &qsc_ast::ast::Stmt, buffer: &mut Vec<Lint>) {
         if let StmtKind::Local(_, _, right) = &*stmt.kind {
             if let ExprKind::Paren(_) = &*right.kind {
-                buffer.push(lint!(self, right.span));
+                buffer.push(lint!(self, right.span, remove_parens(right.span)));
             }
         }
     }
@@ -73,7 +99,7 @@ impl RedundantSemicolons {
     /// found two or more semicolons.
     fn maybe_push(&self, seq: &mut Option<Span>, buffer: &mut Vec<Lint>) {
         if let Some(span) = seq.take() {
-            buffer.push(lint!(self, span));
+            buffer.push(lint!(self, span, vec![(span, String::new())]));
         }
     }
 }
@@ -101,6 +127,97 @@ impl AstLintPass for RedundantSemicolons {
     }
 }
 
+impl ResultBoolComparison {
+    /// Returns true if `operand` has type `Result` and `literal` is a `Bool` literal,
+    /// or `operand` has type `Bool` and `literal` is a `Result` literal.
+    fn is_mismatch(
+        tys: &Table,
+        operand: &qsc_ast::ast::Expr,
+        literal: &qsc_ast::ast::Expr,
+    ) -> bool {
+        let Some(literal) = Self::literal(literal) else {
+            return false;
+        };
+        matches!(
+            (tys.terms.get(operand.id), literal),
+            (Some(Ty::Prim(Prim::Result)), Lit::Bool(_))
+                | (Some(Ty::Prim(Prim::Bool)), Lit::Result(_))
+        )
+    }
+
+    fn literal(expr: &qsc_ast::ast::Expr) -> Option<&Lit> {
+        match &*expr.kind {
+            ExprKind::Lit(lit) => Some(lit),
+            ExprKind::Paren(inner) => Self::literal(inner),
+            _ => None,
+        }
+    }
+}
+
+impl AstLintPass for ResultBoolComparison {
+    /// Checks comparisons such as `M(q) == true`. They fail type checking, so
+    /// they are checked against the types inferred for their operands, which
+    /// are recorded even though the comparison itself is an error.
+    fn check_expr_with_tys(&self, expr: &qsc_ast::ast::Expr, tys: &Table, buffer: &mut Vec<Lint>) {
+        if let ExprKind::BinOp(BinOp::Eq | BinOp::Neq, lhs, rhs) = &*expr.kind {
+            if Self::is_mismatch(tys, lhs, rhs) || Self::is_mismatch(tys, rhs, lhs) {
+                buffer.push(lint!(self, expr.span));
+            }
+        }
+    }
+}
+
+impl AstLintPass for UnreachableBranch {
+    /// Checks `if` expressions whose condition is the literal `true`. The `elif`
+    /// and `else` branches of such an expression are parsed as its else expression,
+    /// so the whole rest of the chain is reported as a single lint.
+    fn check_expr(&self, expr: &qsc_ast::ast::Expr, buffer: &mut Vec<Lint>) {
+        if let ExprKind::If(cond, _, Some(els)) = &*expr.kind {
+            if is_literal_true(cond) {
+                buffer.push(lint!(self, els.span));
+            }
+        }
+    }
+}
+
+impl AstLintPass for UnusedOpens {
+    /// Name resolution records which `open` statements resolved at least one name,
+    /// including names in nested callables and in types, so this lint only reports them.
+    fn check_open(&self, item: &Item, is_unused: bool, buffer: &mut Vec<Lint>) {
+        if is_unused {
+            buffer.push(lint!(self, item.span, vec![(item.span, String::new())]));
+        }
+    }
+}
+
+/// The edits that delete the outer parentheses of a parenthesized expression.
+fn remove_parens(span: Span) -> Vec<(Span, String)> {
+    vec![
+        (
+            Span {
+                lo: span.lo,
+                hi: span.lo + 1,
+            },
+            String::new(),
+        ),
+        (
+            Span {
+                lo: span.hi - 1,
+                hi: span.hi,
+            },
+            String::new(),
+        ),
+    ]
+}
+
+fn is_literal_true(expr: &qsc_ast::ast::Expr) -> bool {
+    match &*expr.kind {
+        ExprKind::Lit(lit) => matches!(**lit, Lit::Bool(true)),
+        ExprKind::Paren(inner) => is_literal_true(inner),
+        _ => false,
+    }
+}
+
 fn precedence(expr: &qsc_ast::ast::Expr) -> u8 {
     match &*expr.kind {
         ExprKind::Lit(_) => 15,
@@ -1,4 +1,9 @@
-use qsc_hir::hir::Lit;
+use qsc_data_structures::span::Span;
+use qsc_hir::{
+    hir::{CallableDecl, CallableKind, Expr, ExprKind, Lit, NodeId, Pat, PatKind, Res, SpecBody},
+    ty::{Prim, Ty},
+    visit::{self, Visitor},
+};
 
 use crate::linter::hir::declare_hir_lints;
 
@@ -6,6 +11,8 @@ use super::lint;
 
 declare_hir_lints! {
     (Placeholder, LintLevel::Allow, "this a placeholder", "remove after addding the first HIR lint"),
+    (UnusedQubitParameter, LintLevel::Allow, "qubit parameter is never used by an operation", "pass the qubits to an operation or remove the parameter"),
+    (DeprecatedCallable, LintLevel::Warn, "use of deprecated callable", "this callable is deprecated and may be removed in a future release"),
 }
 
 impl HirLintPass for Placeholder {
@@ -15,3 +22,124 @@ impl HirLintPass for Placeholder {
         }
     }
 }
+
+impl HirLintPass for UnusedQubitParameter {
+    fn check_callable_decl(&self, decl: &CallableDecl, buffer: &mut Vec<Lint>) {
+        if decl.kind != CallableKind::Operation {
+            return;
+        }
+
+        let mut params = Vec::new();
+        collect_qubit_params(&decl.input, &mut params);
+        if params.is_empty() {
+            return;
+        }
+
+        let mut finder = QubitUseFinder {
+            params: params.iter().map(|(id, _)| *id).collect(),
+            used: Vec::new(),
+            in_function_arg: false,
+        };
+        match &decl.body.body {
+            SpecBody::Impl(_, block) => finder.visit_block(block),
+            // An intrinsic body gives no evidence either way.
+            SpecBody::Gen(_) => return,
+        }
+        let specs = decl.adj.iter().chain(&decl.ctl).chain(&decl.ctl_adj);
+        for spec in specs {
+            // Generated specializations, such as `adjoint self;`, only derive from the body.
+            if let SpecBody::Impl(_, block) = &spec.body {
+                finder.visit_block(block);
+            }
+        }
+
+        for (id, span) in params {
+            if !finder.used.contains(&id) {
+                buffer.push(lint!(self, span));
+            }
+        }
+    }
+}
+
+impl HirLintPass for DeprecatedCallable {
+    /// Uses of deprecated items suggest the replacement named by the `@Deprecated` attribute.
+    fn check_deprecated_use(&self, expr: &Expr, replacement: Option<&str>, buffer: &mut Vec<Lint>) {
+        let mut lint = lint!(self, expr.span);
+        if let Some(replacement) = replacement {
+            lint.help = format!("use `{replacement}` instead").into();
+        }
+        buffer.push(lint);
+    }
+}
+
+/// Collects the bindings in `pat` whose type is `Qubit` or an array of qubits.
+fn collect_qubit_params(pat: &Pat, params: &mut Vec<(NodeId, Span)>) {
+    match &pat.kind {
+        PatKind::Bind(ident) if is_qubit_ty(&pat.ty) => params.push((ident.id, pat.span)),
+        PatKind::Tuple(pats) => {
+            for pat in pats {
+                collect_qubit_params(pat, params);
+            }
+        }
+        PatKind::Bind(_) | PatKind::Discard | PatKind::Err => {}
+    }
+}
+
+fn is_qubit_ty(ty: &Ty) -> bool {
+    match ty {
+        Ty::Prim(Prim::Qubit) => true,
+        Ty::Array(item) => is_qubit_ty(item),
+        _ => false,
+    }
+}
+
+/// Returns true if a value of type `ty` may hold qubits.
+fn may_hold_qubits(ty: &Ty) -> bool {
+    match ty {
+        Ty::Prim(Prim::Qubit) | Ty::Udt(..) | Ty::Param(..) | Ty::Infer(_) | Ty::Err => true,
+        Ty::Array(item) => may_hold_qubits(item),
+        Ty::Tuple(items) => items.iter().any(may_hold_qubits),
+        Ty::Prim(_) | Ty::Arrow(_) => false,
+    }
+}
+
+/// Finds which qubit parameters are used in a way that can act on them.
+/// Uses within the arguments of a function call that returns no qubits,
+/// such as `Length(qs)`, do not count, since functions cannot apply gates.
+/// Any other use does, so that qubits passed to sub-operations or aliased
+/// by a local are not flagged.
+struct QubitUseFinder {
+    params: Vec<NodeId>,
+    used: Vec<NodeId>,
+    in_function_arg: bool,
+}
+
+impl<'a> Visitor<'a> for QubitUseFinder {
+    fn visit_expr(&mut self, expr: &'a Expr) {
+        match &expr.kind {
+            ExprKind::Var(Res::Local(id), _)
+                if !self.in_function_arg && self.params.contains(id) =>
+            {
+                self.used.push(*id);
+            }
+            ExprKind::Closure(captures, _) if !self.in_function_arg => {
+                self.used
+                    .extend(captures.iter().filter(|id| self.params.contains(id)));
+            }
+            ExprKind::Call(callee, arg) => {
+                if let Ty::Arrow(arrow) = &callee.ty {
+                    if arrow.kind == CallableKind::Function && !may_hold_qubits(&arrow.output) {
+                        self.visit_expr(callee);
+                        let outer = self.in_function_arg;
+                        self.in_function_arg = true;
+                        self.visit_expr(arg);
+                        self.in_function_arg = outer;
+                        return;
+                    }
+                }
+            }
+            _ => {}
+        }
+        visit::walk_expr(self, expr);
+    }
+}
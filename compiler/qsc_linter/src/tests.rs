@@ -3,7 +3,8 @@
 
 use crate::{
     linter::{ast::run_ast_lints, hir::run_hir_lints},
-    Lint, LintConfig, LintLevel,
+    lints::{ast::AstLint, hir::HirLint},
+    Lint, LintConfig, LintKind, LintLevel,
 };
 use expect_test::{expect, Expect};
 use qsc_data_structures::{language_features::LanguageFeatures, target::TargetCapabilityFlags};
@@ -142,6 +143,383 @@ fn redundant_semicolons() {
     );
 }
 
+#[test]
+fn long_parameter_list() {
+    check_with_config(
+        "operation Apply(a : Int, b : Int, c : Int, d : Int, e : Int, f : Int) : Unit {}",
+        Some(&[LintConfig {
+            kind: LintKind::Ast(AstLint::LongParameterList),
+            level: LintLevel::Warn,
+            max_params: Some(4),
+        }]),
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "Apply",
+                    level: Warn,
+                    message: "callable has too many parameters",
+                    help: "consider grouping related parameters into a tuple or a struct",
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn long_parameter_list_within_limit() {
+    check_with_config(
+        "operation Apply(a : Int, b : Int, c : Int, d : Int) : Unit {}",
+        Some(&[LintConfig {
+            kind: LintKind::Ast(AstLint::LongParameterList),
+            level: LintLevel::Warn,
+            max_params: Some(4),
+        }]),
+        &expect![[r"
+            []
+        "]],
+    );
+}
+
+#[test]
+fn result_bool_comparison() {
+    check(
+        "use q = Qubit(); if M(q) == true {} if false != (MResetZ(q)) {} if One == false {} if M(q) == One {} if true == false {}",
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "M(q) == true",
+                    level: Allow,
+                    message: "comparison between `Result` and `Bool`",
+                    help: "compare measurement results against the `Result` constants `One` and `Zero` instead of `true` and `false`",
+                },
+                SrcLint {
+                    source: "false != (MResetZ(q))",
+                    level: Allow,
+                    message: "comparison between `Result` and `Bool`",
+                    help: "compare measurement results against the `Result` constants `One` and `Zero` instead of `true` and `false`",
+                },
+                SrcLint {
+                    source: "One == false",
+                    level: Allow,
+                    message: "comparison between `Result` and `Bool`",
+                    help: "compare measurement results against the `Result` constants `One` and `Zero` instead of `true` and `false`",
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn result_bool_comparison_is_reported_alongside_type_error() {
+    let source = wrap_in_namespace("use q = Qubit(); if M(q) == true {}");
+    let mut store = PackageStore::new(compile::core());
+    let std = store.insert(compile::std(&store, TargetCapabilityFlags::all()));
+    let (package, errors) = qsc::compile::compile(
+        &store,
+        &[std],
+        SourceMap::new([("source.qs".into(), source.as_str().into())], None),
+        PackageType::Exe,
+        TargetCapabilityFlags::all(),
+        LanguageFeatures::default(),
+    );
+    assert!(
+        !errors.is_empty(),
+        "comparing a `Result` with a `Bool` should fail type checking"
+    );
+
+    let lints: Vec<String> = run_ast_lints(
+        &package.ast.package,
+        &package.ast.locals,
+        &package.ast.tys,
+        None,
+    )
+    .iter()
+    .filter(|lint| lint.kind == LintKind::Ast(AstLint::ResultBoolComparison))
+    .map(|lint| source[lint.span].to_string())
+    .collect();
+    assert_eq!(lints, ["M(q) == true"]);
+}
+
+#[test]
+fn result_bool_comparison_on_local_variable() {
+    check(
+        "use q = Qubit(); let r = M(q); if r == true {} let b = r == One; if b != One {}",
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "r == true",
+                    level: Allow,
+                    message: "comparison between `Result` and `Bool`",
+                    help: "compare measurement results against the `Result` constants `One` and `Zero` instead of `true` and `false`",
+                },
+                SrcLint {
+                    source: "b != One",
+                    level: Allow,
+                    message: "comparison between `Result` and `Bool`",
+                    help: "compare measurement results against the `Result` constants `One` and `Zero` instead of `true` and `false`",
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn result_bool_comparison_ignores_user_callable_named_m() {
+    check(
+        "function M(flag : Bool) : Bool { flag } if M(true) == true {}",
+        &expect![[r"
+            []
+        "]],
+    );
+}
+
+#[test]
+fn unused_qubit_parameter() {
+    check(
+        "operation Ignore(q : Qubit, qs : Qubit[], n : Int) : Unit {
+            let count = Length(qs) + n;
+        }
+        operation Apply(q : Qubit, qs : Qubit[]) : Unit {
+            for i in 0..Length(qs) - 1 {
+                H(qs[i]);
+            }
+            Ignore(q, [], 0);
+        }
+        operation Alias(qs : Qubit[]) : Unit {
+            let first = Microsoft.Quantum.Arrays.Head(qs);
+            X(first);
+        }",
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "q : Qubit",
+                    level: Allow,
+                    message: "qubit parameter is never used by an operation",
+                    help: "pass the qubits to an operation or remove the parameter",
+                },
+                SrcLint {
+                    source: "qs : Qubit[]",
+                    level: Allow,
+                    message: "qubit parameter is never used by an operation",
+                    help: "pass the qubits to an operation or remove the parameter",
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn unused_qubit_parameter_with_generated_specializations() {
+    check(
+        "operation Noop(q : Qubit) : Unit is Adj {
+            body ... {}
+            adjoint self;
+        }
+        operation Flip(q : Qubit) : Unit is Adj + Ctl {
+            body ... { X(q); }
+            adjoint self;
+            controlled adjoint auto;
+        }
+        operation Native(q : Qubit) : Unit {
+            body intrinsic;
+        }",
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "q : Qubit",
+                    level: Allow,
+                    message: "qubit parameter is never used by an operation",
+                    help: "pass the qubits to an operation or remove the parameter",
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn unreachable_branch() {
+    check(
+        "let x = false; if true {} elif x {} else {} if x {} elif (true) {} else {}",
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "elif x {} else {}",
+                    level: Allow,
+                    message: "unreachable branch",
+                    help: "an earlier condition in this `if` chain is always `true`, so this branch never runs",
+                },
+                SrcLint {
+                    source: "else {}",
+                    level: Allow,
+                    message: "unreachable branch",
+                    help: "an earlier condition in this `if` chain is always `true`, so this branch never runs",
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn unreachable_branch_without_else() {
+    check(
+        "let x = false; if true {} if x {} elif false {} else {}",
+        &expect![[r"
+            []
+        "]],
+    );
+}
+
+#[test]
+fn unused_opens() {
+    check_namespace_source(
+        "namespace Foo {
+            open Microsoft.Quantum.Arrays;
+            open Microsoft.Quantum.Math;
+            operation RunProgram() : Unit {
+                function Nested() : Int[] {
+                    Reversed([1, 2])
+                }
+            }
+        }",
+        None,
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "open Microsoft.Quantum.Math;",
+                    level: Warn,
+                    message: "unused open",
+                    help: "remove the open statement, since no names are resolved through it",
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn opens_used_by_types_aliases_and_local_opens() {
+    check_namespace_source(
+        "namespace Foo {
+            open Microsoft.Quantum.Math;
+            open Microsoft.Quantum.Arrays as Arrays;
+            operation RunProgram(c : Complex) : Unit {
+                open Microsoft.Quantum.Convert;
+                let xs = Arrays.Reversed([IntAsDouble(1)]);
+                open Microsoft.Quantum.Diagnostics;
+            }
+        }",
+        None,
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "open Microsoft.Quantum.Diagnostics;",
+                    level: Warn,
+                    message: "unused open",
+                    help: "remove the open statement, since no names are resolved through it",
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn unused_opens_allowed_by_config() {
+    let config = [LintConfig {
+        kind: LintKind::Ast(AstLint::UnusedOpens),
+        level: LintLevel::Allow,
+        max_params: None,
+    }];
+    check_namespace_source(
+        "namespace Foo {
+            open Microsoft.Quantum.Math;
+            operation RunProgram() : Unit {}
+        }",
+        Some(&config),
+        &expect![[r"
+            []
+        "]],
+    );
+}
+
+#[test]
+fn deprecated_callable_from_dependency() {
+    let (store, package) = compile_with_dependency(
+        "namespace FakeStdLib {
+            @Deprecated(\"FakeStdLib.NewAdd\")
+            function OldAdd(a : Int, b : Int) : Int { a + b }
+            function NewAdd(a : Int, b : Int) : Int { a + b }
+        }",
+        "namespace Foo {
+            open FakeStdLib;
+            operation RunProgram() : Unit {
+                let x = OldAdd(1, 2);
+                let y = NewAdd(1, 2);
+            }
+        }",
+    );
+    check_lints(
+        &store,
+        &package,
+        None,
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "OldAdd",
+                    level: Warn,
+                    message: "use of deprecated callable",
+                    help: "use `FakeStdLib.NewAdd` instead",
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn deprecated_callable_without_replacement_in_same_package() {
+    check_namespace_source(
+        "namespace Foo {
+            @Deprecated()
+            function Old() : Unit {}
+            operation RunProgram() : Unit {
+                Old();
+            }
+        }",
+        None,
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "Old",
+                    level: Warn,
+                    message: "use of deprecated callable",
+                    help: "this callable is deprecated and may be removed in a future release",
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn deprecated_callable_allowed_by_config() {
+    let config = [LintConfig {
+        kind: LintKind::Hir(HirLint::DeprecatedCallable),
+        level: LintLevel::Allow,
+        max_params: None,
+    }];
+    check_namespace_source(
+        "namespace Foo {
+            @Deprecated(\"Foo.New\")
+            function Old() : Unit {}
+            function New() : Unit {}
+            operation RunProgram() : Unit {
+                Old();
+            }
+        }",
+        Some(&config),
+        &expect![[r"
+            []
+        "]],
+    );
+}
+
 #[test]
 fn hir_placeholder() {
     check(
@@ -159,28 +537,201 @@ fn hir_placeholder() {
     );
 }
 
+#[test]
+fn lints_in_multiple_sources_are_in_source_order() {
+    let config = [LintConfig {
+        kind: LintKind::Ast(AstLint::ResultBoolComparison),
+        level: LintLevel::Warn,
+        max_params: None,
+    }];
+    let sources = (0..40)
+        .map(|i| {
+            (
+                format!("source{i}.qs"),
+                format!(
+                    "namespace Foo{i} {{
+                        operation RunProgram() : Unit {{
+                            use q = Qubit(); if M(q) == true {{}} let x = 1 / 0;;;
+                        }}
+                    }}"
+                ),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let (store, multi_file) = compile_sources(&sources);
+    let actual = crate::run_lints(&store, &multi_file, Some(&config))
+        .into_iter()
+        .map(|mut lint| {
+            let source = multi_file
+                .sources
+                .find_by_offset(lint.span.lo)
+                .expect("lint should be within a source");
+            lint.span = lint.span - source.offset;
+            format!(
+                "{}: {}",
+                source.name,
+                SrcLint::from(&lint, &source.contents)
+            )
+        })
+        .collect::<Vec<_>>();
+
+    // Linting each source on its own yields the same lints in the same order.
+    let expected = sources
+        .iter()
+        .flat_map(|(name, contents)| {
+            let (store, single_file) = compile_sources(&[(name.clone(), contents.clone())]);
+            crate::run_lints(&store, &single_file, Some(&config))
+                .iter()
+                .map(|lint| format!("{name}: {}", SrcLint::from(lint, contents)))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(actual.len(), 3 * sources.len());
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn parallel_lints_match_sequential_lints() {
+    let config = [
+        LintConfig {
+            kind: LintKind::Ast(AstLint::NeedlessParens),
+            level: LintLevel::Warn,
+            max_params: None,
+        },
+        LintConfig {
+            kind: LintKind::Hir(HirLint::UnusedQubitParameter),
+            level: LintLevel::Warn,
+            max_params: None,
+        },
+        LintConfig {
+            kind: LintKind::Hir(HirLint::Placeholder),
+            level: LintLevel::Warn,
+            max_params: None,
+        },
+    ];
+    let sources = (0..40)
+        .map(|i| {
+            (
+                format!("source{i}.qs"),
+                format!(
+                    "namespace Foo{i} {{
+                        operation Unused{i}(q : Qubit) : Unit {{
+                            let x = ((1 + 2)) * 42 / 0;;
+                        }}
+                    }}"
+                ),
+            )
+        })
+        .collect::<Vec<_>>();
+    let (store, unit) = compile_sources(&sources);
+
+    let parallel = crate::run_lints(&store, &unit, Some(&config));
+    let (ast_lints, hir_lints) =
+        crate::linter::run_passes_sequentially(&store, &unit, Some(&config));
+    let sequential = crate::linter::merge_lints(ast_lints, hir_lints);
+
+    assert_eq!(parallel.len(), 5 * sources.len());
+    assert_eq!(format!("{parallel:?}"), format!("{sequential:?}"));
+}
+
 fn check(source: &str, expected: &Expect) {
+    check_with_config(source, None, expected);
+}
+
+fn check_with_config(source: &str, config: Option<&[LintConfig]>, expected: &Expect) {
     let source = wrap_in_namespace(source);
+    let (store, package) = compile_sources(&[("source.qs".into(), source.clone())]);
+
+    let actual: Vec<SrcLint> = run_lints(&store, &package, config)
+        .into_iter()
+        .map(|lint| SrcLint::from(&lint, &source))
+        .collect();
+
+    expected.assert_debug_eq(&actual);
+}
+
+/// Like [`check_with_config`], but for a source that declares its own namespace.
+/// Only lints that are not allowed are reported.
+fn check_namespace_source(source: &str, config: Option<&[LintConfig]>, expected: &Expect) {
+    let (store, package) = compile_sources(&[("source.qs".into(), source.into())]);
+    assert!(package.errors.is_empty(), "{:?}", package.errors);
+
+    let actual: Vec<SrcLint> = crate::run_lints(&store, &package, config)
+        .into_iter()
+        .map(|lint| SrcLint::from(&lint, source))
+        .collect();
+
+    expected.assert_debug_eq(&actual);
+}
+
+/// Compiles `source` against the standard library and a package compiled from `dependency`.
+fn compile_with_dependency(dependency: &str, source: &str) -> (PackageStore, CompileUnit) {
     let mut store = PackageStore::new(compile::core());
     let std = store.insert(compile::std(&store, TargetCapabilityFlags::all()));
-    let sources = SourceMap::new([("source.qs".into(), source.clone().into())], None);
-    let (package, _) = qsc::compile::compile(
+    let (dependency, errors) = qsc::compile::compile(
         &store,
         &[std],
-        sources,
-        PackageType::Exe,
+        SourceMap::new([("dependency.qs".into(), dependency.into())], None),
+        PackageType::Lib,
         TargetCapabilityFlags::all(),
         LanguageFeatures::default(),
     );
+    assert!(errors.is_empty(), "{errors:?}");
+    let dependency = store.insert(dependency);
+    let (package, errors) = qsc::compile::compile(
+        &store,
+        &[std, dependency],
+        SourceMap::new([("source.qs".into(), source.into())], None),
+        PackageType::Lib,
+        TargetCapabilityFlags::all(),
+        LanguageFeatures::default(),
+    );
+    assert!(errors.is_empty(), "{errors:?}");
+    (store, package)
+}
 
-    let actual: Vec<SrcLint> = run_lints(&package, None)
+/// Checks the lints that are not allowed in a package compiled with a single source.
+fn check_lints(
+    store: &PackageStore,
+    package: &CompileUnit,
+    config: Option<&[LintConfig]>,
+    expected: &Expect,
+) {
+    let source = &package
+        .sources
+        .find_by_offset(0)
+        .expect("package should have a source")
+        .contents;
+    let actual: Vec<SrcLint> = crate::run_lints(store, package, config)
         .into_iter()
-        .map(|lint| SrcLint::from(&lint, &source))
+        .map(|lint| SrcLint::from(&lint, source))
         .collect();
 
     expected.assert_debug_eq(&actual);
 }
 
+fn compile_sources(sources: &[(String, String)]) -> (PackageStore, CompileUnit) {
+    let mut store = PackageStore::new(compile::core());
+    let std = store.insert(compile::std(&store, TargetCapabilityFlags::all()));
+    let sources = SourceMap::new(
+        sources
+            .iter()
+            .map(|(name, contents)| (name.as_str().into(), contents.as_str().into())),
+        None,
+    );
+    let (package, _) = qsc::compile::compile(
+        &store,
+        &[std],
+        sources,
+        PackageType::Exe,
+        TargetCapabilityFlags::all(),
+        LanguageFeatures::default(),
+    );
+    (store, package)
+}
+
 /// Wraps some source code into a namespace, to make testing easier.
 fn wrap_in_namespace(source: &str) -> String {
     format!(
@@ -199,7 +750,7 @@ struct SrcLint {
     source: String,
     level: LintLevel,
     message: &'static str,
-    help: &'static str,
+    help: String,
 }
 
 impl SrcLint {
@@ -208,7 +759,7 @@ impl SrcLint {
             source: source[lint.span].into(),
             level: lint.level,
             message: lint.message,
-            help: lint.help,
+            help: lint.help.to_string(),
         }
     }
 }
@@ -228,9 +779,18 @@ impl std::fmt::Display for SrcLint {
     }
 }
 
-fn run_lints(compile_unit: &CompileUnit, config: Option<&[LintConfig]>) -> Vec<Lint> {
-    let mut ast_lints = run_ast_lints(&compile_unit.ast.package, config);
-    let mut hir_lints = run_hir_lints(&compile_unit.package, config);
+fn run_lints(
+    store: &PackageStore,
+    compile_unit: &CompileUnit,
+    config: Option<&[LintConfig]>,
+) -> Vec<Lint> {
+    let mut ast_lints = run_ast_lints(
+        &compile_unit.ast.package,
+        &compile_unit.ast.locals,
+        &compile_unit.ast.tys,
+        config,
+    );
+    let mut hir_lints = run_hir_lints(&compile_unit.package, store, config);
     let mut lints = Vec::new();
     lints.append(&mut ast_lints);
     lints.append(&mut hir_lints);
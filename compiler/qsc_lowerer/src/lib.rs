@@ -10,7 +10,7 @@ use qsc_fir::{
 };
 use qsc_hir::hir::{self, SpecBody, SpecGen};
 use std::iter::once;
-use std::{clone::Clone, rc::Rc};
+use std::{clone::Clone, sync::Arc};
 
 #[must_use]
 pub fn map_hir_package_to_fir(package: hir::PackageId) -> fir::PackageId {
@@ -177,7 +177,7 @@ impl Lowerer {
             id: lower_local_item_id(item.id),
             span: item.span,
             parent: item.parent.map(lower_local_item_id),
-            doc: Rc::clone(&item.doc),
+            doc: Arc::clone(&item.doc),
             attrs,
             visibility: lower_visibility(item.visibility),
             kind,
@@ -636,7 +636,7 @@ impl Lowerer {
                 self.exec_graph.push(ExecGraphNode::Store);
                 fir::StringComponent::Expr(expr)
             }
-            hir::StringComponent::Lit(str) => fir::StringComponent::Lit(Rc::clone(str)),
+            hir::StringComponent::Lit(str) => fir::StringComponent::Lit(Arc::clone(str)),
         }
     }
 
@@ -774,7 +774,16 @@ fn lower_generics(generics: &[qsc_hir::ty::GenericParam]) -> Vec<qsc_fir::ty::Ge
 }
 
 fn lower_attrs(attrs: &[hir::Attr]) -> Vec<fir::Attr> {
-    attrs.iter().map(|_| fir::Attr::EntryPoint).collect()
+    attrs
+        .iter()
+        .filter_map(|attr| match attr {
+            hir::Attr::EntryPoint => Some(fir::Attr::EntryPoint),
+            hir::Attr::Config
+            | hir::Attr::Unimplemented
+            | hir::Attr::Test
+            | hir::Attr::Deprecated(_) => None,
+        })
+        .collect()
 }
 
 fn lower_functors(functors: qsc_hir::ty::FunctorSetValue) -> qsc_fir::ty::FunctorSetValue {
@@ -34,11 +34,11 @@ use qsc_rir::{
     },
 };
 use rustc_hash::FxHashMap;
-use std::{collections::hash_map::Entry, rc::Rc, result::Result};
+use std::{collections::hash_map::Entry, rc::Rc, result::Result, sync::Arc};
 use thiserror::Error;
 
 pub struct ProgramEntry {
-    pub exec_graph: Rc<[ExecGraphNode]>,
+    pub exec_graph: Arc<[ExecGraphNode]>,
     pub expr: fir::StoreExprId,
 }
 
@@ -881,7 +881,7 @@ impl<'a> PartialEvaluator<'a> {
         self.eval_context.get_current_scope().package_id
     }
 
-    fn get_current_scope_exec_graph(&self) -> &Rc<[ExecGraphNode]> {
+    fn get_current_scope_exec_graph(&self) -> &Arc<[ExecGraphNode]> {
         if let Some(spec_decl) = self.get_current_scope_spec_decl() {
             &spec_decl.exec_graph
         } else {
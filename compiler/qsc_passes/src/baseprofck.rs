@@ -5,7 +5,7 @@
 mod tests;
 
 use miette::Diagnostic;
-use qsc_data_structures::span::Span;
+use qsc_data_structures::{span::Span, target::TargetCapabilityFlags};
 use qsc_hir::{
     hir::{BinOp, CallableKind, Expr, ExprKind, Item, ItemKind, Lit, Package, SpecBody, SpecGen},
     ty::{Prim, Ty},
@@ -44,6 +44,21 @@ pub enum Error {
     UnsupportedIntrinsic(#[label] Span),
 }
 
+impl Error {
+    /// Returns the target capabilities needed to support the construct this error reports.
+    /// Every construct rejected here needs adaptive execution, though the capabilities
+    /// check may report further requirements once the base profile is no longer used.
+    #[must_use]
+    pub fn required_capabilities(&self) -> TargetCapabilityFlags {
+        match self {
+            Self::ResultComparison(_)
+            | Self::ResultLiteral(_)
+            | Self::ReturnNonResult(_)
+            | Self::UnsupportedIntrinsic(_) => TargetCapabilityFlags::Adaptive,
+        }
+    }
+}
+
 #[must_use]
 pub fn check_base_profile_compliance(package: &Package) -> Vec<Error> {
     let mut checker = Checker { errors: Vec::new() };
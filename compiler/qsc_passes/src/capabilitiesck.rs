@@ -13,6 +13,9 @@ mod tests_adaptive_plus_integers;
 #[cfg(test)]
 pub mod tests_common;
 
+#[cfg(test)]
+mod tests_timeout;
+
 use miette::Diagnostic;
 use qsc_data_structures::{span::Span, target::TargetCapabilityFlags};
 
@@ -32,6 +35,7 @@ use qsc_rca::{
     PackageStoreComputeProperties, RuntimeFeatureFlags,
 };
 use rustc_hash::FxHashMap;
+use std::time::Instant;
 use thiserror::Error;
 
 #[derive(Clone, Debug, Diagnostic, Error)]
@@ -173,6 +177,45 @@ pub enum Error {
     UseOfClosure(#[label] Span),
 }
 
+impl Error {
+    /// Returns the target capabilities needed to support the construct this error reports.
+    #[must_use]
+    pub fn required_capabilities(&self) -> TargetCapabilityFlags {
+        self.runtime_feature().target_capabilities()
+    }
+
+    fn runtime_feature(&self) -> RuntimeFeatureFlags {
+        match self {
+            Self::UseOfDynamicBool(_) => RuntimeFeatureFlags::UseOfDynamicBool,
+            Self::UseOfDynamicInt(_) => RuntimeFeatureFlags::UseOfDynamicInt,
+            Self::UseOfDynamicPauli(_) => RuntimeFeatureFlags::UseOfDynamicPauli,
+            Self::UseOfDynamicRange(_) => RuntimeFeatureFlags::UseOfDynamicRange,
+            Self::UseOfDynamicDouble(_) => RuntimeFeatureFlags::UseOfDynamicDouble,
+            Self::UseOfDynamicQubit(_) => RuntimeFeatureFlags::UseOfDynamicQubit,
+            Self::UseOfDynamicBigInt(_) => RuntimeFeatureFlags::UseOfDynamicBigInt,
+            Self::UseOfDynamicString(_) => RuntimeFeatureFlags::UseOfDynamicString,
+            Self::UseOfDynamicallySizedArray(_) => RuntimeFeatureFlags::UseOfDynamicallySizedArray,
+            Self::UseOfDynamicUdt(_) => RuntimeFeatureFlags::UseOfDynamicUdt,
+            Self::UseOfDynamicArrowFunction(_) => RuntimeFeatureFlags::UseOfDynamicArrowFunction,
+            Self::UseOfDynamicArrowOperation(_) => RuntimeFeatureFlags::UseOfDynamicArrowOperation,
+            Self::CallToCyclicFunctionWithDynamicArg(_) => {
+                RuntimeFeatureFlags::CallToCyclicFunctionWithDynamicArg
+            }
+            Self::CyclicOperationSpec(_) => RuntimeFeatureFlags::CyclicOperationSpec,
+            Self::CallToCyclicOperation(_) => RuntimeFeatureFlags::CallToCyclicOperation,
+            Self::CallToDynamicCallee(_) => RuntimeFeatureFlags::CallToDynamicCallee,
+            Self::CallToUnresolvedCallee(_) => RuntimeFeatureFlags::CallToUnresolvedCallee,
+            Self::MeasurementWithinDynamicScope(_) => {
+                RuntimeFeatureFlags::MeasurementWithinDynamicScope
+            }
+            Self::UseOfDynamicIndex(_) => RuntimeFeatureFlags::UseOfDynamicIndex,
+            Self::ReturnWithinDynamicScope(_) => RuntimeFeatureFlags::ReturnWithinDynamicScope,
+            Self::LoopWithDynamicCondition(_) => RuntimeFeatureFlags::LoopWithDynamicCondition,
+            Self::UseOfClosure(_) => RuntimeFeatureFlags::UseOfClosure,
+        }
+    }
+}
+
 /// Lower a package store from `qsc_frontend` HIR store to a `qsc_fir` FIR store.
 pub fn lower_store(
     package_store: &qsc_frontend::compile::PackageStore,
@@ -192,6 +235,32 @@ pub fn run_rca_pass(
 ) -> Result<PackageStoreComputeProperties, Vec<crate::Error>> {
     let analyzer = Analyzer::init(fir_store);
     let compute_properties = analyzer.analyze_all();
+    check_store_capabilities(fir_store, package_id, capabilities, compute_properties)
+}
+
+/// Like [`run_rca_pass`], but returns `None` if the analysis is still running once `deadline` has passed.
+pub fn run_rca_pass_before(
+    fir_store: &qsc_fir::fir::PackageStore,
+    package_id: qsc_fir::fir::PackageId,
+    capabilities: TargetCapabilityFlags,
+    deadline: Instant,
+) -> Option<Result<PackageStoreComputeProperties, Vec<crate::Error>>> {
+    let analyzer = Analyzer::init(fir_store);
+    let compute_properties = analyzer.analyze_all_before(deadline)?;
+    Some(check_store_capabilities(
+        fir_store,
+        package_id,
+        capabilities,
+        compute_properties,
+    ))
+}
+
+fn check_store_capabilities(
+    fir_store: &qsc_fir::fir::PackageStore,
+    package_id: qsc_fir::fir::PackageId,
+    capabilities: TargetCapabilityFlags,
+    compute_properties: PackageStoreComputeProperties,
+) -> Result<PackageStoreComputeProperties, Vec<crate::Error>> {
     let fir_package = fir_store.get(package_id);
 
     let package_compute_properties = compute_properties.get(package_id);
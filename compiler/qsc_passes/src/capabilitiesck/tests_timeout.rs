@@ -0,0 +1,62 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use super::tests_common::USE_DYNAMIC_BOOLEAN;
+use crate::{lower_hir_to_fir, Error, PassContext};
+use qsc::{incremental::Compiler, PackageType};
+use qsc_data_structures::{language_features::LanguageFeatures, target::TargetCapabilityFlags};
+use qsc_frontend::compile::SourceMap;
+use std::time::Duration;
+
+#[test]
+fn timed_out_passes_leave_store_usable() {
+    let mut compiler = Compiler::new(
+        true,
+        SourceMap::default(),
+        PackageType::Lib,
+        TargetCapabilityFlags::all(),
+        LanguageFeatures::default(),
+    )
+    .expect("should be able to create a new compiler");
+    let increment = compiler
+        .compile_fragments_fail_fast("test", USE_DYNAMIC_BOOLEAN)
+        .expect("code should compile");
+    compiler.update(increment);
+    let (fir_store, package_id) = lower_hir_to_fir(compiler.package_store(), compiler.package_id());
+
+    // A timeout of zero has already elapsed when the passes first check it.
+    let errors = PassContext::run_fir_passes_with_timeout(
+        &fir_store,
+        package_id,
+        TargetCapabilityFlags::empty(),
+        Duration::ZERO,
+    )
+    .expect_err("passes should time out");
+    assert!(
+        matches!(errors.as_slice(), [Error::TimedOut(_)]),
+        "{errors:?}"
+    );
+
+    // The passes only read the store, so running them again reports the same
+    // errors as running them on a store that never timed out.
+    let errors = PassContext::run_fir_passes_with_timeout(
+        &fir_store,
+        package_id,
+        TargetCapabilityFlags::empty(),
+        Duration::from_secs(30),
+    )
+    .expect_err("dynamic bool should not be supported by the base profile");
+    assert!(
+        matches!(errors.as_slice(), [Error::CapabilitiesCk(_)]),
+        "{errors:?}"
+    );
+    let (fresh_store, fresh_package_id) =
+        lower_hir_to_fir(compiler.package_store(), compiler.package_id());
+    let expected = PassContext::run_fir_passes_on_fir(
+        &fresh_store,
+        fresh_package_id,
+        TargetCapabilityFlags::empty(),
+    )
+    .expect_err("dynamic bool should not be supported by the base profile");
+    assert_eq!(format!("{errors:?}"), format!("{expected:?}"));
+}
@@ -16,7 +16,9 @@ mod replace_qubit_allocation;
 mod spec_gen;
 
 use callable_limits::CallableLimits;
-use capabilitiesck::{check_supported_capabilities, lower_store, run_rca_pass};
+use capabilitiesck::{
+    check_supported_capabilities, lower_store, run_rca_pass, run_rca_pass_before,
+};
 use entry_point::generate_entry_expr;
 use loop_unification::LoopUni;
 use miette::Diagnostic;
@@ -34,6 +36,7 @@ use qsc_hir::{
 use qsc_lowerer::map_hir_package_to_fir;
 use qsc_rca::{PackageComputeProperties, PackageStoreComputeProperties};
 use replace_qubit_allocation::ReplaceQubitAllocation;
+use std::time::{Duration, Instant};
 use thiserror::Error;
 
 #[derive(Clone, Debug, Diagnostic, Error)]
@@ -47,6 +50,32 @@ pub enum Error {
     ConjInvert(conjugate_invert::Error),
     EntryPoint(entry_point::Error),
     SpecGen(spec_gen::Error),
+    TimedOut(TimedOut),
+}
+
+/// The FIR passes did not finish within the time they were given.
+#[derive(Clone, Debug, Diagnostic, Error)]
+#[error("the passes did not finish within {0:?}")]
+#[diagnostic(code("Qsc.Passes.TimedOut"))]
+#[diagnostic(help("the program is too expensive to analyze within the time allowed"))]
+pub struct TimedOut(pub Duration);
+
+impl Error {
+    /// Returns the target capabilities needed to support the construct this error reports,
+    /// or `None` if the error is not about a construct the target does not support.
+    #[must_use]
+    pub fn required_capabilities(&self) -> Option<TargetCapabilityFlags> {
+        match self {
+            Self::BaseProfCk(error) => Some(error.required_capabilities()),
+            Self::CapabilitiesCk(error) => Some(error.required_capabilities()),
+            Self::BorrowCk(_)
+            | Self::CallableLimits(_)
+            | Self::ConjInvert(_)
+            | Self::EntryPoint(_)
+            | Self::SpecGen(_)
+            | Self::TimedOut(_) => None,
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -138,6 +167,25 @@ impl PassContext {
     ) -> Result<PackageStoreComputeProperties, Vec<Error>> {
         run_rca_pass(fir_store, package_id, capabilities)
     }
+
+    /// Like [`PassContext::run_fir_passes_on_fir`], but returns [`Error::TimedOut`] if the passes
+    /// are still running once `timeout` has elapsed. The passes only read `fir_store`, so it can
+    /// still be used after they time out.
+    /// This relies on `std::time::Instant`, which is unavailable on `wasm32-unknown-unknown`.
+    pub fn run_fir_passes_with_timeout(
+        fir_store: &qsc_fir::fir::PackageStore,
+        package_id: qsc_fir::fir::PackageId,
+        capabilities: TargetCapabilityFlags,
+        timeout: Duration,
+    ) -> Result<PackageStoreComputeProperties, Vec<Error>> {
+        run_rca_pass_before(
+            fir_store,
+            package_id,
+            capabilities,
+            Instant::now() + timeout,
+        )
+        .unwrap_or_else(|| Err(vec![Error::TimedOut(TimedOut(timeout))]))
+    }
 }
 
 /// Run the default set of passes required for evaluation.
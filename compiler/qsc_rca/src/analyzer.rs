@@ -6,6 +6,7 @@ use crate::{
     scaffolding::InternalPackageStoreComputeProperties, PackageStoreComputeProperties,
 };
 use qsc_fir::fir::{PackageId, PackageStore};
+use std::time::Instant;
 
 /// A runtime capabilities analyzer.
 pub struct Analyzer<'a> {
@@ -35,6 +36,21 @@ impl<'a> Analyzer<'a> {
 
     #[must_use]
     pub fn analyze_all(self) -> PackageStoreComputeProperties {
+        self.analyze_all_with_deadline(None)
+            .expect("analysis without a deadline should finish")
+    }
+
+    /// Like [`Analyzer::analyze_all`], but gives up and returns `None` once `deadline` has passed.
+    /// The deadline is checked before analyzing each package and each of its top-level items.
+    #[must_use]
+    pub fn analyze_all_before(self, deadline: Instant) -> Option<PackageStoreComputeProperties> {
+        self.analyze_all_with_deadline(Some(deadline))
+    }
+
+    fn analyze_all_with_deadline(
+        self,
+        deadline: Option<Instant>,
+    ) -> Option<PackageStoreComputeProperties> {
         // First, we populate the elements for which we override its compute properties.
         let overrider = Overrider::new(self.package_store, self.scaffolding);
         let scaffolding = overrider.populate_overrides();
@@ -43,11 +59,11 @@ impl<'a> Analyzer<'a> {
         // rest of the items without causing an infinite analysis loop.
         let cyclic_callables_analyzer =
             cyclic_callables::Analyzer::new(self.package_store, scaffolding);
-        let scaffolding = cyclic_callables_analyzer.analyze_all();
+        let scaffolding = cyclic_callables_analyzer.analyze_all(deadline)?;
 
         // Now we can safely analyze the rest of the items.
         let core_analyzer = core::Analyzer::new(self.package_store, scaffolding);
-        core_analyzer.analyze_all().into()
+        core_analyzer.analyze_all(deadline).map(Into::into)
     }
 
     #[must_use]
@@ -12,7 +12,10 @@ use qsc_fir::{
     ty::{FunctorSetValue, Ty},
 };
 use rustc_hash::FxHashMap;
-use std::fmt::{Debug, Formatter};
+use std::{
+    fmt::{Debug, Formatter},
+    time::Instant,
+};
 
 /// A represenation of a local symbol.
 #[derive(Clone, Debug)]
@@ -230,6 +233,11 @@ fn try_resolve_un_op_callee(
     })
 }
 
+/// Returns true if there is a deadline and it has passed.
+pub fn deadline_passed(deadline: Option<Instant>) -> bool {
+    deadline.is_some_and(|deadline| Instant::now() >= deadline)
+}
+
 pub fn set_indentation<'a, 'b>(
     indent: Indented<'a, Formatter<'b>>,
     level: usize,
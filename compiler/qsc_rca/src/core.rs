@@ -3,7 +3,10 @@
 
 use crate::{
     applications::{ApplicationInstance, GeneratorSetsBuilder, LocalComputeKind},
-    common::{try_resolve_callee, Callee, FunctorAppExt, GlobalSpecId, Local, LocalKind, TyExt},
+    common::{
+        deadline_passed, try_resolve_callee, Callee, FunctorAppExt, GlobalSpecId, Local, LocalKind,
+        TyExt,
+    },
     scaffolding::{InternalItemComputeProperties, InternalPackageStoreComputeProperties},
     ApplicationGeneratorSet, ArrayParamApplication, ComputeKind, ComputePropertiesLookup,
     ParamApplication, QuantumProperties, RuntimeFeatureFlags, RuntimeKind, ValueKind,
@@ -20,6 +23,7 @@ use qsc_fir::{
     ty::{Arrow, FunctorSetValue, Prim, Ty},
     visit::Visitor,
 };
+use std::time::Instant;
 
 pub struct Analyzer<'a> {
     package_store: &'a PackageStore,
@@ -39,11 +43,15 @@ impl<'a> Analyzer<'a> {
         }
     }
 
-    pub fn analyze_all(mut self) -> InternalPackageStoreComputeProperties {
+    /// Analyzes all the packages in the store, or returns `None` if `deadline` passes first.
+    pub fn analyze_all(
+        mut self,
+        deadline: Option<Instant>,
+    ) -> Option<InternalPackageStoreComputeProperties> {
         for (package_id, package) in self.package_store {
-            self.analyze_package_internal(package_id, package);
+            self.analyze_package_internal(package_id, package, deadline)?;
         }
-        self.package_store_compute_properties
+        Some(self.package_store_compute_properties)
     }
 
     pub fn analyze_package(
@@ -51,7 +59,8 @@ impl<'a> Analyzer<'a> {
         package_id: PackageId,
     ) -> InternalPackageStoreComputeProperties {
         let package = self.package_store.get(package_id);
-        self.analyze_package_internal(package_id, package);
+        self.analyze_package_internal(package_id, package, None)
+            .expect("analysis without a deadline should finish");
         self.package_store_compute_properties
     }
 
@@ -984,9 +993,17 @@ impl<'a> Analyzer<'a> {
         assert!(popped_item_id == item_id);
     }
 
-    fn analyze_package_internal(&mut self, package_id: PackageId, package: &'a Package) {
+    fn analyze_package_internal(
+        &mut self,
+        package_id: PackageId,
+        package: &'a Package,
+        deadline: Option<Instant>,
+    ) -> Option<()> {
         // Analyze all top level items.
         for (local_item_id, item) in &package.items {
+            if deadline_passed(deadline) {
+                return None;
+            }
             self.analyze_item((package_id, local_item_id).into(), item);
         }
 
@@ -1010,6 +1027,7 @@ impl<'a> Analyzer<'a> {
         top_level_context
             .builder
             .save_to_package_compute_properties(package_compute_properties, None);
+        Some(())
     }
 
     fn analyze_spec(&mut self, id: GlobalSpecId, callable_decl: &'a CallableDecl) {
@@ -2,9 +2,11 @@
 // Licensed under the MIT License.
 
 use crate::{
-    common::LocalSpecId, cycle_detection::CycleDetector,
-    scaffolding::InternalPackageStoreComputeProperties, ApplicationGeneratorSet,
-    ArrayParamApplication, ComputeKind, ParamApplication, RuntimeFeatureFlags, ValueKind,
+    common::{deadline_passed, LocalSpecId},
+    cycle_detection::CycleDetector,
+    scaffolding::InternalPackageStoreComputeProperties,
+    ApplicationGeneratorSet, ArrayParamApplication, ComputeKind, ParamApplication,
+    RuntimeFeatureFlags, ValueKind,
 };
 use qsc_fir::{
     extensions::InputParam,
@@ -15,6 +17,7 @@ use qsc_fir::{
     ty::{FunctorSetValue, Ty},
     visit::{self, Visitor},
 };
+use std::time::Instant;
 
 pub struct Analyzer<'a> {
     package_store: &'a PackageStore,
@@ -36,11 +39,18 @@ impl<'a> Analyzer<'a> {
         }
     }
 
-    pub fn analyze_all(mut self) -> InternalPackageStoreComputeProperties {
+    /// Analyzes all the packages in the store, or returns `None` if `deadline` passes first.
+    pub fn analyze_all(
+        mut self,
+        deadline: Option<Instant>,
+    ) -> Option<InternalPackageStoreComputeProperties> {
         for (package_id, package) in self.package_store {
+            if deadline_passed(deadline) {
+                return None;
+            }
             self.analyze_package_internal(package_id, package);
         }
-        self.package_store_compute_properties
+        Some(self.package_store_compute_properties)
     }
 
     pub fn analyze_package(
@@ -1,21 +1,35 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
+// The analyses are for tooling built on the language service and have no
+// callers among the protocol handlers.
+#[allow(dead_code)]
+mod analyses;
+mod lint_cache;
+#[cfg(test)]
+mod tests;
+
+#[allow(unused_imports)]
+pub(crate) use analyses::{
+    CompilationSummary, RenameError, ResolvedDiagnostic, SourceRole, StaticGateCounts,
+};
+pub(crate) use lint_cache::LintCache;
 use log::trace;
 use qsc::{
     ast,
     compile::{self, Error},
     display::Lookup,
     error::WithSource,
+    fir,
     hir::{self, PackageId},
-    incremental::Compiler,
+    incremental::{self, Compiler},
     line_column::{Encoding, Position},
     resolve,
     target::Profile,
     CompileUnit, LanguageFeatures, PackageStore, PackageType, PassContext, SourceMap, Span,
 };
 use qsc_linter::LintConfig;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 
 /// Represents an immutable compilation state that can be used
 /// to implement language service features.
@@ -28,6 +42,28 @@ pub(crate) struct Compilation {
     pub user_package_id: PackageId,
     pub errors: Vec<Error>,
     pub kind: CompilationKind,
+    /// The target profile the user package was compiled against.
+    pub target_profile: Profile,
+    /// The language features the user package was compiled with.
+    pub language_features: LanguageFeatures,
+    /// The package type the user package was compiled as.
+    pub package_type: PackageType,
+    /// The lint configuration used for the user package.
+    pub lints_config: Vec<LintConfig>,
+    /// The package store lowered to FIR, and the FIR id of the user package.
+    /// Filled in on first use, and shared by everything that consumes FIR so
+    /// that lowering happens at most once, even when several threads read the
+    /// compilation at the same time.
+    pub fir: OnceLock<(fir::PackageStore, fir::PackageId)>,
+    /// The sources compiled as the standard library in place of the bundled one,
+    /// kept so that recompiling the project uses them again.
+    pub std_sources: Option<Vec<(Arc<str>, Arc<str>)>>,
+    /// For notebooks, the state of the incremental compiler that compiled the
+    /// cells, kept so that compiling more cells can continue from it.
+    pub incremental_state: Option<incremental::CompilerState>,
+    /// The lints of the user package, reused by recompilations after edits
+    /// that only change whitespace or comments.
+    pub lint_cache: LintCache,
 }
 
 #[derive(Debug)]
@@ -42,6 +78,12 @@ pub(crate) enum CompilationKind {
     Notebook,
 }
 
+// Handlers read a shared compilation from several threads at once.
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Compilation>();
+};
+
 impl Compilation {
     /// Creates a new `Compilation` by compiling sources.
     pub(crate) fn new(
@@ -50,6 +92,49 @@ impl Compilation {
         target_profile: Profile,
         language_features: LanguageFeatures,
         lints_config: &[LintConfig],
+    ) -> Self {
+        Self::new_with_std_sources(
+            None,
+            sources,
+            package_type,
+            target_profile,
+            language_features,
+            lints_config,
+        )
+    }
+
+    /// Creates a new `Compilation` by compiling sources against a standard library
+    /// compiled from `std_sources`, or against the bundled one if `std_sources` is `None`.
+    /// Errors in the given standard library are reported along with those of the sources.
+    pub(crate) fn new_with_std_sources(
+        std_sources: Option<SourceMap>,
+        sources: &[(Arc<str>, Arc<str>)],
+        package_type: PackageType,
+        target_profile: Profile,
+        language_features: LanguageFeatures,
+        lints_config: &[LintConfig],
+    ) -> Self {
+        Self::new_with_lint_cache(
+            std_sources,
+            sources,
+            package_type,
+            target_profile,
+            language_features,
+            lints_config,
+            LintCache::default(),
+        )
+    }
+
+    /// Like [`Compilation::new_with_std_sources`], but takes the lints from
+    /// `lint_cache` if they are still valid for the sources.
+    fn new_with_lint_cache(
+        std_sources: Option<SourceMap>,
+        sources: &[(Arc<str>, Arc<str>)],
+        package_type: PackageType,
+        target_profile: Profile,
+        language_features: LanguageFeatures,
+        lints_config: &[LintConfig],
+        mut lint_cache: LintCache,
     ) -> Self {
         if sources.len() == 1 {
             trace!("compiling single-file document {}", sources[0].0);
@@ -59,11 +144,19 @@ impl Compilation {
 
         let source_map = SourceMap::new(sources.iter().map(|(x, y)| (x.clone(), y.clone())), None);
 
+        let std_sources: Option<Vec<_>> = std_sources.map(|std_sources| {
+            std_sources
+                .iter()
+                .map(|source| (source.name.clone(), source.contents.clone()))
+                .collect()
+        });
+
         let mut package_store = PackageStore::new(compile::core());
-        let std_package_id =
-            package_store.insert(compile::std(&package_store, target_profile.into()));
+        let (std_unit, mut errors) =
+            compile_std(&package_store, std_sources.as_deref(), target_profile);
+        let std_package_id = package_store.insert(std_unit);
 
-        let (unit, mut errors) = compile::compile(
+        let (unit, user_errors) = compile::compile(
             &package_store,
             &[std_package_id],
             source_map,
@@ -71,21 +164,7 @@ impl Compilation {
             target_profile.into(),
             language_features,
         );
-
-        // Compute new lints and append them to the errors Vec.
-        // Lints are only computed if the erros vector is empty. For performance
-        // reasons we don't want to waste time running lints every few keystrokes,
-        // if the user is in the middle of typing a statement, for example.
-        if errors.is_empty() {
-            let lints = qsc::linter::run_lints(&unit, Some(lints_config));
-            let lints: Vec<_> = lints
-                .into_iter()
-                .map(|lint| {
-                    WithSource::from_map(&unit.sources, qsc::compile::ErrorKind::Lint(lint))
-                })
-                .collect();
-            errors.extend(lints);
-        }
+        errors.extend(user_errors);
 
         let package_id = package_store.insert(unit);
         let unit = package_store
@@ -100,7 +179,16 @@ impl Compilation {
             unit,
         );
 
-        let lints = qsc::linter::run_lints(unit, Some(lints_config));
+        // Lints run exactly once, after the FIR passes, so that they neither
+        // keep the passes from running nor get reported twice.
+        let lints = lint_cache.lints(
+            &package_store,
+            unit,
+            lints_config,
+            package_type,
+            target_profile,
+            language_features,
+        );
         for lint in lints {
             errors.push(WithSource::from_map(
                 &unit.sources,
@@ -113,6 +201,14 @@ impl Compilation {
             user_package_id: package_id,
             errors,
             kind: CompilationKind::OpenProject,
+            target_profile,
+            language_features,
+            package_type,
+            lints_config: lints_config.to_vec(),
+            fir: OnceLock::new(),
+            std_sources,
+            incremental_state: None,
+            lint_cache,
         }
     }
 
@@ -123,6 +219,27 @@ impl Compilation {
         language_features: LanguageFeatures,
         lints_config: &[LintConfig],
     ) -> Self
+    where
+        I: Iterator<Item = (Arc<str>, Arc<str>)>,
+    {
+        Self::new_notebook_with_lint_cache(
+            cells,
+            target_profile,
+            language_features,
+            lints_config,
+            LintCache::default(),
+        )
+    }
+
+    /// Like [`Compilation::new_notebook`], but takes the lints from
+    /// `lint_cache` if they are still valid for the cells.
+    fn new_notebook_with_lint_cache<I>(
+        cells: I,
+        target_profile: Profile,
+        language_features: LanguageFeatures,
+        lints_config: &[LintConfig],
+        mut lint_cache: LintCache,
+    ) -> Self
     where
         I: Iterator<Item = (Arc<str>, Arc<str>)>,
     {
@@ -149,7 +266,8 @@ impl Compilation {
             compiler.update(increment);
         }
 
-        let (package_store, package_id) = compiler.into_package_store();
+        let (package_store, package_id, incremental_state) =
+            compiler.into_package_store_and_state();
         let unit = package_store
             .get(package_id)
             .expect("expected to find user package");
@@ -159,7 +277,14 @@ impl Compilation {
         // reasons we don't want to waste time running lints every few keystrokes,
         // if the user is in the middle of typing a statement, for example.
         if errors.is_empty() {
-            let lints = qsc::linter::run_lints(unit, Some(lints_config));
+            let lints = lint_cache.lints(
+                &package_store,
+                unit,
+                lints_config,
+                PackageType::Lib,
+                target_profile,
+                language_features,
+            );
             let lints: Vec<_> = lints
                 .into_iter()
                 .map(|lint| {
@@ -182,6 +307,14 @@ impl Compilation {
             user_package_id: package_id,
             errors,
             kind: CompilationKind::Notebook,
+            target_profile,
+            language_features,
+            package_type: PackageType::Lib,
+            lints_config: lints_config.to_vec(),
+            fir: OnceLock::new(),
+            std_sources: None,
+            incremental_state: Some(incremental_state),
+            lint_cache,
         }
     }
 
@@ -254,23 +387,86 @@ impl Compilation {
             .user_unit()
             .sources
             .iter()
-            .map(|source| (source.name.clone(), source.contents.clone()));
+            .map(|source| (source.name.clone(), source.contents.clone()))
+            .collect();
 
+        self.recompile_sources(
+            sources,
+            package_type,
+            target_profile,
+            language_features,
+            lints_config,
+        );
+    }
+
+    /// Replaces the compilation with one built from `sources` and the passed in
+    /// workspace configuration options.
+    fn recompile_sources(
+        &mut self,
+        sources: Vec<(Arc<str>, Arc<str>)>,
+        package_type: PackageType,
+        target_profile: Profile,
+        language_features: LanguageFeatures,
+        lints_config: &[LintConfig],
+    ) {
+        let lint_cache = std::mem::take(&mut self.lint_cache);
         let new = match self.kind {
-            CompilationKind::OpenProject => Self::new(
-                &sources.collect::<Vec<_>>(),
+            CompilationKind::OpenProject => Self::new_with_lint_cache(
+                self.std_sources
+                    .as_ref()
+                    .map(|std_sources| SourceMap::new(std_sources.iter().cloned(), None)),
+                &sources,
                 package_type,
                 target_profile,
                 language_features,
                 lints_config,
+                lint_cache,
+            ),
+            CompilationKind::Notebook => Self::new_notebook_with_lint_cache(
+                sources.into_iter(),
+                target_profile,
+                language_features,
+                lints_config,
+                lint_cache,
             ),
-            CompilationKind::Notebook => {
-                Self::new_notebook(sources, target_profile, language_features, lints_config)
-            }
         };
         self.package_store = new.package_store;
         self.user_package_id = new.user_package_id;
         self.errors = new.errors;
+        self.target_profile = new.target_profile;
+        self.language_features = new.language_features;
+        self.package_type = new.package_type;
+        self.lints_config = new.lints_config;
+        self.fir = new.fir;
+        self.std_sources = new.std_sources;
+        self.incremental_state = new.incremental_state;
+        self.lint_cache = new.lint_cache;
+    }
+}
+
+/// Compiles the standard library from `std_sources`, or returns the bundled one
+/// if `std_sources` is `None`.
+fn compile_std(
+    package_store: &PackageStore,
+    std_sources: Option<&[(Arc<str>, Arc<str>)]>,
+    target_profile: Profile,
+) -> (CompileUnit, Vec<Error>) {
+    match std_sources {
+        Some(std_sources) => {
+            trace!("compiling custom standard library");
+            compile::compile(
+                package_store,
+                &[PackageId::CORE],
+                SourceMap::new(std_sources.iter().cloned(), None),
+                PackageType::Lib,
+                target_profile.into(),
+                LanguageFeatures::default(),
+            )
+        }
+        None => (
+            compile::std(package_store, target_profile.into()),
+            Vec::new(),
+        ),
     }
 }
 
@@ -306,7 +502,7 @@ fn run_fir_passes(
         PassContext::run_fir_passes_on_fir(&fir_store, fir_package_id, target_profile.into());
     if let Err(caps_errors) = caps_results {
         for err in caps_errors {
-            let err = WithSource::from_map(&unit.sources, compile::ErrorKind::Pass(err));
+            let err = WithSource::from_map(&unit.sources, err.into());
             errors.push(err);
         }
     }
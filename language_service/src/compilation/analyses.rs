@@ -0,0 +1,1239 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+//! Analyses over the user package of a [`Compilation`], for tooling built on
+//! top of the language service rather than for the protocol handlers.
+
+use super::{compile_std, Compilation};
+use crate::{
+    hover::{self, HoverInfo},
+    protocol::TextEdit,
+    qsc_utils::{into_range, span_contains},
+    rename,
+};
+use qsc::{
+    ast, codegen,
+    compile::{self, Error},
+    const_eval,
+    display::Lookup,
+    fir,
+    hir::{self, ty::FunctorSetValue, CallableKind, ExprKind, ItemKind, UnOp},
+    incremental::Compiler,
+    interpret::{self, Value},
+    lex::{ConcreteTokenIterator, ConcreteTokenKind, TokenKind},
+    line_column::{Encoding, Position, Range},
+    resolve,
+    target::Profile,
+    LanguageFeatures, PackageStore, PackageType, SourceMap, Span,
+};
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde::Serialize;
+use std::{fmt::Display, sync::Arc};
+
+/// The role a source file plays in a project.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum SourceRole {
+    /// The source contains the entry point.
+    Entry,
+    /// The source contains callables marked with the `@Test()` attribute.
+    Test,
+    /// The source contains neither the entry point nor any tests.
+    Library,
+}
+
+/// Counts describing a compilation, for tools that want a single result
+/// rather than the individual diagnostics.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CompilationSummary {
+    /// The number of diagnostics with error severity, including lints.
+    pub errors: usize,
+    /// The number of diagnostics with any other severity, including lints.
+    pub warnings: usize,
+    /// The number of lints reported as errors.
+    pub lint_errors: usize,
+    /// The number of lints reported as warnings.
+    pub lint_warnings: usize,
+    /// The number of sources in the user package.
+    pub sources: usize,
+    /// The total number of lines in the sources of the user package.
+    pub lines: usize,
+    /// The name of the target profile the user package was compiled against.
+    pub target_profile: &'static str,
+    /// See [`Compilation::is_codegen_ready`].
+    pub codegen_ready: bool,
+}
+
+/// A diagnostic of the compilation, with the span of its first label
+/// resolved to a range within the source that contains it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct ResolvedDiagnostic {
+    pub message: String,
+    /// The name of the source containing the diagnostic, and the range of the
+    /// diagnostic in that source. `None` if the diagnostic has no labels.
+    pub location: Option<(Arc<str>, Range)>,
+}
+
+/// The reason a rename was refused.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum RenameError {
+    /// The new name is not a valid Q# identifier.
+    InvalidName(String),
+    /// There is no symbol that can be renamed at the offset.
+    NoSymbol,
+    /// The symbol is defined in a library rather than in the user package.
+    LibrarySymbol,
+}
+
+impl Display for RenameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RenameError::InvalidName(name) => write!(f, "`{name}` is not a valid identifier"),
+            RenameError::NoSymbol => write!(f, "there is no symbol to rename here"),
+            RenameError::LibrarySymbol => {
+                write!(f, "symbols defined in a library cannot be renamed")
+            }
+        }
+    }
+}
+
+/// Static counts of the gates a callable applies directly, as returned by
+/// [`Compilation::static_t_counts`]. Each count is `None` when a gate of that
+/// kind is applied inside a loop whose number of iterations is not known
+/// statically.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct StaticGateCounts {
+    /// The number of `T` and `Adjoint T` gates.
+    pub t_gates: Option<u64>,
+    /// The number of single-qubit rotations. Their T cost depends on the
+    /// rotation angle and the synthesis precision, so they are not included
+    /// in `t_gates`.
+    pub rotations: Option<u64>,
+}
+
+/// Analyses over the user package, for tooling built on top of the language service.
+impl Compilation {
+    /// Gets the package store lowered to FIR and the FIR id of the user package,
+    /// lowering it on first use.
+    pub(crate) fn fir(&self) -> (&fir::PackageStore, fir::PackageId) {
+        let (fir_store, fir_package_id) = self
+            .fir
+            .get_or_init(|| qsc::lower_hir_to_fir(&self.package_store, self.user_package_id));
+        (fir_store, *fir_package_id)
+    }
+
+    /// Returns the target profile the user package was compiled against.
+    pub(crate) fn target_profile(&self) -> Profile {
+        self.target_profile
+    }
+
+    /// Gets the span of a single zero-based line in the named source file,
+    /// excluding its line terminator. Returns `None` if the source has no such line.
+    pub(crate) fn package_span_of_line(
+        &self,
+        source_name: &str,
+        line: u32,
+        encoding: Encoding,
+    ) -> Option<Span> {
+        let source = self.user_unit().sources.find_by_name(source_name)?;
+        let contents = source.contents.as_ref();
+
+        let text = contents.split('\n').nth(usize::try_from(line).ok()?)?;
+        let text = text.strip_suffix('\r').unwrap_or(text);
+        let len = u32::try_from(text.len()).expect("line length should fit into u32");
+        let lo =
+            source.offset + Position { line, column: 0 }.to_utf8_byte_offset(encoding, contents);
+
+        Some(Span { lo, hi: lo + len })
+    }
+
+    /// Gets the edits that automatically fix the diagnostic at `diagnostic_index`
+    /// in [`Compilation::errors`]. Returns an empty vector if the index is out of
+    /// range or the diagnostic has no automatic fix.
+    pub(crate) fn code_actions_for(
+        &self,
+        diagnostic_index: usize,
+        encoding: Encoding,
+    ) -> Vec<TextEdit> {
+        let Some(compile::ErrorKind::Lint(lint)) =
+            self.errors.get(diagnostic_index).map(Error::error)
+        else {
+            return Vec::new();
+        };
+
+        lint.code_action_edits
+            .iter()
+            .map(|(span, new_text)| TextEdit {
+                new_text: new_text.clone(),
+                range: into_range(encoding, *span, &self.user_unit().sources),
+            })
+            .collect()
+    }
+
+    /// Gets the signature and documentation of the symbol at `position` in the
+    /// named source file. Returns `None` over whitespace, comments, and anything
+    /// else that does not name a symbol.
+    pub(crate) fn hover_at(
+        &self,
+        source_name: &str,
+        position: Position,
+        encoding: Encoding,
+    ) -> Option<HoverInfo> {
+        hover::get_hover_info(self, source_name, position, encoding)
+    }
+
+    /// Returns the language features the user package was compiled with.
+    pub(crate) fn language_features(&self) -> LanguageFeatures {
+        self.language_features
+    }
+
+    /// Returns the package type the user package was compiled as.
+    pub(crate) fn package_type(&self) -> PackageType {
+        self.package_type
+    }
+
+    /// Returns true if code can be generated for the user package: the target
+    /// profile has a QIR representation, which rules out `Unrestricted`, and
+    /// there are no errors other than lints, including capability errors
+    /// reported against the target profile.
+    pub(crate) fn is_codegen_ready(&self) -> bool {
+        self.target_profile != Profile::Unrestricted && self.non_lint_errors().is_empty()
+    }
+
+    /// Summarizes the diagnostics and sources of the compilation.
+    pub(crate) fn summary(&self) -> CompilationSummary {
+        let mut summary = CompilationSummary {
+            errors: 0,
+            warnings: 0,
+            lint_errors: 0,
+            lint_warnings: 0,
+            sources: 0,
+            lines: 0,
+            target_profile: self.target_profile.to_str(),
+            codegen_ready: self.is_codegen_ready(),
+        };
+
+        for error in &self.errors {
+            let is_error = matches!(
+                miette::Diagnostic::severity(error),
+                None | Some(miette::Severity::Error)
+            );
+            let is_lint = matches!(error.error(), compile::ErrorKind::Lint(_));
+            match (is_error, is_lint) {
+                (true, true) => summary.lint_errors += 1,
+                (false, true) => summary.lint_warnings += 1,
+                _ => {}
+            }
+            if is_error {
+                summary.errors += 1;
+            } else {
+                summary.warnings += 1;
+            }
+        }
+
+        for source in self.user_unit().sources.iter() {
+            summary.sources += 1;
+            summary.lines += source.contents.lines().count();
+        }
+
+        summary
+    }
+
+    /// Resolves the first label of each diagnostic in [`Compilation::errors`] to a
+    /// range in its source, returning the diagnostics in the same order.
+    ///
+    /// Rather than walking the contents of a source once per diagnostic, the
+    /// label offsets are sorted and each source is walked at most once, which
+    /// keeps this linear in the size of the sources for files with many diagnostics.
+    pub(crate) fn resolve_all_diagnostics(&self, encoding: Encoding) -> Vec<ResolvedDiagnostic> {
+        let sources = &self.user_unit().sources;
+
+        // The span of the first label of each diagnostic, with the diagnostic's index.
+        let mut spans: Vec<(usize, Span)> = self
+            .errors
+            .iter()
+            .enumerate()
+            .filter_map(|(index, error)| {
+                let label = miette::Diagnostic::labels(error)?.next()?;
+                let lo = u32::try_from(label.offset()).expect("offset should fit into u32");
+                let len = u32::try_from(label.len()).expect("length should fit into u32");
+                Some((index, Span { lo, hi: lo + len }))
+            })
+            .collect();
+        spans.sort_by_key(|(_, span)| span.lo);
+
+        let mut locations: Vec<Option<(Arc<str>, Range)>> = vec![None; self.errors.len()];
+        let mut spans = spans.as_slice();
+        while let Some((_, first)) = spans.first() {
+            let source = sources
+                .find_by_offset(first.lo)
+                .expect("source should exist for offset");
+            // Spans are sorted by their start, so the spans that start in this source
+            // are the ones before the first span that starts in a later source.
+            let count = spans
+                .iter()
+                .position(|(_, span)| {
+                    sources
+                        .find_by_offset(span.lo)
+                        .is_some_and(|s| s.offset != source.offset)
+                })
+                .unwrap_or(spans.len());
+            let (in_source, rest) = spans.split_at(count);
+            spans = rest;
+
+            // The start and end offsets of all the spans in the source, relative to
+            // the source, each with the index of its span and whether it is an end.
+            let mut offsets: Vec<(u32, bool, usize)> = in_source
+                .iter()
+                .enumerate()
+                .flat_map(|(i, (_, span))| {
+                    [
+                        (span.lo - source.offset, false, i),
+                        (span.hi - source.offset, true, i),
+                    ]
+                })
+                .collect();
+            offsets.sort_unstable();
+            let byte_offsets: Vec<u32> = offsets.iter().map(|&(offset, _, _)| offset).collect();
+            let positions =
+                Position::from_sorted_utf8_byte_offsets(encoding, &source.contents, &byte_offsets);
+
+            let mut ranges = vec![
+                Range {
+                    start: Position { line: 0, column: 0 },
+                    end: Position { line: 0, column: 0 },
+                };
+                in_source.len()
+            ];
+            for (&(_, is_end, i), position) in offsets.iter().zip(positions) {
+                if is_end {
+                    ranges[i].end = position;
+                } else {
+                    ranges[i].start = position;
+                }
+            }
+            for (&(index, _), range) in in_source.iter().zip(ranges) {
+                locations[index] = Some((source.name.clone(), range));
+            }
+        }
+
+        self.errors
+            .iter()
+            .zip(locations)
+            .map(|(error, location)| ResolvedDiagnostic {
+                message: error.to_string(),
+                location,
+            })
+            .collect()
+    }
+
+    /// Regenerates the compilation with the same sources and the
+    /// configuration options it was last compiled with.
+    pub(crate) fn recompile_with_defaults(&mut self) {
+        let lints_config = std::mem::take(&mut self.lints_config);
+        self.recompile(
+            self.package_type,
+            self.target_profile,
+            self.language_features,
+            &lints_config,
+        );
+    }
+
+    /// Consumes a notebook compilation and returns the incremental compiler that
+    /// compiled its cells, so that more fragments can be compiled without compiling
+    /// the previous cells again. Items and top-level bindings from previous cells
+    /// are in scope for the new fragments. Returns `None` for project compilations.
+    pub(crate) fn into_incremental(self) -> Option<Compiler> {
+        let state = self.incremental_state?;
+        Some(Compiler::resume(
+            self.package_store,
+            self.user_package_id,
+            state,
+        ))
+    }
+
+    /// Replaces the contents of the named source, or adds it if the user package
+    /// has no such source, and recompiles with the stored configuration.
+    /// The contents of the other sources are shared with the new compilation
+    /// rather than copied.
+    pub(crate) fn update_source(&mut self, name: &str, new_contents: Arc<str>) {
+        let mut new_contents = Some(new_contents);
+        let mut sources: Vec<(Arc<str>, Arc<str>)> = self
+            .user_unit()
+            .sources
+            .iter()
+            .map(|source| {
+                let contents = if source.name.as_ref() == name {
+                    new_contents.take()
+                } else {
+                    None
+                };
+                (
+                    source.name.clone(),
+                    contents.unwrap_or_else(|| source.contents.clone()),
+                )
+            })
+            .collect();
+        if let Some(contents) = new_contents {
+            sources.push((name.into(), contents));
+        }
+
+        let lints_config = std::mem::take(&mut self.lints_config);
+        self.recompile_sources(
+            sources,
+            self.package_type,
+            self.target_profile,
+            self.language_features,
+            &lints_config,
+        );
+    }
+
+    /// Parses the sources without compiling them, for features that only need
+    /// the structure of the code. Only syntax errors are reported.
+    pub(crate) fn parse_only(
+        sources: &[(Arc<str>, Arc<str>)],
+        language_features: LanguageFeatures,
+    ) -> (ast::Package, Vec<Error>) {
+        let source_map = SourceMap::new(sources.iter().cloned(), None);
+        compile::parse(&source_map, language_features)
+    }
+
+    /// Returns, for each operation called from user code, the union of the
+    /// functors applied to it across all of its call sites. Operations that
+    /// are only ever called plainly map to the empty set.
+    pub(crate) fn required_functors(&self) -> FxHashMap<hir::ItemId, FunctorSetValue> {
+        let mut collector = FunctorUsageCollector {
+            compilation: self,
+            functors: FxHashMap::default(),
+        };
+        for item in self.user_unit().package.items.values() {
+            hir::visit::Visitor::visit_item(&mut collector, item);
+        }
+        collector.functors
+    }
+
+    /// Returns, for each callable in the user package, the callables it
+    /// references, in order of first reference. References are direct and
+    /// not followed further, so recursive and mutually recursive callables
+    /// simply reference each other. Callables from other packages, such as
+    /// std, appear only as references, and are left out entirely unless
+    /// `include_library` is set.
+    pub(crate) fn call_graph(
+        &self,
+        include_library: bool,
+    ) -> FxHashMap<hir::ItemId, Vec<hir::ItemId>> {
+        let mut graph = FxHashMap::default();
+        for item in self.user_unit().package.items.values() {
+            if let ItemKind::Callable(_) = &item.kind {
+                let mut collector = CalleeCollector {
+                    compilation: self,
+                    include_library,
+                    seen: FxHashSet::default(),
+                    callees: Vec::new(),
+                };
+                hir::visit::Visitor::visit_item(&mut collector, item);
+                let item_id = hir::ItemId {
+                    package: Some(self.user_package_id),
+                    item: item.id,
+                };
+                graph.insert(item_id, collector.callees);
+            }
+        }
+        graph
+    }
+
+    /// Evaluates the expression at the source-relative `span` of the named source,
+    /// if its value is a compile-time constant. Returns `None` if there is no
+    /// expression with exactly that span, if the package has errors, or if the
+    /// expression could touch qubits, produce output, or depend on runtime values.
+    /// See [`qsc::const_eval::try_eval_expr`] for the exact rules.
+    pub(crate) fn try_const_eval(&self, source_name: &str, span: Span) -> Option<Value> {
+        if !self.non_lint_errors().is_empty() {
+            return None;
+        }
+
+        let source = self.user_unit().sources.find_by_name(source_name)?;
+        let mut finder = ExprAtSpan {
+            span: span + source.offset,
+            expr: None,
+        };
+        hir::visit::Visitor::visit_package(&mut finder, &self.user_unit().package);
+
+        const_eval::try_eval_expr(
+            &self.package_store,
+            self.fir().0,
+            self.user_package_id,
+            finder.expr?,
+        )
+    }
+
+    /// Returns the source name and source-relative span of each expression
+    /// embedded in an interpolated string (`$"..."`) in the user package.
+    pub(crate) fn interpolation_expressions(&self) -> Vec<(Arc<str>, Span)> {
+        let mut collector = InterpolationCollector { spans: Vec::new() };
+        ast::visit::Visitor::visit_package(&mut collector, &self.user_unit().ast.package);
+        collector
+            .spans
+            .into_iter()
+            .map(|span| self.source_relative_span(span))
+            .collect()
+    }
+
+    /// Returns the source name and source-relative span of each conjugation
+    /// (`within { ... } apply { ... }`) in the user package.
+    pub(crate) fn conjugation_blocks(&self) -> Vec<(Arc<str>, Span)> {
+        let mut collector = ConjugationCollector { spans: Vec::new() };
+        ast::visit::Visitor::visit_package(&mut collector, &self.user_unit().ast.package);
+        collector
+            .spans
+            .into_iter()
+            .map(|span| self.source_relative_span(span))
+            .collect()
+    }
+
+    /// Classifies each source in the user package by the role it plays in the project.
+    pub(crate) fn source_roles(&self) -> Vec<(Arc<str>, SourceRole)> {
+        let unit = self.user_unit();
+        let mut roles = unit
+            .sources
+            .iter()
+            .map(|source| (source.name.clone(), SourceRole::Library))
+            .collect::<Vec<_>>();
+
+        for item in unit.package.items.values() {
+            let role = if item.attrs.contains(&hir::Attr::EntryPoint) {
+                SourceRole::Entry
+            } else if item.attrs.contains(&hir::Attr::Test) {
+                SourceRole::Test
+            } else {
+                continue;
+            };
+            let Some(source) = unit.sources.find_by_offset(item.span.lo) else {
+                continue;
+            };
+            if let Some((_, current)) = roles.iter_mut().find(|(name, _)| *name == source.name) {
+                // A source containing the entry point is an entry source
+                // even if it also contains tests.
+                if *current != SourceRole::Entry {
+                    *current = role;
+                }
+            }
+        }
+
+        roles
+    }
+
+    /// Returns an inlay hint for each binding in a `let` or `mutable` statement
+    /// in the given source that has no type annotation. Each hint is placed
+    /// right after the bound name and renders the inferred type, e.g. `: Int`.
+    pub(crate) fn inlay_hints(
+        &self,
+        source_name: &str,
+        encoding: Encoding,
+    ) -> Vec<(Position, String)> {
+        let source = self
+            .user_unit()
+            .sources
+            .find_by_name(source_name)
+            .expect("source should exist in the user source map");
+        let source_span = self.package_span_of_source(source_name);
+
+        let mut collector = UnannotatedBindingCollector {
+            bindings: Vec::new(),
+        };
+        ast::visit::Visitor::visit_package(&mut collector, &self.user_unit().ast.package);
+
+        collector
+            .bindings
+            .into_iter()
+            .filter(|(offset, _)| source_span.lo <= *offset && *offset <= source_span.hi)
+            .filter_map(|(offset, id)| {
+                let ty = self.get_ty(id)?;
+                let position = Position::from_utf8_byte_offset(
+                    encoding,
+                    &source.contents,
+                    offset - source.offset,
+                );
+                Some((position, format!(": {}", ty.display())))
+            })
+            .collect()
+    }
+
+    /// Returns the source name and range of the identifier, literal or keyword
+    /// token that contains the given package offset. An offset right after the
+    /// last character of a token is considered to be within that token.
+    pub(crate) fn token_range_at(
+        &self,
+        offset: u32,
+        encoding: Encoding,
+    ) -> Option<(Arc<str>, Range)> {
+        let source = self.user_unit().sources.find_by_offset(offset)?;
+        let source_offset = offset - source.offset;
+        let token = ConcreteTokenIterator::new(&source.contents)
+            .take_while(|token| token.span.lo <= source_offset)
+            .find(|token| {
+                source_offset <= token.span.hi
+                    && matches!(
+                        token.kind,
+                        ConcreteTokenKind::Syntax(
+                            TokenKind::AposIdent
+                                | TokenKind::BigInt(_)
+                                | TokenKind::Float
+                                | TokenKind::Ident
+                                | TokenKind::Int(_)
+                                | TokenKind::Keyword(_)
+                                | TokenKind::String(_)
+                        )
+                    )
+            })?;
+        Some((
+            source.name.clone(),
+            Range::from_span(encoding, &source.contents, &token.span),
+        ))
+    }
+
+    /// Returns the source name and source-relative span of each assignment
+    /// (`set x = ...`, `set x += ...`, `set x w/= ...`) to the local variable that
+    /// is bound or referenced at the given package offset.
+    /// Returns an empty vector if there is no local variable at the offset.
+    pub(crate) fn assignment_sites(&self, binding_offset: u32) -> Vec<(Arc<str>, Span)> {
+        let package = &self.user_unit().package;
+        let mut finder = LocalAtOffsetFinder {
+            offset: binding_offset,
+            local: None,
+        };
+        hir::visit::Visitor::visit_package(&mut finder, package);
+        let Some(local) = finder.local else {
+            return Vec::new();
+        };
+
+        let mut collector = AssignmentCollector {
+            local,
+            spans: Vec::new(),
+        };
+        hir::visit::Visitor::visit_package(&mut collector, package);
+        collector
+            .spans
+            .into_iter()
+            .map(|span| self.source_relative_span(span))
+            .collect()
+    }
+
+    /// Returns, for each callable declared in the user package, a rough static
+    /// count of the T gates and of the single-qubit rotations in
+    /// `Microsoft.Quantum.Intrinsic` it applies directly; calls to other
+    /// callables are not followed. Loops over literal ranges or array literals
+    /// multiply the counts of their body, and the `within` block of a
+    /// conjugation counts twice.
+    /// This is a lower bound for tooling previews, not a resource estimate.
+    pub(crate) fn static_t_counts(&self) -> FxHashMap<hir::ItemId, StaticGateCounts> {
+        let mut collector = CallableCollector {
+            callables: Vec::new(),
+        };
+        ast::visit::Visitor::visit_package(&mut collector, &self.user_unit().ast.package);
+
+        collector
+            .callables
+            .into_iter()
+            .filter_map(|decl| {
+                let Some(resolve::Res::Item(item_id, _)) = self.get_res(decl.name.id) else {
+                    return None;
+                };
+                let (_, _, item_id) = self.resolve_item_relative_to_user_package(item_id);
+                let mut counter = GateCounter {
+                    compilation: self,
+                    multiplier: Some(1),
+                    counts: StaticGateCounts {
+                        t_gates: Some(0),
+                        rotations: Some(0),
+                    },
+                };
+                ast::visit::Visitor::visit_callable_decl(&mut counter, decl);
+                Some((item_id, counter.counts))
+            })
+            .collect()
+    }
+
+    /// Returns each callable declared in the user package with its name and the
+    /// number of source lines its declaration spans, longest first.
+    /// Callables with the same line count keep their declaration order.
+    pub(crate) fn callables_by_line_count(&self) -> Vec<(hir::ItemId, Arc<str>, u32)> {
+        let unit = self.user_unit();
+        let mut collector = CallableCollector {
+            callables: Vec::new(),
+        };
+        ast::visit::Visitor::visit_package(&mut collector, &unit.ast.package);
+
+        let mut callables = collector
+            .callables
+            .into_iter()
+            .filter_map(|decl| {
+                let Some(resolve::Res::Item(item_id, _)) = self.get_res(decl.name.id) else {
+                    return None;
+                };
+                let (_, _, item_id) = self.resolve_item_relative_to_user_package(item_id);
+                let source = unit.sources.find_by_offset(decl.span.lo)?;
+                let text = &source.contents[(decl.span.lo - source.offset) as usize
+                    ..(decl.span.hi - source.offset) as usize];
+                let lines = u32::try_from(text.lines().count()).unwrap_or(u32::MAX);
+                Some((item_id, Arc::from(decl.name.name.as_ref()), lines))
+            })
+            .collect::<Vec<_>>();
+        callables.sort_by(|(_, _, a), (_, _, b)| b.cmp(a));
+        callables
+    }
+
+    /// Returns the innermost item in the user package whose span contains the
+    /// given package offset, or `None` if the offset is outside every item,
+    /// such as in whitespace between namespaces.
+    pub(crate) fn item_containing_offset(&self, offset: u32) -> Option<hir::ItemId> {
+        self.user_unit()
+            .package
+            .items
+            .iter()
+            .filter(|(_, item)| span_contains(item.span, offset))
+            .min_by_key(|(_, item)| item.span.hi - item.span.lo)
+            .map(|(id, _)| hir::ItemId {
+                package: Some(self.user_package_id),
+                item: id,
+            })
+    }
+
+    /// Returns the edits that rename the symbol at the given package offset,
+    /// together with all its references, to `new_name`. Each edit is the source
+    /// name and range of the text to replace, and the replacement text.
+    pub(crate) fn rename(
+        &self,
+        offset: u32,
+        new_name: &str,
+        encoding: Encoding,
+    ) -> Result<Vec<(Arc<str>, Range, String)>, RenameError> {
+        if !is_identifier(new_name) {
+            return Err(RenameError::InvalidName(new_name.to_string()));
+        }
+
+        let (locations, is_library_symbol) = rename::get_rename_at_offset(self, offset, encoding);
+        if is_library_symbol {
+            return Err(RenameError::LibrarySymbol);
+        }
+        if locations.is_empty() {
+            return Err(RenameError::NoSymbol);
+        }
+
+        Ok(locations
+            .into_iter()
+            .map(|location| (location.source, location.range, new_name.to_string()))
+            .collect())
+    }
+
+    /// Generates QIR for the user package under its target profile.
+    ///
+    /// When `entry` is `None`, the package's own entry point is used. Otherwise the
+    /// user sources are recompiled with `entry` as the entry expression.
+    /// Fails if the target profile is `Unrestricted`, which has no QIR representation,
+    /// or if the compilation has errors (lints are not considered errors here).
+    pub(crate) fn to_qir(&self, entry: Option<&str>) -> Result<String, Vec<interpret::Error>> {
+        if self.target_profile == Profile::Unrestricted {
+            return Err(vec![interpret::Error::UnsupportedRuntimeCapabilities]);
+        }
+
+        let errors = self.non_lint_errors();
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        let Some(entry) = entry else {
+            return codegen::get_qir_from_fir(
+                &self.package_store,
+                self.fir().0,
+                self.user_package_id,
+                self.target_profile.into(),
+            );
+        };
+
+        let sources = SourceMap::new(
+            self.user_unit()
+                .sources
+                .iter()
+                .map(|source| (source.name.clone(), source.contents.clone())),
+            Some(entry.into()),
+        );
+        // Compile against the same standard library as the compilation itself.
+        let mut package_store = PackageStore::new(compile::core());
+        let (std_unit, mut errors) = compile_std(
+            &package_store,
+            self.std_sources.as_deref(),
+            self.target_profile,
+        );
+        let std_package_id = package_store.insert(std_unit);
+        let (unit, user_errors) = compile::compile(
+            &package_store,
+            &[std_package_id],
+            sources,
+            PackageType::Exe,
+            self.target_profile.into(),
+            self.language_features,
+        );
+        errors.extend(user_errors);
+        if !errors.is_empty() {
+            return Err(errors.into_iter().map(interpret::Error::Compile).collect());
+        }
+        let package_id = package_store.insert(unit);
+        codegen::get_qir_from_package(&package_store, package_id, self.target_profile.into())
+    }
+
+    /// Renders a text diagram of the circuit traced from the entry point,
+    /// with one row per qubit.
+    ///
+    /// When `entry` is `None`, the package's own entry point is used. Otherwise the
+    /// user sources are recompiled with `entry` as the entry expression.
+    /// The circuit is traced without simulation, so programs whose gates depend on
+    /// measurement results fail. Fails if the target profile is `Unrestricted`,
+    /// which does not guarantee a static circuit, or if the compilation has errors.
+    pub(crate) fn render_circuit(
+        &self,
+        entry: Option<&str>,
+    ) -> Result<String, Vec<interpret::Error>> {
+        if self.target_profile == Profile::Unrestricted {
+            return Err(vec![interpret::Error::UnsupportedRuntimeCapabilities]);
+        }
+
+        let errors = self.non_lint_errors();
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        let sources = SourceMap::new(
+            self.user_unit()
+                .sources
+                .iter()
+                .map(|source| (source.name.clone(), source.contents.clone())),
+            entry.map(Into::into),
+        );
+        let mut interpreter = interpret::Interpreter::new(
+            true,
+            sources,
+            PackageType::Exe,
+            self.target_profile.into(),
+            self.language_features,
+        )?;
+        let circuit = interpreter.circuit(interpret::CircuitEntryPoint::EntryPoint, false)?;
+        Ok(circuit.to_string())
+    }
+
+    /// Returns the compilation errors, excluding lints, as interpreter errors.
+    fn non_lint_errors(&self) -> Vec<interpret::Error> {
+        self.errors
+            .iter()
+            .filter(|error| !matches!(error.error(), compile::ErrorKind::Lint(_)))
+            .cloned()
+            .map(interpret::Error::Compile)
+            .collect()
+    }
+
+    /// Maps a package span to the name of the source that contains it
+    /// and the span relative to the start of that source.
+    fn source_relative_span(&self, span: Span) -> (Arc<str>, Span) {
+        let source = self
+            .user_unit()
+            .sources
+            .find_by_offset(span.lo)
+            .expect("source should exist for span in the user package");
+        (
+            source.name.clone(),
+            Span {
+                lo: span.lo - source.offset,
+                hi: span.hi - source.offset,
+            },
+        )
+    }
+}
+
+struct FunctorUsageCollector<'a> {
+    compilation: &'a Compilation,
+    functors: FxHashMap<hir::ItemId, FunctorSetValue>,
+}
+
+impl FunctorUsageCollector<'_> {
+    fn record(&mut self, callee: &hir::Expr) {
+        let mut callee = callee;
+        let mut applied = FunctorSetValue::Empty;
+        while let ExprKind::UnOp(UnOp::Functor(functor), inner) = &callee.kind {
+            let functor = match functor {
+                hir::Functor::Adj => FunctorSetValue::Adj,
+                hir::Functor::Ctl => FunctorSetValue::Ctl,
+            };
+            applied = applied.union(&functor);
+            callee = inner;
+        }
+
+        if let ExprKind::Var(hir::Res::Item(item_id), _) = &callee.kind {
+            let (item, _, item_id) = self
+                .compilation
+                .resolve_item(self.compilation.user_package_id, item_id);
+            if let ItemKind::Callable(decl) = &item.kind {
+                if decl.kind == CallableKind::Operation {
+                    let entry = self.functors.entry(item_id).or_default();
+                    *entry = entry.union(&applied);
+                }
+            }
+        }
+    }
+}
+
+impl<'a> hir::visit::Visitor<'a> for FunctorUsageCollector<'_> {
+    fn visit_expr(&mut self, expr: &'a hir::Expr) {
+        match &expr.kind {
+            ExprKind::Call(callee, _) => self.record(callee),
+            ExprKind::UnOp(UnOp::Functor(_), _) => self.record(expr),
+            _ => {}
+        }
+        hir::visit::walk_expr(self, expr);
+    }
+}
+
+struct CalleeCollector<'a> {
+    compilation: &'a Compilation,
+    include_library: bool,
+    seen: FxHashSet<hir::ItemId>,
+    callees: Vec<hir::ItemId>,
+}
+
+impl<'a> hir::visit::Visitor<'a> for CalleeCollector<'_> {
+    fn visit_expr(&mut self, expr: &'a hir::Expr) {
+        if let ExprKind::Var(hir::Res::Item(item_id), _) = &expr.kind {
+            let (item, _, item_id) = self
+                .compilation
+                .resolve_item(self.compilation.user_package_id, item_id);
+            let is_user_item = item_id.package == Some(self.compilation.user_package_id);
+            if matches!(item.kind, ItemKind::Callable(_))
+                && (self.include_library || is_user_item)
+                && self.seen.insert(item_id)
+            {
+                self.callees.push(item_id);
+            }
+        }
+        hir::visit::walk_expr(self, expr);
+    }
+}
+
+/// Finds the outermost expression with exactly the given span.
+struct ExprAtSpan<'a> {
+    span: Span,
+    expr: Option<&'a hir::Expr>,
+}
+
+impl<'a> hir::visit::Visitor<'a> for ExprAtSpan<'a> {
+    fn visit_expr(&mut self, expr: &'a hir::Expr) {
+        if self.expr.is_some() {
+            return;
+        }
+        if expr.span == self.span {
+            self.expr = Some(expr);
+        } else if expr.span.lo <= self.span.lo && self.span.hi <= expr.span.hi {
+            hir::visit::walk_expr(self, expr);
+        }
+    }
+}
+
+struct InterpolationCollector {
+    spans: Vec<Span>,
+}
+
+impl<'a> ast::visit::Visitor<'a> for InterpolationCollector {
+    fn visit_expr(&mut self, expr: &'a ast::Expr) {
+        if let ast::ExprKind::Interpolate(components) = expr.kind.as_ref() {
+            for component in components.as_ref() {
+                if let ast::StringComponent::Expr(expr) = component {
+                    self.spans.push(expr.span);
+                }
+            }
+        }
+        ast::visit::walk_expr(self, expr);
+    }
+}
+
+struct ConjugationCollector {
+    spans: Vec<Span>,
+}
+
+impl<'a> ast::visit::Visitor<'a> for ConjugationCollector {
+    fn visit_expr(&mut self, expr: &'a ast::Expr) {
+        if let ast::ExprKind::Conjugate(..) = expr.kind.as_ref() {
+            self.spans.push(expr.span);
+        }
+        ast::visit::walk_expr(self, expr);
+    }
+}
+
+struct UnannotatedBindingCollector {
+    /// The package offset right after each bound name, and the id of its pattern.
+    bindings: Vec<(u32, ast::NodeId)>,
+}
+
+impl UnannotatedBindingCollector {
+    fn collect(&mut self, pat: &ast::Pat) {
+        match pat.kind.as_ref() {
+            ast::PatKind::Bind(ident, None) => self.bindings.push((ident.span.hi, pat.id)),
+            ast::PatKind::Paren(pat) => self.collect(pat),
+            ast::PatKind::Tuple(pats) => {
+                for pat in pats.as_ref() {
+                    self.collect(pat);
+                }
+            }
+            ast::PatKind::Bind(_, Some(_))
+            | ast::PatKind::Discard(_)
+            | ast::PatKind::Elided
+            | ast::PatKind::Err => {}
+        }
+    }
+}
+
+impl<'a> ast::visit::Visitor<'a> for UnannotatedBindingCollector {
+    fn visit_stmt(&mut self, stmt: &'a ast::Stmt) {
+        if let ast::StmtKind::Local(_, pat, _) = stmt.kind.as_ref() {
+            self.collect(pat);
+        }
+        ast::visit::walk_stmt(self, stmt);
+    }
+}
+
+struct LocalAtOffsetFinder {
+    offset: u32,
+    local: Option<hir::NodeId>,
+}
+
+impl<'a> hir::visit::Visitor<'a> for LocalAtOffsetFinder {
+    fn visit_pat(&mut self, pat: &'a hir::Pat) {
+        if let hir::PatKind::Bind(ident) = &pat.kind {
+            if ident.span.lo <= self.offset && self.offset <= ident.span.hi {
+                self.local = Some(ident.id);
+            }
+        }
+        hir::visit::walk_pat(self, pat);
+    }
+
+    fn visit_expr(&mut self, expr: &'a hir::Expr) {
+        if let ExprKind::Var(hir::Res::Local(id), _) = &expr.kind {
+            if expr.span.lo <= self.offset && self.offset <= expr.span.hi {
+                self.local = Some(*id);
+            }
+        }
+        hir::visit::walk_expr(self, expr);
+    }
+}
+
+struct AssignmentCollector {
+    local: hir::NodeId,
+    spans: Vec<Span>,
+}
+
+impl AssignmentCollector {
+    /// Returns true if assigning to `lhs` updates the local variable.
+    fn assigns_local(&self, lhs: &hir::Expr) -> bool {
+        match &lhs.kind {
+            ExprKind::Var(hir::Res::Local(id), _) => *id == self.local,
+            ExprKind::Tuple(items) => items.iter().any(|item| self.assigns_local(item)),
+            _ => false,
+        }
+    }
+}
+
+impl<'a> hir::visit::Visitor<'a> for AssignmentCollector {
+    fn visit_expr(&mut self, expr: &'a hir::Expr) {
+        match &expr.kind {
+            ExprKind::Assign(lhs, _)
+            | ExprKind::AssignOp(_, lhs, _)
+            | ExprKind::AssignField(lhs, _, _)
+            | ExprKind::AssignIndex(lhs, _, _)
+                if self.assigns_local(lhs) =>
+            {
+                self.spans.push(expr.span);
+            }
+            _ => {}
+        }
+        hir::visit::walk_expr(self, expr);
+    }
+}
+
+struct CallableCollector<'a> {
+    callables: Vec<&'a ast::CallableDecl>,
+}
+
+impl<'a> ast::visit::Visitor<'a> for CallableCollector<'a> {
+    fn visit_callable_decl(&mut self, decl: &'a ast::CallableDecl) {
+        self.callables.push(decl);
+        ast::visit::walk_callable_decl(self, decl);
+    }
+}
+
+/// The single-qubit rotation intrinsics.
+const ROTATION_INTRINSICS: [&str; 5] = ["R", "R1", "Rx", "Ry", "Rz"];
+
+/// A kind of gate counted by [`GateCounter`].
+#[derive(Clone, Copy)]
+enum CountedGate {
+    T,
+    Rotation,
+}
+
+struct GateCounter<'a> {
+    compilation: &'a Compilation,
+    /// The number of times the current expression runs, or `None` if unknown.
+    multiplier: Option<u64>,
+    counts: StaticGateCounts,
+}
+
+impl GateCounter<'_> {
+    /// Returns the kind of gate applied by calling `callee`, if it is one of
+    /// the counted intrinsics.
+    fn counted_gate(&self, callee: &ast::Expr) -> Option<CountedGate> {
+        let mut callee = callee;
+        while let ast::ExprKind::Paren(inner) | ast::ExprKind::UnOp(ast::UnOp::Functor(_), inner) =
+            callee.kind.as_ref()
+        {
+            callee = inner;
+        }
+        let ast::ExprKind::Path(path) = callee.kind.as_ref() else {
+            return None;
+        };
+        let Some(resolve::Res::Item(item_id, _)) = self.compilation.get_res(path.id) else {
+            return None;
+        };
+        let (item, package, _) = self
+            .compilation
+            .resolve_item_relative_to_user_package(item_id);
+        let ItemKind::Callable(decl) = &item.kind else {
+            return None;
+        };
+        let in_intrinsic_namespace = item
+            .parent
+            .and_then(|parent| package.items.get(parent))
+            .is_some_and(|parent| {
+                matches!(&parent.kind, ItemKind::Namespace(name, _)
+                    if name.name.as_ref() == "Microsoft.Quantum.Intrinsic")
+            });
+        if !in_intrinsic_namespace {
+            return None;
+        }
+        match decl.name.name.as_ref() {
+            "T" => Some(CountedGate::T),
+            name if ROTATION_INTRINSICS.contains(&name) => Some(CountedGate::Rotation),
+            _ => None,
+        }
+    }
+
+    /// Walks `f` with the multiplier scaled by `iterations`, where `None`
+    /// means the number of iterations is unknown.
+    fn repeated(&mut self, iterations: Option<u64>, f: impl FnOnce(&mut Self)) {
+        let outer = self.multiplier;
+        self.multiplier = outer.zip(iterations).map(|(m, n)| m.saturating_mul(n));
+        f(self);
+        self.multiplier = outer;
+    }
+}
+
+impl<'a> ast::visit::Visitor<'a> for GateCounter<'_> {
+    fn visit_item(&mut self, _: &'a ast::Item) {
+        // Nested callables are counted on their own.
+    }
+
+    fn visit_expr(&mut self, expr: &'a ast::Expr) {
+        match expr.kind.as_ref() {
+            ast::ExprKind::Call(callee, _) => {
+                if let Some(gate) = self.counted_gate(callee) {
+                    let count = match gate {
+                        CountedGate::T => &mut self.counts.t_gates,
+                        CountedGate::Rotation => &mut self.counts.rotations,
+                    };
+                    *count = count
+                        .zip(self.multiplier)
+                        .map(|(count, m)| count.saturating_add(m));
+                }
+            }
+            ast::ExprKind::For(pat, iterable, block) => {
+                ast::visit::Visitor::visit_pat(self, pat);
+                ast::visit::Visitor::visit_expr(self, iterable);
+                self.repeated(static_iterations(iterable), |this| {
+                    ast::visit::Visitor::visit_block(this, block);
+                });
+                return;
+            }
+            ast::ExprKind::While(cond, block) => {
+                self.repeated(None, |this| {
+                    ast::visit::Visitor::visit_expr(this, cond);
+                    ast::visit::Visitor::visit_block(this, block);
+                });
+                return;
+            }
+            ast::ExprKind::Repeat(body, until, fixup) => {
+                self.repeated(None, |this| {
+                    ast::visit::Visitor::visit_block(this, body);
+                    ast::visit::Visitor::visit_expr(this, until);
+                    if let Some(fixup) = fixup {
+                        ast::visit::Visitor::visit_block(this, fixup);
+                    }
+                });
+                return;
+            }
+            ast::ExprKind::Conjugate(within, apply) => {
+                self.repeated(Some(2), |this| {
+                    ast::visit::Visitor::visit_block(this, within);
+                });
+                ast::visit::Visitor::visit_block(self, apply);
+                return;
+            }
+            _ => {}
+        }
+        ast::visit::walk_expr(self, expr);
+    }
+}
+
+/// Returns the number of iterations of a `for` loop over `iterable`
+/// if it is a range with literal bounds or an array literal.
+fn static_iterations(iterable: &ast::Expr) -> Option<u64> {
+    match iterable.kind.as_ref() {
+        ast::ExprKind::Paren(inner) => static_iterations(inner),
+        ast::ExprKind::Array(items) => u64::try_from(items.len()).ok(),
+        ast::ExprKind::Range(Some(start), step, Some(end)) => {
+            let start = int_literal(start)?;
+            let end = int_literal(end)?;
+            let step = match step {
+                Some(step) => int_literal(step)?,
+                None => 1,
+            };
+            let span = match step.signum() {
+                1 if start <= end => end.checked_sub(start)?,
+                -1 if end <= start => start.checked_sub(end)?,
+                0 => return None,
+                _ => return Some(0),
+            };
+            u64::try_from(span / step.abs() + 1).ok()
+        }
+        _ => None,
+    }
+}
+
+fn int_literal(expr: &ast::Expr) -> Option<i64> {
+    match expr.kind.as_ref() {
+        ast::ExprKind::Lit(lit) => match lit.as_ref() {
+            ast::Lit::Int(value) => Some(*value),
+            _ => None,
+        },
+        ast::ExprKind::Paren(inner) => int_literal(inner),
+        ast::ExprKind::UnOp(ast::UnOp::Neg, inner) => int_literal(inner)?.checked_neg(),
+        _ => None,
+    }
+}
+
+/// Returns true if `name` lexes as a single identifier, which rules out keywords.
+fn is_identifier(name: &str) -> bool {
+    let mut tokens = ConcreteTokenIterator::new(name);
+    matches!(
+        (tokens.next(), tokens.next()),
+        (Some(token), None)
+            if token.kind == ConcreteTokenKind::Syntax(TokenKind::Ident)
+                && token.span.lo == 0
+                && token.span.hi as usize == name.len()
+    )
+}
@@ -0,0 +1,159 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use qsc::{
+    lex::{ConcreteTokenIterator, ConcreteTokenKind},
+    target::Profile,
+    CompileUnit, LanguageFeatures, PackageStore, PackageType, Span,
+};
+use qsc_linter::{Lint, LintConfig};
+use rustc_hash::FxHasher;
+use std::{
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
+
+/// The lints computed for the last compilation, kept so that recompiling after
+/// an edit that only touches whitespace or comments can reuse them instead of
+/// running the linter again.
+#[derive(Debug, Default)]
+pub(crate) struct LintCache {
+    /// The inputs the cached lints were computed from, or `None` if
+    /// no lints have been computed yet.
+    key: Option<LintCacheKey>,
+    /// The tokens of each source the cached lints were computed from,
+    /// used to move the lints to the same tokens in the new sources.
+    sources: Vec<SourceTokens>,
+    /// The cached lints, with spans into the sources in `sources`.
+    lints: Vec<Lint>,
+    /// The number of times the linter has actually run.
+    #[cfg(test)]
+    pub(crate) lint_runs: usize,
+}
+
+/// Everything that affects the lints reported for a package.
+#[derive(Debug, PartialEq)]
+struct LintCacheKey {
+    lints_config: Vec<LintConfig>,
+    package_type: PackageType,
+    target_profile: Profile,
+    language_features: LanguageFeatures,
+    /// The name of each source with a hash of its tokens, ignoring whitespace and comments.
+    source_hashes: Vec<(Arc<str>, u64)>,
+}
+
+/// The position of a source in the package and the spans of its tokens,
+/// relative to the start of the source.
+#[derive(Debug)]
+struct SourceTokens {
+    offset: u32,
+    len: u32,
+    tokens: Vec<Span>,
+}
+
+impl LintCache {
+    /// Returns the lints for the user package in `unit`. If the configuration is
+    /// the same as for the cached lints and the sources differ only in whitespace
+    /// and comments, the cached lints are returned with their spans moved to the
+    /// same tokens in the new sources. Otherwise the linter is run and its result cached.
+    pub(crate) fn lints(
+        &mut self,
+        package_store: &PackageStore,
+        unit: &CompileUnit,
+        lints_config: &[LintConfig],
+        package_type: PackageType,
+        target_profile: Profile,
+        language_features: LanguageFeatures,
+    ) -> Vec<Lint> {
+        let mut source_hashes = Vec::new();
+        let mut sources = Vec::new();
+        for source in unit.sources.iter() {
+            let (hash, tokens) = tokenize(&source.contents);
+            source_hashes.push((source.name.clone(), hash));
+            sources.push(SourceTokens {
+                offset: source.offset,
+                len: u32::try_from(source.contents.len())
+                    .expect("source length should fit into u32"),
+                tokens,
+            });
+        }
+        let key = LintCacheKey {
+            lints_config: lints_config.to_vec(),
+            package_type,
+            target_profile,
+            language_features,
+            source_hashes,
+        };
+
+        if self.key.as_ref() == Some(&key) {
+            for lint in &mut self.lints {
+                lint.span = remap_span(lint.span, &self.sources, &sources);
+                for (span, _) in &mut lint.code_action_edits {
+                    *span = remap_span(*span, &self.sources, &sources);
+                }
+            }
+        } else {
+            self.lints = qsc_linter::run_lints(package_store, unit, Some(lints_config));
+            self.key = Some(key);
+            #[cfg(test)]
+            {
+                self.lint_runs += 1;
+            }
+        }
+        self.sources = sources;
+        self.lints.clone()
+    }
+}
+
+/// Returns a hash of the tokens in `contents`, ignoring whitespace and comments,
+/// along with the spans of those tokens.
+fn tokenize(contents: &str) -> (u64, Vec<Span>) {
+    let mut hasher = FxHasher::default();
+    let mut tokens = Vec::new();
+    for token in ConcreteTokenIterator::new(contents) {
+        if matches!(
+            token.kind,
+            ConcreteTokenKind::WhiteSpace | ConcreteTokenKind::Comment
+        ) {
+            continue;
+        }
+        contents[token.span.lo as usize..token.span.hi as usize].hash(&mut hasher);
+        tokens.push(token.span);
+    }
+    (hasher.finish(), tokens)
+}
+
+/// Moves a package span from the old sources to the new ones.
+fn remap_span(span: Span, old: &[SourceTokens], new: &[SourceTokens]) -> Span {
+    Span {
+        lo: remap_offset(span.lo, old, new),
+        hi: remap_offset(span.hi, old, new),
+    }
+}
+
+/// Moves a package offset from the old sources to the new ones. Both must
+/// have the same sources in the same order, with the same tokens.
+fn remap_offset(offset: u32, old: &[SourceTokens], new: &[SourceTokens]) -> u32 {
+    let index = old
+        .partition_point(|source| source.offset <= offset)
+        .saturating_sub(1);
+    let (old, new) = (&old[index], &new[index]);
+    let offset = offset - old.offset;
+
+    // An offset within a token keeps its position relative to that token.
+    let token = old.tokens.partition_point(|token| token.hi < offset);
+    if let Some(span) = old.tokens.get(token) {
+        if span.lo <= offset {
+            return new.offset + new.tokens[token].lo + (offset - span.lo);
+        }
+    }
+
+    // An offset between tokens keeps its distance from the end of the previous
+    // token, but does not move past the start of the next one.
+    let (old_start, new_start) = match token.checked_sub(1) {
+        Some(previous) => (old.tokens[previous].hi, new.tokens[previous].hi),
+        None => (0, 0),
+    };
+    let new_end = new.tokens.get(token).map_or(new.len, |span| span.lo);
+    new.offset + (new_start + (offset - old_start)).min(new_end)
+}
@@ -0,0 +1,2094 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#![allow(clippy::needless_raw_string_hashes)]
+
+use super::{Compilation, CompilationSummary, RenameError, ResolvedDiagnostic, SourceRole};
+use crate::{
+    qsc_utils::into_range,
+    test_utils::{
+        compile_project_with_fake_stdlib_and_markers_no_cursor,
+        compile_with_fake_stdlib_and_markers_no_cursor,
+    },
+};
+use expect_test::{expect, Expect};
+use indoc::indoc;
+use qsc::{
+    ast,
+    compile::ErrorKind,
+    display::Lookup,
+    hir::ItemKind,
+    interpret::Value,
+    line_column::{Encoding, Position},
+    target::Profile,
+    LanguageFeatures, PackageType, SourceMap, Span,
+};
+use std::{fmt::Write, sync::Arc};
+
+fn compile(source: &str) -> Compilation {
+    let (compilation, _) = compile_with_fake_stdlib_and_markers_no_cursor(source);
+    assert!(
+        compilation.errors.is_empty(),
+        "expected no compilation errors: {:?}",
+        compilation.errors
+    );
+    compilation
+}
+
+fn check_required_functors(source: &str, expect: &Expect) {
+    let compilation = compile(source);
+    let mut functors = compilation
+        .required_functors()
+        .into_iter()
+        .map(|(item_id, functors)| {
+            let (item, _, _) = compilation.resolve_item_relative_to_user_package(&item_id);
+            let ItemKind::Callable(decl) = &item.kind else {
+                panic!("expected a callable");
+            };
+            format!("{}: {functors}", decl.name.name)
+        })
+        .collect::<Vec<_>>();
+    functors.sort();
+    expect.assert_eq(&functors.join("\n"));
+}
+
+#[test]
+fn required_functors_plain_and_controlled_call() {
+    check_required_functors(
+        indoc! {r#"
+        namespace Test {
+            @EntryPoint()
+            operation Main() : Unit {}
+            operation Foo(q : Qubit) : Unit is Ctl {}
+            operation Bar(ctl : Qubit, q : Qubit) : Unit {
+                Foo(q);
+                Controlled Foo([ctl], q);
+            }
+        }
+    "#},
+        &expect![[r#"
+            Foo: Ctl"#]],
+    );
+}
+
+#[test]
+fn required_functors_union_across_call_sites() {
+    check_required_functors(
+        indoc! {r#"
+        namespace Test {
+            @EntryPoint()
+            operation Main() : Unit {}
+            operation Foo(q : Qubit) : Unit is Adj + Ctl {}
+            operation Bar(q : Qubit) : Unit {}
+            operation Baz(ctl : Qubit, q : Qubit) : Unit {
+                Adjoint Foo(q);
+                Controlled Foo([ctl], q);
+                Bar(q);
+            }
+        }
+    "#},
+        &expect![[r#"
+            Bar: empty set
+            Foo: Adj + Ctl"#]],
+    );
+}
+
+#[test]
+fn required_functors_include_library_operations() {
+    check_required_functors(
+        indoc! {r#"
+        namespace Test {
+            @EntryPoint()
+            operation Main() : Unit {}
+            open FakeStdLib;
+            operation Foo() : Unit {
+                Adjoint FakeCtlAdj();
+            }
+        }
+    "#},
+        &expect![[r#"
+            FakeCtlAdj: Adj"#]],
+    );
+}
+
+fn check_interpolation_expressions(source: &str, expect: &Expect) {
+    let compilation = compile(source);
+    let expressions = compilation
+        .interpolation_expressions()
+        .into_iter()
+        .map(|(name, span)| format!("{name}: {:?}", &source[span.lo as usize..span.hi as usize]))
+        .collect::<Vec<_>>();
+    expect.assert_eq(&expressions.join("\n"));
+}
+
+#[test]
+fn interpolation_expressions_reports_embedded_expression() {
+    check_interpolation_expressions(
+        indoc! {r#"
+        namespace Test {
+            @EntryPoint()
+            function Main() : String {
+                let x = 1;
+                $"value is {x + 1}"
+            }
+        }
+    "#},
+        &expect![[r#"
+            <source>: "x + 1""#]],
+    );
+}
+
+#[test]
+fn interpolation_expressions_reports_multiple_and_nested_expressions() {
+    check_interpolation_expressions(
+        indoc! {r#"
+        namespace Test {
+            @EntryPoint()
+            function Main() : String {
+                let (a, b) = (1, 2.0);
+                let plain = "not {a}";
+                $"{a} and {$"inner {b}"}"
+            }
+        }
+    "#},
+        &expect![[r#"
+            <source>: "a"
+            <source>: "$\"inner {b}\""
+            <source>: "b""#]],
+    );
+}
+
+#[test]
+fn source_roles_classifies_entry_test_and_library_sources() {
+    let (compilation, _) = compile_project_with_fake_stdlib_and_markers_no_cursor(&[
+        (
+            "Main.qs",
+            indoc! {r#"
+            namespace Main {
+                @EntryPoint()
+                operation Main() : Unit {
+                    Helpers.Helper();
+                }
+            }
+        "#},
+        ),
+        (
+            "Tests.qs",
+            indoc! {r#"
+            namespace Tests {
+                @Test()
+                operation CheckHelper() : Unit {
+                    Helpers.Helper();
+                }
+            }
+        "#},
+        ),
+        (
+            "Helpers.qs",
+            indoc! {r#"
+            namespace Helpers {
+                operation Helper() : Unit {}
+            }
+        "#},
+        ),
+    ]);
+    assert!(compilation.errors.is_empty(), "{:?}", compilation.errors);
+
+    assert_eq!(
+        compilation.source_roles(),
+        vec![
+            ("Main.qs".into(), SourceRole::Entry),
+            ("Tests.qs".into(), SourceRole::Test),
+            ("Helpers.qs".into(), SourceRole::Library),
+        ]
+    );
+}
+
+#[test]
+fn inlay_hints_for_unannotated_bindings() {
+    let compilation = compile(indoc! {r#"
+        namespace Test {
+            @EntryPoint()
+            operation Main() : Unit {
+                let x = 1 + 2;
+                let y : Double = 1.0;
+                mutable (a, b) = (true, [x]);
+            }
+        }
+    "#});
+
+    let hints = compilation
+        .inlay_hints("<source>", Encoding::Utf8)
+        .into_iter()
+        .map(|(position, hint)| format!("{}:{} {hint}", position.line, position.column))
+        .collect::<Vec<_>>();
+    expect![[r#"
+        3:13 : Int
+        5:18 : Bool
+        5:21 : Int[]"#]]
+    .assert_eq(&hints.join("\n"));
+}
+
+fn compile_with_profile(source: &str, target_profile: Profile) -> Compilation {
+    Compilation::new(
+        &[("<source>".into(), source.into())],
+        PackageType::Exe,
+        target_profile,
+        LanguageFeatures::default(),
+        &[],
+    )
+}
+
+#[test]
+fn to_qir_base_profile_entry_point() {
+    let compilation = compile_with_profile(
+        indoc! {r#"
+        namespace Test {
+            @EntryPoint()
+            operation Main() : Result {
+                use q = Qubit();
+                H(q);
+                M(q)
+            }
+        }
+    "#},
+        Profile::Base,
+    );
+
+    let qir = compilation
+        .to_qir(None)
+        .expect("QIR generation should succeed");
+    assert!(qir.contains("define void @ENTRYPOINT__main()"), "{qir}");
+    assert!(qir.contains("__quantum__qis__h__body"), "{qir}");
+}
+
+#[test]
+fn to_qir_with_entry_expression() {
+    let compilation = compile_with_profile(
+        indoc! {r#"
+        namespace Test {
+            @EntryPoint()
+            operation Main() : Result {
+                Measure(false)
+            }
+            operation Measure(flip : Bool) : Result {
+                use q = Qubit();
+                if flip {
+                    X(q);
+                }
+                M(q)
+            }
+        }
+    "#},
+        Profile::Base,
+    );
+
+    let qir = compilation
+        .to_qir(Some("Test.Measure(true)"))
+        .expect("QIR generation should succeed");
+    assert!(qir.contains("define void @ENTRYPOINT__main()"), "{qir}");
+    assert!(qir.contains("__quantum__qis__x__body"), "{qir}");
+}
+
+#[test]
+fn to_qir_with_entry_expression_uses_custom_std_sources() {
+    let std_sources = SourceMap::new(
+        [(
+            "custom_std.qs".into(),
+            indoc! {r#"
+                namespace Custom.Std {
+                    operation Custom(q : Qubit) : Unit {
+                        body intrinsic;
+                    }
+                }
+            "#}
+            .into(),
+        )],
+        None,
+    );
+    let compilation = Compilation::new_with_std_sources(
+        Some(std_sources),
+        &[(
+            "<source>".into(),
+            indoc! {r#"
+                namespace Test {
+                    operation Apply() : Unit {
+                        use q = Qubit();
+                        Custom.Std.Custom(q);
+                    }
+                }
+            "#}
+            .into(),
+        )],
+        PackageType::Lib,
+        Profile::Quantinuum,
+        LanguageFeatures::default(),
+        &[],
+    );
+    assert!(compilation.errors.is_empty(), "{:?}", compilation.errors);
+
+    let qir = compilation
+        .to_qir(Some("Test.Apply()"))
+        .expect("QIR generation should succeed");
+    assert!(qir.contains("declare void @Custom(%Qubit*)"), "{qir}");
+}
+
+#[test]
+fn to_qir_unrestricted_profile_is_rejected() {
+    let compilation = compile_with_profile(
+        indoc! {r#"
+        namespace Test {
+            @EntryPoint()
+            operation Main() : Result {
+                use q = Qubit();
+                M(q)
+            }
+        }
+    "#},
+        Profile::Unrestricted,
+    );
+
+    let errors = compilation
+        .to_qir(None)
+        .expect_err("QIR generation should fail");
+    expect![[r#"
+        [
+            UnsupportedRuntimeCapabilities,
+        ]
+    "#]]
+    .assert_debug_eq(&errors);
+}
+
+#[test]
+fn to_qir_with_compilation_errors_is_rejected() {
+    let compilation = compile_with_profile(
+        indoc! {r#"
+        namespace Test {
+            @EntryPoint()
+            operation Main() : Result {
+                Undefined()
+            }
+        }
+    "#},
+        Profile::Base,
+    );
+
+    let errors = compilation
+        .to_qir(None)
+        .expect_err("QIR generation should fail");
+    let messages = errors.iter().map(ToString::to_string).collect::<Vec<_>>();
+    expect![[r#"
+        [
+            "name error",
+        ]
+    "#]]
+    .assert_debug_eq(&messages);
+}
+
+#[test]
+fn to_qir_reuses_fir_lowering() {
+    let mut compilation = compile_with_profile(
+        indoc! {r#"
+        namespace Test {
+            @EntryPoint()
+            operation Main() : Result {
+                use q = Qubit();
+                H(q);
+                M(q)
+            }
+        }
+    "#},
+        Profile::Quantinuum,
+    );
+    assert!(compilation.errors.is_empty(), "{:?}", compilation.errors);
+    assert!(
+        compilation.fir.get().is_none(),
+        "the FIR cache should only be filled on first use"
+    );
+
+    let first = compilation
+        .to_qir(None)
+        .expect("QIR generation should succeed");
+    let lowered: *const _ = compilation
+        .fir
+        .get()
+        .map(|(fir_store, _)| fir_store)
+        .expect("QIR generation should fill the FIR cache");
+    let second = compilation
+        .to_qir(None)
+        .expect("QIR generation should succeed");
+    assert_eq!(first, second);
+    assert!(std::ptr::eq(lowered, compilation.fir().0));
+
+    compilation.update_source("<source>", "namespace Test {}".into());
+    assert!(
+        compilation.fir.get().is_none(),
+        "recompiling should drop the FIR cache"
+    );
+}
+
+#[test]
+fn render_circuit_bell_pair() {
+    let compilation = compile_with_profile(
+        indoc! {r#"
+        namespace Test {
+            @EntryPoint()
+            operation Main() : (Result, Result) {
+                use (q0, q1) = (Qubit(), Qubit());
+                H(q0);
+                CNOT(q0, q1);
+                (M(q0), M(q1))
+            }
+        }
+    "#},
+        Profile::Quantinuum,
+    );
+
+    let diagram = compilation
+        .render_circuit(None)
+        .expect("circuit rendering should succeed");
+    let rows = diagram
+        .lines()
+        .filter(|line| line.starts_with("q_"))
+        .collect::<Vec<_>>();
+    assert_eq!(rows.len(), 2, "{diagram}");
+    assert!(
+        rows[0].contains(" H ") && rows[0].contains('●'),
+        "{diagram}"
+    );
+    assert!(
+        rows[1].contains(" X ") && !rows[1].contains('●'),
+        "{diagram}"
+    );
+    assert!(rows.iter().all(|row| row.contains(" M ")), "{diagram}");
+}
+
+#[test]
+fn render_circuit_dynamic_program_is_rejected() {
+    let compilation = compile_with_profile(
+        indoc! {r#"
+        namespace Test {
+            @EntryPoint()
+            operation Main() : Result {
+                use (q0, q1) = (Qubit(), Qubit());
+                H(q0);
+                if M(q0) == One {
+                    X(q1);
+                }
+                M(q1)
+            }
+        }
+    "#},
+        Profile::Quantinuum,
+    );
+
+    let errors = compilation
+        .render_circuit(None)
+        .expect_err("circuit rendering should fail");
+    assert!(!errors.is_empty());
+}
+
+#[test]
+fn capability_errors_name_minimal_profile() {
+    let compilation = compile_with_profile(
+        indoc! {r#"
+        namespace Test {
+            @EntryPoint()
+            operation Main() : Result {
+                use (q0, q1) = (Qubit(), Qubit());
+                H(q0);
+                if M(q0) == One {
+                    X(q1);
+                }
+                M(q1)
+            }
+        }
+    "#},
+        Profile::Base,
+    );
+
+    assert!(!compilation.errors.is_empty());
+    for error in &compilation.errors {
+        assert_eq!(error.error().required_profile(), Some(Profile::Quantinuum));
+        let help = miette::Diagnostic::help(error)
+            .expect("capability errors should have help")
+            .to_string();
+        assert!(
+            help.ends_with("this requires at least the Quantinuum target profile"),
+            "{help}"
+        );
+    }
+}
+
+#[test]
+fn capability_errors_from_fir_passes_name_minimal_profile() {
+    let compilation = compile_with_profile(
+        indoc! {r#"
+        namespace Test {
+            @EntryPoint()
+            operation Main() : Result {
+                use q = Qubit();
+                H(q);
+                let angle = M(q) == One ? 1.0 | 0.0;
+                Rx(angle, q);
+                M(q)
+            }
+        }
+    "#},
+        Profile::Quantinuum,
+    );
+
+    assert!(!compilation.errors.is_empty());
+    for error in &compilation.errors {
+        assert_eq!(
+            error.error().required_profile(),
+            Some(Profile::Unrestricted)
+        );
+    }
+}
+
+#[test]
+fn non_capability_errors_name_no_profile() {
+    let compilation = compile_with_profile(
+        indoc! {r#"
+        namespace Test {
+            @EntryPoint()
+            operation Main() : Unit {
+                let x : Int = true;
+            }
+        }
+    "#},
+        Profile::Base,
+    );
+
+    assert!(!compilation.errors.is_empty());
+    for error in &compilation.errors {
+        assert_eq!(error.error().required_profile(), None);
+    }
+}
+
+#[test]
+fn token_range_at_identifier() {
+    let compilation = compile_with_profile(
+        indoc! {r#"
+        namespace Test {
+            function Main() : Int {
+                let foo = 42;
+                foo
+            }
+        }
+    "#},
+        Profile::Unrestricted,
+    );
+
+    let source = &compilation
+        .user_unit()
+        .sources
+        .iter()
+        .next()
+        .expect("source should exist");
+    let foo = u32::try_from(
+        source
+            .contents
+            .find("foo")
+            .expect("source should contain `foo`"),
+    )
+    .expect("offset should fit in u32");
+    let (name, range) = compilation
+        .token_range_at(source.offset + foo + 1, Encoding::Utf8)
+        .expect("offset should be within a token");
+
+    assert_eq!(name, source.name);
+    expect![[r#"
+        Range {
+            start: Position {
+                line: 2,
+                column: 12,
+            },
+            end: Position {
+                line: 2,
+                column: 15,
+            },
+        }
+    "#]]
+    .assert_debug_eq(&range);
+}
+
+#[test]
+fn token_range_at_whitespace() {
+    let compilation = compile_with_profile(
+        indoc! {r#"
+        namespace Test {
+            function Main() : Unit {}
+        }
+    "#},
+        Profile::Unrestricted,
+    );
+
+    let source = &compilation
+        .user_unit()
+        .sources
+        .iter()
+        .next()
+        .expect("source should exist");
+    assert!(compilation
+        .token_range_at(source.offset + 17, Encoding::Utf8)
+        .is_none());
+}
+
+#[test]
+fn assignment_sites_of_mutable_binding() {
+    let source = indoc! {r#"
+        namespace Test {
+            function Main() : Int {
+                mutable x = 0;
+                mutable y = 0;
+                set x = 1;
+                set y = 5;
+                set x += 2;
+                set (x, y) = (y, x);
+                x
+            }
+        }
+    "#};
+    let compilation = compile_with_profile(source, Profile::Unrestricted);
+
+    let x = u32::try_from(source.find("x = 0").expect("source should contain `x`"))
+        .expect("offset should fit in u32");
+    let sites = compilation
+        .assignment_sites(x)
+        .into_iter()
+        .map(|(name, span)| format!("{name}: {}", &source[span]))
+        .collect::<Vec<_>>();
+
+    expect![[r#"
+        [
+            "<source>: set x = 1",
+            "<source>: set x += 2",
+            "<source>: set (x, y) = (y, x)",
+        ]
+    "#]]
+    .assert_debug_eq(&sites);
+}
+
+#[test]
+fn assignment_sites_without_local_at_offset() {
+    let compilation = compile_with_profile(
+        indoc! {r#"
+        namespace Test {
+            function Main() : Unit {}
+        }
+    "#},
+        Profile::Unrestricted,
+    );
+
+    assert!(compilation.assignment_sites(0).is_empty());
+}
+
+#[test]
+fn parse_only_ignores_type_errors() {
+    let source = indoc! {r#"
+        namespace Test {
+            function Main() : Int {
+                let x : Bool = 42;
+                Undefined(x)
+            }
+        }
+    "#};
+    let sources = [("<source>".into(), source.into())];
+
+    let compilation = Compilation::new(
+        &sources,
+        PackageType::Lib,
+        Profile::Unrestricted,
+        LanguageFeatures::default(),
+        &[],
+    );
+    assert!(!compilation.errors.is_empty());
+
+    let (package, errors) = Compilation::parse_only(&sources, LanguageFeatures::default());
+    assert!(errors.is_empty(), "{errors:?}");
+    let [ast::TopLevelNode::Namespace(namespace)] = package.nodes.as_ref() else {
+        panic!("expected a single namespace");
+    };
+    let [item] = namespace.items.as_ref() else {
+        panic!("expected a single item");
+    };
+    let ast::ItemKind::Callable(decl) = item.kind.as_ref() else {
+        panic!("expected a callable");
+    };
+    assert_eq!(decl.name.name.as_ref(), "Main");
+    let ast::CallableBody::Block(block) = decl.body.as_ref() else {
+        panic!("expected a block body");
+    };
+    assert_eq!(block.stmts.len(), 2);
+}
+
+#[test]
+fn parse_only_reports_syntax_errors() {
+    let sources = [(
+        "<source>".into(),
+        "namespace Test { function Main() : Unit { let x = ; } }".into(),
+    )];
+
+    let (_, errors) = Compilation::parse_only(&sources, LanguageFeatures::default());
+    expect![[r#"
+        [
+            "syntax error",
+        ]
+    "#]]
+    .assert_debug_eq(&errors.iter().map(ToString::to_string).collect::<Vec<_>>());
+}
+
+fn check_static_t_counts(source: &str, expect: &Expect) {
+    let compilation = compile_with_profile(source, Profile::Unrestricted);
+    let mut counts = compilation
+        .static_t_counts()
+        .into_iter()
+        .map(|(item_id, count)| {
+            let (item, _, _) = compilation.resolve_item_relative_to_user_package(&item_id);
+            let ItemKind::Callable(decl) = &item.kind else {
+                panic!("expected a callable");
+            };
+            format!("{}: {count:?}", decl.name.name)
+        })
+        .collect::<Vec<_>>();
+    counts.sort();
+    expect.assert_eq(&counts.join("\n"));
+}
+
+#[test]
+fn static_t_counts_direct_calls() {
+    check_static_t_counts(
+        indoc! {r#"
+        namespace Test {
+            @EntryPoint()
+            operation Main() : Unit {
+                use q = Qubit();
+                ApplyT(q);
+            }
+            operation ApplyT(q : Qubit) : Unit {
+                T(q);
+                T(q);
+                Adjoint T(q);
+                Rz(0.1, q);
+                H(q);
+            }
+        }
+    "#},
+        &expect![[r#"
+            ApplyT: StaticGateCounts { t_gates: Some(3), rotations: Some(1) }
+            Main: StaticGateCounts { t_gates: Some(0), rotations: Some(0) }"#]],
+    );
+}
+
+#[test]
+fn static_t_counts_static_loops_and_conjugations() {
+    check_static_t_counts(
+        indoc! {r#"
+        namespace Test {
+            @EntryPoint()
+            operation Main() : Unit {
+                use q = Qubit();
+                for _ in 0..3 {
+                    Rz(0.1, q);
+                }
+                for _ in 4..-2..0 {
+                    T(q);
+                }
+                within {
+                    T(q);
+                } apply {
+                    H(q);
+                }
+            }
+        }
+    "#},
+        &expect!["Main: StaticGateCounts { t_gates: Some(5), rotations: Some(4) }"],
+    );
+}
+
+#[test]
+fn static_t_counts_dynamic_loop() {
+    check_static_t_counts(
+        indoc! {r#"
+        namespace Test {
+            @EntryPoint()
+            operation Main() : Unit {
+                use q = Qubit();
+                ApplyTs(2, q);
+            }
+            operation ApplyTs(n : Int, q : Qubit) : Unit {
+                for _ in 1..n {
+                    T(q);
+                }
+                Rx(0.1, q);
+            }
+        }
+    "#},
+        &expect![[r#"
+            ApplyTs: StaticGateCounts { t_gates: None, rotations: Some(1) }
+            Main: StaticGateCounts { t_gates: Some(0), rotations: Some(0) }"#]],
+    );
+}
+
+#[test]
+fn callables_by_line_count_longest_first() {
+    let compilation = compile(indoc! {r#"
+        namespace Test {
+            operation Short() : Unit {
+            }
+            operation Long() : Unit {
+                mutable x = 0;
+                set x += 1;
+                set x += 2;
+                set x += 3;
+                set x += 4;
+                set x += 5;
+                set x += 6;
+                set x += 7;
+            }
+            @EntryPoint()
+            operation Main() : Unit { Short(); Long(); }
+        }
+    "#});
+    let callables = compilation
+        .callables_by_line_count()
+        .into_iter()
+        .map(|(item_id, name, lines)| {
+            let (item, _, _) = compilation.resolve_item_relative_to_user_package(&item_id);
+            let ItemKind::Callable(decl) = &item.kind else {
+                panic!("expected a callable");
+            };
+            assert_eq!(decl.name.name.as_ref(), name.as_ref());
+            format!("{name}: {lines}")
+        })
+        .collect::<Vec<_>>();
+    expect![[r#"
+        [
+            "Long: 10",
+            "Short: 2",
+            "Main: 1",
+        ]
+    "#]]
+    .assert_debug_eq(&callables);
+}
+
+fn item_name_containing(compilation: &Compilation, source: &str, marker: &str) -> Option<String> {
+    let offset = u32::try_from(source.find(marker).expect("marker should be in source"))
+        .expect("offset should fit in u32");
+    compilation.item_containing_offset(offset).map(|item_id| {
+        let (item, _, _) = compilation.resolve_item_relative_to_user_package(&item_id);
+        match &item.kind {
+            ItemKind::Callable(decl) => decl.name.name.to_string(),
+            ItemKind::Namespace(name, _) => name.name.to_string(),
+            ItemKind::Ty(..) => panic!("expected a callable or namespace"),
+        }
+    })
+}
+
+#[test]
+fn item_containing_offset_in_callable_body() {
+    let source = indoc! {r#"
+        namespace Test {
+            @EntryPoint()
+            operation Foo() : Unit {
+                let x = 1;
+            }
+        }
+    "#};
+    let compilation = compile(source);
+    assert_eq!(
+        item_name_containing(&compilation, source, "let x").as_deref(),
+        Some("Foo")
+    );
+}
+
+#[test]
+fn item_containing_offset_resolves_to_innermost_item() {
+    let source = indoc! {r#"
+        namespace Test {
+            @EntryPoint()
+            operation Outer() : Unit {
+                function Inner() : Int {
+                    let y = 2;
+                    y
+                }
+                let x = Inner();
+            }
+        }
+    "#};
+    let compilation = compile(source);
+    assert_eq!(
+        item_name_containing(&compilation, source, "let y").as_deref(),
+        Some("Inner")
+    );
+    assert_eq!(
+        item_name_containing(&compilation, source, "let x").as_deref(),
+        Some("Outer")
+    );
+}
+
+#[test]
+fn item_containing_offset_between_namespaces_is_none() {
+    let source = indoc! {r#"
+        namespace First {
+            operation Foo() : Unit {}
+        }
+
+        namespace Second {
+            @EntryPoint()
+            operation Bar() : Unit {}
+        }
+    "#};
+    let compilation = compile(source);
+    let offset = u32::try_from(
+        source
+            .find("\n\nnamespace Second")
+            .expect("marker should be in source")
+            + 1,
+    )
+    .expect("offset should fit in u32");
+    assert_eq!(compilation.item_containing_offset(offset), None);
+    assert_eq!(
+        item_name_containing(&compilation, source, "namespace Second").as_deref(),
+        Some("Second")
+    );
+}
+
+#[test]
+fn is_codegen_ready_for_clean_base_program() {
+    let compilation = compile_with_profile(
+        indoc! {r#"
+        namespace Test {
+            @EntryPoint()
+            operation Main() : Result {
+                use q = Qubit();
+                H(q);
+                M(q)
+            }
+        }
+    "#},
+        Profile::Base,
+    );
+
+    assert_eq!(compilation.target_profile(), Profile::Base);
+    assert!(compilation.is_codegen_ready());
+}
+
+#[test]
+fn is_codegen_ready_with_capability_error() {
+    let compilation = compile_with_profile(
+        indoc! {r#"
+        namespace Test {
+            @EntryPoint()
+            operation Main() : Result {
+                use q = Qubit();
+                if M(q) == One {
+                    X(q);
+                }
+                M(q)
+            }
+        }
+    "#},
+        Profile::Base,
+    );
+
+    assert!(!compilation.errors.is_empty());
+    assert!(!compilation.is_codegen_ready());
+}
+
+#[test]
+fn is_codegen_ready_for_unrestricted_program() {
+    let compilation = compile_with_profile(
+        indoc! {r#"
+        namespace Test {
+            @EntryPoint()
+            operation Main() : Unit {}
+        }
+    "#},
+        Profile::Unrestricted,
+    );
+
+    assert_eq!(compilation.target_profile(), Profile::Unrestricted);
+    assert!(!compilation.is_codegen_ready());
+}
+
+#[test]
+fn recompile_with_defaults_preserves_configuration() {
+    let lints_config: Vec<qsc_linter::LintConfig> =
+        serde_json::from_str(r#"[{ "lint": "divisionByZero", "level": "error" }]"#)
+            .expect("lint config should deserialize");
+    let mut compilation = Compilation::new(
+        &[(
+            "<source>".into(),
+            "namespace Test { function Foo() : Int { 1 / 0 } }".into(),
+        )],
+        PackageType::Lib,
+        Profile::Base,
+        LanguageFeatures::V2PreviewSyntax,
+        &lints_config,
+    );
+    let errors_before = compilation.errors.len();
+    assert!(errors_before > 0, "expected a division by zero lint");
+
+    compilation.recompile_with_defaults();
+
+    assert_eq!(compilation.target_profile(), Profile::Base);
+    assert_eq!(
+        compilation.language_features(),
+        LanguageFeatures::V2PreviewSyntax
+    );
+    assert_eq!(compilation.package_type(), PackageType::Lib);
+    assert_eq!(compilation.lints_config.len(), 1);
+    assert_eq!(
+        compilation.errors.len(),
+        errors_before,
+        "{:?}",
+        compilation.errors
+    );
+}
+
+fn check_conjugation_blocks(source: &str, expect: &Expect) {
+    let compilation = compile(source);
+    let blocks = compilation
+        .conjugation_blocks()
+        .into_iter()
+        .map(|(name, span)| format!("{name}: {:?}", &source[span.lo as usize..span.hi as usize]))
+        .collect::<Vec<_>>();
+    expect.assert_eq(&blocks.join("\n"));
+}
+
+#[test]
+fn conjugation_blocks_reports_within_apply() {
+    check_conjugation_blocks(
+        indoc! {r#"
+        namespace Test {
+            open FakeStdLib;
+            @EntryPoint()
+            operation Main() : Unit {
+                within { FakeCtlAdj(); } apply { Fake(); }
+            }
+        }
+    "#},
+        &expect![[r#"
+            <source>: "within { FakeCtlAdj(); } apply { Fake(); }""#]],
+    );
+}
+
+#[test]
+fn conjugation_blocks_ignores_plain_blocks() {
+    check_conjugation_blocks(
+        indoc! {r#"
+        namespace Test {
+            open FakeStdLib;
+            @EntryPoint()
+            operation Main() : Unit {
+                { FakeCtlAdj(); }
+                Fake();
+            }
+        }
+    "#},
+        &expect![""],
+    );
+}
+
+#[test]
+fn lints_are_reported_once() {
+    let lints_config: Vec<qsc_linter::LintConfig> =
+        serde_json::from_str(r#"[{ "lint": "divisionByZero", "level": "warn" }]"#)
+            .expect("lint config should deserialize");
+    let compilation = Compilation::new(
+        &[(
+            "<source>".into(),
+            "namespace Test { function Foo() : Int { 1 / 0 } }".into(),
+        )],
+        PackageType::Lib,
+        Profile::Unrestricted,
+        LanguageFeatures::default(),
+        &lints_config,
+    );
+
+    let lints = compilation
+        .errors
+        .iter()
+        .filter(|error| matches!(error.error(), ErrorKind::Lint(_)))
+        .count();
+    assert_eq!(lints, 1, "{:?}", compilation.errors);
+}
+
+#[test]
+fn rename_local_with_two_uses() {
+    let source = indoc! {r#"
+        namespace Test {
+            @EntryPoint()
+            function Main() : Int {
+                let value = 1;
+                value + value
+            }
+        }
+    "#};
+    let compilation = compile(source);
+    let offset = u32::try_from(source.find("value").expect("binding should exist"))
+        .expect("offset should fit into u32");
+
+    let mut edits = compilation
+        .rename(offset, "total", Encoding::Utf8)
+        .expect("rename should succeed");
+    edits.sort_by_key(|(_, range, _)| (range.start.line, range.start.column));
+    let edits = edits
+        .into_iter()
+        .map(|(name, range, text)| {
+            format!(
+                "{name} {}:{}-{}:{} {text}",
+                range.start.line, range.start.column, range.end.line, range.end.column
+            )
+        })
+        .collect::<Vec<_>>();
+    expect![[r#"
+        [
+            "<source> 3:12-3:17 total",
+            "<source> 4:8-4:13 total",
+            "<source> 4:16-4:21 total",
+        ]
+    "#]]
+    .assert_debug_eq(&edits);
+}
+
+#[test]
+fn rename_rejects_library_symbol() {
+    let source = indoc! {r#"
+        namespace Test {
+            open FakeStdLib;
+            @EntryPoint()
+            operation Main() : Unit {
+                Fake();
+            }
+        }
+    "#};
+    let compilation = compile(source);
+    let offset = u32::try_from(source.find("Fake()").expect("call should exist"))
+        .expect("offset should fit into u32");
+
+    assert_eq!(
+        compilation.rename(offset, "Renamed", Encoding::Utf8),
+        Err(RenameError::LibrarySymbol)
+    );
+}
+
+#[test]
+fn rename_rejects_invalid_names() {
+    let source = indoc! {r#"
+        namespace Test {
+            @EntryPoint()
+            function Main() : Int {
+                let value = 1;
+                value
+            }
+        }
+    "#};
+    let compilation = compile(source);
+    let offset = u32::try_from(source.find("value").expect("binding should exist"))
+        .expect("offset should fit into u32");
+
+    for name in ["", "1st", "two words", "let", "a.b"] {
+        assert_eq!(
+            compilation.rename(offset, name, Encoding::Utf8),
+            Err(RenameError::InvalidName(name.to_string())),
+            "{name:?} should be rejected"
+        );
+    }
+    assert_eq!(
+        compilation.rename(0, "other", Encoding::Utf8),
+        Err(RenameError::NoSymbol)
+    );
+}
+
+fn check_package_span_of_line(line: u32, expected: Option<&str>) {
+    let source = "namespace Test {\r\n    @EntryPoint() function Main() : Unit {}\n}";
+    let compilation = compile(source);
+    let source = compilation
+        .user_unit()
+        .sources
+        .iter()
+        .next()
+        .expect("source should exist");
+
+    let span = compilation.package_span_of_line(&source.name, line, Encoding::Utf16);
+    let text = span.map(|span| {
+        &source.contents[(span.lo - source.offset) as usize..(span.hi - source.offset) as usize]
+    });
+    assert_eq!(text, expected);
+}
+
+#[test]
+fn package_span_of_first_line() {
+    check_package_span_of_line(0, Some("namespace Test {"));
+}
+
+#[test]
+fn package_span_of_middle_line() {
+    check_package_span_of_line(1, Some("    @EntryPoint() function Main() : Unit {}"));
+}
+
+#[test]
+fn package_span_of_last_line_without_trailing_newline() {
+    check_package_span_of_line(2, Some("}"));
+}
+
+#[test]
+fn package_span_of_out_of_range_line() {
+    check_package_span_of_line(3, None);
+}
+
+#[test]
+fn update_source_recompiles_changed_file_and_shares_others() {
+    let main: Arc<str> = indoc! {r#"
+        namespace Main {
+            @EntryPoint()
+            operation Main() : Result {
+                use q = Qubit();
+                Helpers.Helper();
+                MResetZ(q)
+            }
+        }
+    "#}
+    .into();
+    let helpers: Arc<str> = indoc! {r#"
+        namespace Helpers {
+            operation Helper() : Unit {}
+        }
+    "#}
+    .into();
+    let mut compilation = Compilation::new(
+        &[
+            ("Main.qs".into(), main.clone()),
+            ("Helpers.qs".into(), helpers.clone()),
+        ],
+        PackageType::Exe,
+        Profile::Base,
+        LanguageFeatures::default(),
+        &[],
+    );
+    assert!(compilation.errors.is_empty(), "{:?}", compilation.errors);
+
+    compilation.update_source(
+        "Helpers.qs",
+        "namespace Helpers { operation Helper() : Unit { let x : Int = true; } }".into(),
+    );
+
+    let messages: Vec<_> = compilation.errors.iter().map(ToString::to_string).collect();
+    assert_eq!(messages, ["type error"]);
+    assert_eq!(compilation.target_profile(), Profile::Base);
+    assert_eq!(compilation.package_type(), PackageType::Exe);
+
+    let sources = &compilation.user_unit().sources;
+    let main_source = sources
+        .find_by_name("Main.qs")
+        .expect("Main.qs should exist");
+    assert!(Arc::ptr_eq(&main_source.contents, &main));
+    let helpers_source = sources
+        .find_by_name("Helpers.qs")
+        .expect("Helpers.qs should exist");
+    assert!(!Arc::ptr_eq(&helpers_source.contents, &helpers));
+
+    compilation.update_source("Helpers.qs", helpers);
+    assert!(compilation.errors.is_empty(), "{:?}", compilation.errors);
+}
+
+/// Returns the source name and text covered by each lint in the compilation.
+fn lint_locations(compilation: &Compilation) -> Vec<(String, String)> {
+    let sources = &compilation.user_unit().sources;
+    compilation
+        .errors
+        .iter()
+        .filter_map(|error| match error.error() {
+            ErrorKind::Lint(lint) => {
+                let source = sources
+                    .find_by_offset(lint.span.lo)
+                    .expect("lint should be in a user source");
+                let text = &source.contents[(lint.span.lo - source.offset) as usize
+                    ..(lint.span.hi - source.offset) as usize];
+                Some((source.name.to_string(), text.to_string()))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn lints_are_reused_after_whitespace_and_comment_edits() {
+    let mut compilation = Compilation::new(
+        &[
+            (
+                "A.qs".into(),
+                "namespace A { function F() : Unit {} }".into(),
+            ),
+            (
+                "B.qs".into(),
+                "namespace B { function G() : Int { 1 / 0 } }".into(),
+            ),
+        ],
+        PackageType::Lib,
+        Profile::Unrestricted,
+        LanguageFeatures::default(),
+        &[],
+    );
+    assert_eq!(compilation.lint_cache.lint_runs, 1);
+    assert_eq!(
+        lint_locations(&compilation),
+        [("B.qs".to_string(), "1 / 0".to_string())]
+    );
+
+    compilation.update_source(
+        "A.qs",
+        "// A comment.\nnamespace A {\n    function F() : Unit {}\n}\n".into(),
+    );
+    compilation.update_source(
+        "B.qs",
+        "namespace B {\n    function G() : Int {\n        1 /   0 // Oops.\n    }\n}\n".into(),
+    );
+    assert_eq!(compilation.lint_cache.lint_runs, 1);
+    assert_eq!(
+        lint_locations(&compilation),
+        [("B.qs".to_string(), "1 /   0".to_string())]
+    );
+
+    compilation.update_source(
+        "B.qs",
+        "namespace B {\n    function G() : Int {\n        2 / 0\n    }\n}\n".into(),
+    );
+    assert_eq!(compilation.lint_cache.lint_runs, 2);
+    assert_eq!(
+        lint_locations(&compilation),
+        [("B.qs".to_string(), "2 / 0".to_string())]
+    );
+
+    compilation.update_source("B.qs", "namespace B { function G() : Int { 2 } }".into());
+    assert_eq!(compilation.lint_cache.lint_runs, 3);
+    assert!(lint_locations(&compilation).is_empty());
+}
+
+fn check_call_graph(source: &str, include_library: bool, expect: &Expect) {
+    let compilation = compile(source);
+    let name = |item_id: &qsc::hir::ItemId| {
+        let (item, _, _) = compilation.resolve_item_relative_to_user_package(item_id);
+        let ItemKind::Callable(decl) = &item.kind else {
+            panic!("expected a callable");
+        };
+        decl.name.name.to_string()
+    };
+    let mut edges = compilation
+        .call_graph(include_library)
+        .iter()
+        .map(|(caller, callees)| {
+            let callees = callees.iter().map(name).collect::<Vec<_>>();
+            format!("{} -> [{}]", name(caller), callees.join(", "))
+        })
+        .collect::<Vec<_>>();
+    edges.sort();
+    expect.assert_eq(&edges.join("\n"));
+}
+
+#[test]
+fn call_graph_mutual_recursion() {
+    check_call_graph(
+        indoc! {r#"
+        namespace Test {
+            @EntryPoint()
+            operation Main() : Unit {
+                A(3);
+            }
+            operation A(n : Int) : Unit {
+                if n > 0 {
+                    B(n - 1);
+                }
+            }
+            operation B(n : Int) : Unit {
+                A(n);
+                B(n - 1);
+                A(n - 1);
+            }
+        }
+    "#},
+        false,
+        &expect![[r#"
+            A -> [B]
+            B -> [A, B]
+            Main -> [A]"#]],
+    );
+}
+
+#[test]
+fn call_graph_library_references() {
+    let source = indoc! {r#"
+        namespace Test {
+            open FakeStdLib;
+            @EntryPoint()
+            operation Main() : Unit {
+                Fake();
+                Helper(FakeCtlAdj);
+            }
+            operation Helper(op : Unit => Unit) : Unit {
+                op();
+            }
+        }
+    "#};
+    check_call_graph(
+        source,
+        false,
+        &expect![[r#"
+            Helper -> []
+            Main -> [Helper]"#]],
+    );
+    check_call_graph(
+        source,
+        true,
+        &expect![[r#"
+            Helper -> []
+            Main -> [Fake, Helper, FakeCtlAdj]"#]],
+    );
+}
+
+#[test]
+fn code_actions_for_needless_parens_remove_them() {
+    let lints_config: Vec<qsc_linter::LintConfig> =
+        serde_json::from_str(r#"[{ "lint": "needlessParens", "level": "warn" }]"#)
+            .expect("lint config should deserialize");
+    let compilation = Compilation::new(
+        &[(
+            "<source>".into(),
+            "namespace Test { function Foo() : Int { let x = (42); x } }".into(),
+        )],
+        PackageType::Lib,
+        Profile::Unrestricted,
+        LanguageFeatures::default(),
+        &lints_config,
+    );
+    assert_eq!(compilation.errors.len(), 1, "{:?}", compilation.errors);
+
+    expect![[r#"
+        [
+            TextEdit {
+                new_text: "",
+                range: Range {
+                    start: Position {
+                        line: 0,
+                        column: 48,
+                    },
+                    end: Position {
+                        line: 0,
+                        column: 49,
+                    },
+                },
+            },
+            TextEdit {
+                new_text: "",
+                range: Range {
+                    start: Position {
+                        line: 0,
+                        column: 51,
+                    },
+                    end: Position {
+                        line: 0,
+                        column: 52,
+                    },
+                },
+            },
+        ]
+    "#]]
+    .assert_debug_eq(&compilation.code_actions_for(0, Encoding::Utf8));
+}
+
+#[test]
+fn code_actions_for_diagnostic_without_fix_is_empty() {
+    let compilation = Compilation::new(
+        &[(
+            "<source>".into(),
+            "namespace Test { function Foo() : Int { 1 / 0 } }".into(),
+        )],
+        PackageType::Lib,
+        Profile::Unrestricted,
+        LanguageFeatures::default(),
+        &[],
+    );
+    assert_eq!(compilation.errors.len(), 1, "{:?}", compilation.errors);
+    assert!(compilation.code_actions_for(0, Encoding::Utf8).is_empty());
+    assert!(compilation.code_actions_for(1, Encoding::Utf8).is_empty());
+}
+
+/// Hovers over the first occurrence of `target` in `source`.
+fn check_hover_at(source: &str, target: &str, expect: &Expect) {
+    let compilation = compile_with_profile(source, Profile::Unrestricted);
+    let offset = source.find(target).expect("source should contain target");
+    let line = &source[..offset];
+    let position = Position {
+        line: u32::try_from(line.matches('\n').count()).expect("line should fit in u32"),
+        column: u32::try_from(offset - line.rfind('\n').map_or(0, |i| i + 1))
+            .expect("column should fit in u32"),
+    };
+    expect.assert_debug_eq(&compilation.hover_at("<source>", position, Encoding::Utf8));
+}
+
+#[test]
+fn hover_at_local_variable() {
+    check_hover_at(
+        indoc! {r#"
+        namespace Test {
+            function Main() : Int {
+                let foo = 42;
+                foo
+            }
+        }
+    "#},
+        "foo\n",
+        &expect![[r#"
+            Some(
+                HoverInfo {
+                    label: Some(
+                        "local",
+                    ),
+                    namespace: None,
+                    signature: "foo : Int",
+                    doc: None,
+                    span: Range {
+                        start: Position {
+                            line: 3,
+                            column: 8,
+                        },
+                        end: Position {
+                            line: 3,
+                            column: 11,
+                        },
+                    },
+                },
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn hover_at_std_function_call() {
+    check_hover_at(
+        indoc! {r#"
+        namespace Test {
+            function Main() : Int {
+                Microsoft.Quantum.Math.AbsI(-1)
+            }
+        }
+    "#},
+        "AbsI",
+        &expect![[r#"
+            Some(
+                HoverInfo {
+                    label: None,
+                    namespace: Some(
+                        "Microsoft.Quantum.Math",
+                    ),
+                    signature: "function AbsI(a : Int) : Int",
+                    doc: Some(
+                        "Returns the absolute value of an integer.",
+                    ),
+                    span: Range {
+                        start: Position {
+                            line: 2,
+                            column: 8,
+                        },
+                        end: Position {
+                            line: 2,
+                            column: 35,
+                        },
+                    },
+                },
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn hover_at_type_name() {
+    check_hover_at(
+        indoc! {r#"
+        namespace Test {
+            newtype Pair = (First : Int, Second : Int);
+            function Main() : Int {
+                let pair = Pair(1, 2);
+                pair::First
+            }
+        }
+    "#},
+        "Pair(1",
+        &expect![[r#"
+            Some(
+                HoverInfo {
+                    label: None,
+                    namespace: None,
+                    signature: "newtype Pair = (First: Int, Second: Int)",
+                    doc: None,
+                    span: Range {
+                        start: Position {
+                            line: 3,
+                            column: 19,
+                        },
+                        end: Position {
+                            line: 3,
+                            column: 23,
+                        },
+                    },
+                },
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn hover_at_whitespace_and_comment_is_none() {
+    let source = indoc! {r#"
+        namespace Test {
+            // a comment
+            function Main() : Int {
+                42
+            }
+        }
+    "#};
+    let compilation = compile_with_profile(source, Profile::Unrestricted);
+    for position in [
+        Position { line: 1, column: 9 },
+        Position { line: 3, column: 2 },
+    ] {
+        assert!(
+            compilation
+                .hover_at("<source>", position, Encoding::Utf8)
+                .is_none(),
+            "{position:?}"
+        );
+    }
+}
+
+/// Const-evaluates the first occurrence of `target` in `source`.
+fn const_eval_at(source: &str, target: &str) -> Option<Value> {
+    let compilation = Compilation::new(
+        &[("<source>".into(), source.into())],
+        PackageType::Lib,
+        Profile::Unrestricted,
+        LanguageFeatures::default(),
+        &[],
+    );
+    assert!(compilation.errors.is_empty(), "{:?}", compilation.errors);
+    let lo = u32::try_from(source.find(target).expect("source should contain target"))
+        .expect("offset should fit in u32");
+    let hi = lo + u32::try_from(target.len()).expect("length should fit in u32");
+    compilation.try_const_eval("<source>", Span { lo, hi })
+}
+
+#[test]
+fn try_const_eval_folds_arithmetic() {
+    let source = indoc! {r#"
+        namespace Test {
+            open Microsoft.Quantum.Convert;
+            open Microsoft.Quantum.Math;
+            function Main() : Double {
+                let turns = 3;
+                IntAsDouble(turns * 4 + 1) + PI() * 2.0
+            }
+        }
+    "#};
+    assert_eq!(const_eval_at(source, "turns * 4 + 1"), Some(Value::Int(13)));
+    assert_eq!(
+        const_eval_at(source, "IntAsDouble(turns * 4 + 1) + PI() * 2.0"),
+        Some(Value::Double(13.0 + 2.0 * std::f64::consts::PI))
+    );
+    assert_eq!(
+        const_eval_at(source, "PI() * 2.0"),
+        Some(Value::Double(2.0 * std::f64::consts::PI))
+    );
+}
+
+#[test]
+fn try_const_eval_rejects_qubit_allocation() {
+    let source = indoc! {r#"
+        namespace Test {
+            operation Main() : Result {
+                let r = { use q = Qubit(); MResetZ(q) };
+                r
+            }
+        }
+    "#};
+    assert_eq!(
+        const_eval_at(source, "{ use q = Qubit(); MResetZ(q) }"),
+        None
+    );
+    assert_eq!(const_eval_at(source, "MResetZ(q)"), None);
+}
+
+#[test]
+fn try_const_eval_rejects_runtime_values_and_effects() {
+    let source = indoc! {r#"
+        namespace Test {
+            function Count(n : Int) : Int {
+                mutable total = 0;
+                for i in 1..n {
+                    set total += i;
+                }
+                total
+            }
+            function Main(x : Int) : Int {
+                mutable y = 1;
+                let logged = { Message("side effect"); 2 };
+                x + y + Count(3) + logged
+            }
+        }
+    "#};
+    // A parameter and a mutable variable depend on runtime values.
+    assert_eq!(const_eval_at(source, "x + y"), None);
+    // Loops are not evaluated, even when they would terminate.
+    assert_eq!(const_eval_at(source, "Count(3)"), None);
+    // Output is an effect.
+    assert_eq!(
+        const_eval_at(source, "{ Message(\"side effect\"); 2 }"),
+        None
+    );
+    // There is no expression with this exact span.
+    assert_eq!(const_eval_at(source, "+ y"), None);
+}
+
+#[test]
+fn summary_counts_diagnostics_by_severity() {
+    let compilation = Compilation::new(
+        &[
+            (
+                "a.qs".into(),
+                "namespace A {\n    function F() : Int {\n        let x : Int = true;\n        1 / 0\n    }\n}\n"
+                    .into(),
+            ),
+            (
+                "b.qs".into(),
+                "namespace B {\n    function G() : Unit {\n        let y = 1;;\n    }\n}\n".into(),
+            ),
+        ],
+        PackageType::Lib,
+        Profile::Base,
+        LanguageFeatures::default(),
+        &[],
+    );
+
+    let summary = compilation.summary();
+    assert_eq!(
+        summary,
+        CompilationSummary {
+            errors: 1,
+            warnings: 2,
+            lint_errors: 0,
+            lint_warnings: 2,
+            sources: 2,
+            lines: 11,
+            target_profile: "Base",
+            codegen_ready: false,
+        }
+    );
+    assert_eq!(
+        serde_json::to_string(&summary).expect("summary should serialize"),
+        r#"{"errors":1,"warnings":2,"lintErrors":0,"lintWarnings":2,"sources":2,"lines":11,"targetProfile":"Base","codegenReady":false}"#
+    );
+}
+
+#[test]
+fn summary_of_clean_compilation_is_codegen_ready() {
+    let compilation = Compilation::new(
+        &[(
+            "<source>".into(),
+            "namespace Test { @EntryPoint() operation Main() : Result { use q = Qubit(); M(q) } }"
+                .into(),
+        )],
+        PackageType::Exe,
+        Profile::Base,
+        LanguageFeatures::default(),
+        &[],
+    );
+
+    let summary = compilation.summary();
+    assert_eq!((summary.errors, summary.warnings), (0, 0));
+    assert_eq!((summary.sources, summary.lines), (1, 1));
+    assert!(summary.codegen_ready);
+}
+
+#[test]
+fn into_incremental_continues_notebook_compilation() {
+    let compilation = Compilation::new_notebook(
+        [
+            ("cell1".into(), "function Answer() : Int { 42 }".into()),
+            ("cell2".into(), "let offset = Answer() - 40;".into()),
+        ]
+        .into_iter(),
+        Profile::Unrestricted,
+        LanguageFeatures::default(),
+        &[],
+    );
+    assert!(compilation.errors.is_empty(), "{:?}", compilation.errors);
+    let cell_count = compilation.user_unit().sources.iter().count();
+
+    let mut compiler = compilation
+        .into_incremental()
+        .expect("notebook compilation should have an incremental compiler");
+    // The new fragment refers to a callable and a binding from earlier cells.
+    let increment = compiler
+        .compile_fragments_fail_fast("cell3", "let total = Answer() + offset;")
+        .expect("fragment referring to earlier cells should compile");
+    compiler.update(increment);
+
+    let unit = compiler
+        .package_store()
+        .get(compiler.package_id())
+        .expect("notebook package should be in the store");
+    assert_eq!(unit.sources.iter().count(), cell_count + 1);
+    assert!(unit.sources.find_by_name("cell3").is_some());
+}
+
+#[test]
+fn into_incremental_is_none_for_projects() {
+    let compilation = Compilation::new(
+        &[(
+            "<source>".into(),
+            "namespace Test { function F() : Unit {} }".into(),
+        )],
+        PackageType::Lib,
+        Profile::Unrestricted,
+        LanguageFeatures::default(),
+        &[],
+    );
+    assert!(compilation.into_incremental().is_none());
+}
+
+#[test]
+fn diagnostic_codes_do_not_depend_on_message_or_level() {
+    let source = indoc! {"
+        namespace Test {
+            function F() : Unit {
+                let x : Int = true;
+                let y : Double = \"text\";
+                let z = 1 / 0;
+            }
+        }
+    "};
+    let codes = |lints_config: &[qsc_linter::LintConfig]| {
+        let compilation = Compilation::new(
+            &[("<source>".into(), source.into())],
+            PackageType::Lib,
+            Profile::Unrestricted,
+            LanguageFeatures::default(),
+            lints_config,
+        );
+        compilation
+            .errors
+            .iter()
+            .map(|error| {
+                (
+                    error.diagnostic_code().map(str::to_string),
+                    format!("{error:?}"),
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let warnings = codes(&[]);
+    let [(x_code, x_details), (y_code, y_details), (lint_code, _)] = warnings.as_slice() else {
+        panic!("expected two type errors and a lint, got {warnings:?}");
+    };
+    // The errors mention different types, but share the code of the error kind.
+    assert_ne!(x_details, y_details);
+    assert_eq!(x_code.as_deref(), Some("Qsc.TypeCk.TyMismatch"));
+    assert_eq!(y_code.as_deref(), Some("Qsc.TypeCk.TyMismatch"));
+    assert_eq!(lint_code.as_deref(), Some("Qsc.Lint.DivisionByZero"));
+
+    // Reporting the lint as an error does not change its code.
+    let lints_config: Vec<qsc_linter::LintConfig> =
+        serde_json::from_str(r#"[{ "lint": "divisionByZero", "level": "error" }]"#)
+            .expect("lint config should deserialize");
+    let errors = codes(&lints_config);
+    assert_eq!(
+        errors.last().and_then(|(code, _)| code.as_deref()),
+        Some("Qsc.Lint.DivisionByZero")
+    );
+}
+
+fn source_with_many_errors(namespace: &str, count: usize) -> String {
+    let mut source = String::new();
+    let _ = writeln!(
+        source,
+        "namespace {namespace} {{\n    function F() : Unit {{"
+    );
+    for i in 0..count {
+        // Characters that take a different number of code units in utf-8 and utf-16
+        // make the columns differ between encodings.
+        let _ = writeln!(
+            source,
+            "        // 𝑓(𝑥⃗) Σ {i}\n        let x{i} : Int = true;"
+        );
+    }
+    source.push_str("    }\n}\n");
+    source
+}
+
+#[test]
+fn resolve_all_diagnostics_matches_per_diagnostic_resolution() {
+    let compilation = Compilation::new(
+        &[
+            ("a.qs".into(), source_with_many_errors("A", 500).into()),
+            ("b.qs".into(), source_with_many_errors("B", 300).into()),
+        ],
+        PackageType::Lib,
+        Profile::Unrestricted,
+        LanguageFeatures::default(),
+        &[],
+    );
+    assert_eq!(compilation.errors.len(), 800);
+
+    let sources = &compilation.user_unit().sources;
+    for encoding in [Encoding::Utf8, Encoding::Utf16] {
+        let expected: Vec<ResolvedDiagnostic> = compilation
+            .errors
+            .iter()
+            .map(|error| {
+                let label = miette::Diagnostic::labels(error)
+                    .and_then(|mut labels| labels.next())
+                    .expect("error should have a label");
+                let lo = u32::try_from(label.offset()).expect("offset should fit into u32");
+                let hi = lo + u32::try_from(label.len()).expect("length should fit into u32");
+                let source = sources
+                    .find_by_offset(lo)
+                    .expect("source should exist for offset");
+                ResolvedDiagnostic {
+                    message: error.to_string(),
+                    location: Some((
+                        source.name.clone(),
+                        into_range(encoding, Span { lo, hi }, sources),
+                    )),
+                }
+            })
+            .collect();
+
+        assert_eq!(compilation.resolve_all_diagnostics(encoding), expected);
+    }
+}
+
+#[test]
+fn custom_std_sources_replace_bundled_std() {
+    let std_sources = SourceMap::new(
+        [(
+            "custom_std.qs".into(),
+            "namespace Custom.Std { function Answer() : Int { 42 } }".into(),
+        )],
+        None,
+    );
+    let mut compilation = Compilation::new_with_std_sources(
+        Some(std_sources),
+        &[(
+            "<source>".into(),
+            "namespace Test { function Main() : Int { Custom.Std.Answer() } }".into(),
+        )],
+        PackageType::Lib,
+        Profile::Unrestricted,
+        LanguageFeatures::default(),
+        &[],
+    );
+    assert!(compilation.errors.is_empty(), "{:?}", compilation.errors);
+
+    // The bundled std is not a dependency, and recompiling keeps the custom one.
+    compilation.update_source(
+        "<source>",
+        "namespace Test { function Main() : Double { Custom.Std.Answer(); Microsoft.Quantum.Math.PI() } }"
+            .into(),
+    );
+    let [error] = compilation.errors.as_slice() else {
+        panic!("expected a single error, got {:?}", compilation.errors);
+    };
+    assert!(
+        format!("{error:?}").contains(r#"NotFound("PI""#),
+        "{error:?}"
+    );
+}
+
+#[test]
+fn no_std_sources_uses_bundled_std() {
+    let compilation = Compilation::new_with_std_sources(
+        None,
+        &[(
+            "<source>".into(),
+            "namespace Test { function Main() : Double { Microsoft.Quantum.Math.PI() } }".into(),
+        )],
+        PackageType::Lib,
+        Profile::Unrestricted,
+        LanguageFeatures::default(),
+        &[],
+    );
+    assert!(compilation.errors.is_empty(), "{:?}", compilation.errors);
+}
+
+#[test]
+fn concurrent_reads_share_one_compilation() {
+    let source = indoc! {r#"
+        namespace Test {
+            function Square(x : Int) : Int { x * x }
+            function Main() : Int {
+                let side = 7;
+                Square(side) + 1
+            }
+        }
+    "#};
+    let compilation = Arc::new(Compilation::new(
+        &[("<source>".into(), source.into())],
+        PackageType::Lib,
+        Profile::Unrestricted,
+        LanguageFeatures::default(),
+        &[],
+    ));
+    assert!(compilation.errors.is_empty(), "{:?}", compilation.errors);
+    assert!(
+        compilation.fir.get().is_none(),
+        "FIR should be lowered lazily by the readers"
+    );
+
+    let position = Position { line: 4, column: 9 };
+    let lo = u32::try_from(
+        source
+            .find("Square(side) + 1")
+            .expect("source should contain call"),
+    )
+    .expect("offset should fit in u32");
+    let span = Span {
+        lo,
+        hi: lo + u32::try_from("Square(side) + 1".len()).expect("length should fit in u32"),
+    };
+
+    let results = std::thread::scope(|scope| {
+        let readers = (0..8)
+            .map(|_| {
+                let compilation = Arc::clone(&compilation);
+                scope.spawn(move || {
+                    let hover = compilation.hover_at("<source>", position, Encoding::Utf8);
+                    let callables = compilation.callables_by_line_count().len();
+                    // Values are not `Send`, so only their text leaves the thread.
+                    let value = compilation
+                        .try_const_eval("<source>", span)
+                        .map(|value| value.to_string());
+                    (hover, callables, value)
+                })
+            })
+            .collect::<Vec<_>>();
+        readers
+            .into_iter()
+            .map(|reader| reader.join().expect("reader thread should not panic"))
+            .collect::<Vec<_>>()
+    });
+
+    let (hover, callables, value) = &results[0];
+    assert!(
+        hover
+            .as_ref()
+            .is_some_and(|hover| hover.signature.contains("Square")),
+        "{hover:?}"
+    );
+    assert_eq!(*callables, 2);
+    assert_eq!(value.as_deref(), Some("50"));
+    assert!(results.iter().all(|result| result == &results[0]));
+}
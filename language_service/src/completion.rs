@@ -13,7 +13,7 @@ use qsc::hir::{ItemKind, Package, PackageId, Visibility};
 use qsc::line_column::{Encoding, Position, Range};
 use qsc::resolve::{Local, LocalKind};
 use rustc_hash::FxHashSet;
-use std::rc::Rc;
+use std::sync::Arc;
 
 const PRELUDE: [&str; 3] = [
     "Microsoft.Quantum.Canon",
@@ -71,7 +71,7 @@ pub(crate) fn get_completions(
     // The PRELUDE namespaces are always implicitly opened.
     context_finder
         .opens
-        .extend(PRELUDE.into_iter().map(|ns| (Rc::from(ns), None)));
+        .extend(PRELUDE.into_iter().map(|ns| (Arc::from(ns), None)));
 
     let mut builder = CompletionListBuilder::new();
 
@@ -272,9 +272,9 @@ impl CompletionListBuilder {
     fn push_globals(
         &mut self,
         compilation: &Compilation,
-        opens: &[(Rc<str>, Option<Rc<str>>)],
+        opens: &[(Arc<str>, Option<Arc<str>>)],
         insert_open_range: Option<Range>,
-        current_namespace_name: &Option<Rc<str>>,
+        current_namespace_name: &Option<Arc<str>>,
         indent: &String,
     ) {
         let core = &compilation
@@ -394,9 +394,9 @@ impl CompletionListBuilder {
     fn get_callables<'a>(
         compilation: &'a Compilation,
         package_id: PackageId,
-        opens: &'a [(Rc<str>, Option<Rc<str>>)],
+        opens: &'a [(Arc<str>, Option<Arc<str>>)],
         insert_open_at: Option<Range>,
-        current_namespace_name: Option<Rc<str>>,
+        current_namespace_name: Option<Arc<str>>,
         indent: &'a String,
     ) -> impl Iterator<Item = (CompletionItem, u32)> + 'a {
         let package = &compilation
@@ -441,11 +441,11 @@ impl CompletionListBuilder {
                                 // Everything that starts with a __ goes last in the list
                                 let sort_group = u32::from(name.starts_with("__"));
                                 let mut additional_edits = vec![];
-                                let mut qualification: Option<Rc<str>> = None;
+                                let mut qualification: Option<Arc<str>> = None;
                                 match &current_namespace_name {
                                     Some(curr_ns) if *curr_ns == namespace.name => {}
                                     _ => {
-                                        // open is an option of option of Rc<str>
+                                        // open is an option of option of Arc<str>
                                         // the first option tells if it found an open with the namespace name
                                         // the second, nested option tells if that open has an alias
                                         let open = opens.iter().find_map(|(name, alias)| {
@@ -610,9 +610,9 @@ fn local_completion(
 struct ContextFinder {
     offset: u32,
     context: Context,
-    opens: Vec<(Rc<str>, Option<Rc<str>>)>,
+    opens: Vec<(Arc<str>, Option<Arc<str>>)>,
     start_of_namespace: Option<u32>,
-    current_namespace_name: Option<Rc<str>>,
+    current_namespace_name: Option<Arc<str>>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -13,7 +13,7 @@ use qsc::display::{parse_doc_for_param, parse_doc_for_summary, CodeDisplay};
 use qsc::line_column::{Encoding, Position, Range};
 use qsc::{ast, hir, Span};
 use std::fmt::Display;
-use std::rc::Rc;
+use std::sync::Arc;
 
 pub(crate) fn get_hover(
     compilation: &Compilation,
@@ -21,6 +21,20 @@ pub(crate) fn get_hover(
     position: Position,
     position_encoding: Encoding,
 ) -> Option<Hover> {
+    get_hover_info(compilation, source_name, position, position_encoding).map(|info| Hover {
+        contents: info.to_markdown(),
+        span: info.span,
+    })
+}
+
+/// Gets the structured hover information for the symbol at `position`,
+/// or `None` if there is no symbol there.
+pub(crate) fn get_hover_info(
+    compilation: &Compilation,
+    source_name: &str,
+    position: Position,
+    position_encoding: Encoding,
+) -> Option<HoverInfo> {
     let offset =
         compilation.source_position_to_package_offset(source_name, position, position_encoding);
     let user_ast_package = &compilation.user_unit().ast.package;
@@ -37,6 +51,39 @@ pub(crate) fn get_hover(
     hover_visitor.hover
 }
 
+/// The hover information for a symbol, before it is rendered as markdown.
+#[derive(Debug, PartialEq)]
+pub(crate) struct HoverInfo {
+    /// What the symbol is relative to its callable, e.g. "local" or
+    /// "parameter of `Foo`". `None` for items, fields and type definitions.
+    pub label: Option<String>,
+    /// The namespace containing the symbol, for callables.
+    pub namespace: Option<Arc<str>>,
+    /// The rendered declaration or type signature of the symbol.
+    pub signature: String,
+    /// The documentation attached to the symbol, if any.
+    pub doc: Option<String>,
+    /// The range of the symbol under the cursor.
+    pub span: Range,
+}
+
+impl HoverInfo {
+    fn to_markdown(&self) -> String {
+        let code = match &self.namespace {
+            Some(namespace) => format!("{namespace}\n{}", self.signature),
+            None => self.signature.clone(),
+        };
+        let mut code = markdown_fenced_block(code);
+        if let Some(label) = &self.label {
+            code = format!("{label}\n{code}");
+        }
+        match &self.doc {
+            Some(doc) => format!("{code}---\n{doc}\n"),
+            None => code,
+        }
+    }
+}
+
 enum LocalKind {
     Param,
     TypeParam,
@@ -45,7 +92,7 @@ enum LocalKind {
 }
 struct HoverGenerator<'a> {
     position_encoding: Encoding,
-    hover: Option<Hover>,
+    hover: Option<HoverInfo>,
     display: CodeDisplay<'a>,
     compilation: &'a Compilation,
 }
@@ -57,15 +104,12 @@ impl<'a> Handler<'a> for HoverGenerator<'a> {
         name: &'a ast::Ident,
         decl: &'a ast::CallableDecl,
     ) {
-        let contents = display_callable(
+        self.hover = Some(self.callable(
             &context.current_item_doc,
             &context.current_namespace,
             self.display.ast_callable_decl(decl),
-        );
-        self.hover = Some(Hover {
-            contents,
-            span: self.range(name.span),
-        });
+            name.span,
+        ));
     }
 
     fn at_callable_ref(
@@ -80,19 +124,19 @@ impl<'a> Handler<'a> for HoverGenerator<'a> {
             .parent
             .and_then(|parent_id| package.items.get(parent_id))
             .map_or_else(
-                || Rc::from(""),
+                || Arc::from(""),
                 |parent| match &parent.kind {
                     qsc::hir::ItemKind::Namespace(namespace, _) => namespace.name.clone(),
-                    _ => Rc::from(""),
+                    _ => Arc::from(""),
                 },
             );
 
-        let contents = display_callable(&item.doc, &ns, self.display.hir_callable_decl(decl));
-
-        self.hover = Some(Hover {
-            contents,
-            span: self.range(path.span),
-        });
+        self.hover = Some(self.callable(
+            &item.doc,
+            &ns,
+            self.display.hir_callable_decl(decl),
+            path.span,
+        ));
     }
 
     fn at_type_param_def(
@@ -101,23 +145,19 @@ impl<'a> Handler<'a> for HoverGenerator<'a> {
         def_name: &'a ast::Ident,
         _: hir::ty::ParamId,
     ) {
-        let code = markdown_fenced_block(def_name.name.clone());
         let callable_name = &context
             .current_callable
             .expect("type params should only exist in callables")
             .name
             .name;
-        let contents = display_local(
+        self.hover = Some(self.local(
             &LocalKind::TypeParam,
-            &code,
+            def_name.name.to_string(),
             &def_name.name,
             callable_name,
             &context.current_item_doc,
-        );
-        self.hover = Some(Hover {
-            contents,
-            span: self.range(def_name.span),
-        });
+            def_name.span,
+        ));
     }
 
     fn at_type_param_ref(
@@ -127,31 +167,26 @@ impl<'a> Handler<'a> for HoverGenerator<'a> {
         _: hir::ty::ParamId,
         _: &'a ast::Ident,
     ) {
-        let code = markdown_fenced_block(reference.name.clone());
         let callable_name = &context
             .current_callable
             .expect("type params should only exist in callables")
             .name
             .name;
-        let contents = display_local(
+        self.hover = Some(self.local(
             &LocalKind::TypeParam,
-            &code,
+            reference.name.to_string(),
             &reference.name,
             callable_name,
             &context.current_item_doc,
-        );
-        self.hover = Some(Hover {
-            contents,
-            span: self.range(reference.span),
-        });
+            reference.span,
+        ));
     }
 
     fn at_new_type_def(&mut self, type_name: &'a ast::Ident, def: &'a ast::TyDef) {
-        let contents = markdown_fenced_block(self.display.ident_ty_def(type_name, def));
-        self.hover = Some(Hover {
-            contents,
-            span: self.range(type_name.span),
-        });
+        self.hover = Some(self.code(
+            self.display.ident_ty_def(type_name, def).to_string(),
+            type_name.span,
+        ));
     }
 
     fn at_new_type_ref(
@@ -162,12 +197,7 @@ impl<'a> Handler<'a> for HoverGenerator<'a> {
         _: &'a hir::Ident,
         udt: &'a hir::ty::Udt,
     ) {
-        let contents = markdown_fenced_block(self.display.hir_udt(udt));
-
-        self.hover = Some(Hover {
-            contents,
-            span: self.range(path.span),
-        });
+        self.hover = Some(self.code(self.display.hir_udt(udt).to_string(), path.span));
     }
 
     fn at_field_def(
@@ -176,11 +206,10 @@ impl<'a> Handler<'a> for HoverGenerator<'a> {
         field_name: &'a ast::Ident,
         ty: &'a ast::Ty,
     ) {
-        let contents = markdown_fenced_block(self.display.ident_ty(field_name, ty));
-        self.hover = Some(Hover {
-            contents,
-            span: self.range(field_name.span),
-        });
+        self.hover = Some(self.code(
+            self.display.ident_ty(field_name, ty).to_string(),
+            field_name.span,
+        ));
     }
 
     fn at_field_ref(
@@ -190,11 +219,14 @@ impl<'a> Handler<'a> for HoverGenerator<'a> {
         _: &'_ hir::ItemId,
         _: &'a hir::ty::UdtField,
     ) {
-        let contents = markdown_fenced_block(self.display.name_ty_id(&field_ref.name, *expr_id));
-        self.hover = Some(Hover {
-            contents,
-            span: self.range(field_ref.span),
-        });
+        self.hover = Some(
+            self.code(
+                self.display
+                    .name_ty_id(&field_ref.name, *expr_id)
+                    .to_string(),
+                field_ref.span,
+            ),
+        );
     }
 
     fn at_local_def(
@@ -203,7 +235,6 @@ impl<'a> Handler<'a> for HoverGenerator<'a> {
         ident: &'a ast::Ident,
         pat: &'a ast::Pat,
     ) {
-        let code = markdown_fenced_block(self.display.name_ty_id(&ident.name, pat.id));
         let kind = if context.in_params {
             LocalKind::Param
         } else if context.in_lambda_params {
@@ -216,17 +247,14 @@ impl<'a> Handler<'a> for HoverGenerator<'a> {
             .expect("locals should only exist in callables")
             .name
             .name;
-        let contents = display_local(
+        self.hover = Some(self.local(
             &kind,
-            &code,
+            self.display.name_ty_id(&ident.name, pat.id).to_string(),
             &ident.name,
             callable_name,
             &context.current_item_doc,
-        );
-        self.hover = Some(Hover {
-            contents,
-            span: self.range(ident.span),
-        });
+            ident.span,
+        ));
     }
 
     fn at_local_ref(
@@ -242,7 +270,6 @@ impl<'a> Handler<'a> for HoverGenerator<'a> {
             .expect("locals should only exist in callables")
             .name
             .name;
-        let code = markdown_fenced_block(self.display.name_ty_id(local_name, *node_id));
         let kind = if is_param(&curr_callable_to_params(context.current_callable), *node_id) {
             LocalKind::Param
         } else if is_param(&context.lambda_params, *node_id) {
@@ -250,17 +277,14 @@ impl<'a> Handler<'a> for HoverGenerator<'a> {
         } else {
             LocalKind::Local
         };
-        let contents = display_local(
+        self.hover = Some(self.local(
             &kind,
-            &code,
+            self.display.name_ty_id(local_name, *node_id).to_string(),
             local_name,
             callable_name,
             &context.current_item_doc,
-        );
-        self.hover = Some(Hover {
-            contents,
-            span: self.range(path.span),
-        });
+            path.span,
+        ));
     }
 }
 
@@ -272,6 +296,62 @@ impl HoverGenerator<'_> {
             &self.compilation.user_unit().sources,
         )
     }
+
+    fn code(&self, signature: String, span: Span) -> HoverInfo {
+        HoverInfo {
+            label: None,
+            namespace: None,
+            signature,
+            doc: None,
+            span: self.range(span),
+        }
+    }
+
+    fn callable(
+        &self,
+        doc: &str,
+        namespace: &Arc<str>,
+        code: impl Display,
+        span: Span,
+    ) -> HoverInfo {
+        HoverInfo {
+            label: None,
+            namespace: (!namespace.is_empty()).then(|| namespace.clone()),
+            signature: code.to_string(),
+            doc: non_empty(parse_doc_for_summary(doc)),
+            span: self.range(span),
+        }
+    }
+
+    fn local(
+        &self,
+        kind: &LocalKind,
+        signature: String,
+        local_name: &str,
+        callable_name: &str,
+        callable_doc: &str,
+        span: Span,
+    ) -> HoverInfo {
+        let (label, doc) = match kind {
+            LocalKind::Param => (
+                format!("parameter of `{callable_name}`"),
+                non_empty(parse_doc_for_param(callable_doc, local_name)),
+            ),
+            LocalKind::TypeParam => (
+                format!("type parameter of `{callable_name}`"),
+                non_empty(parse_doc_for_param(callable_doc, local_name)),
+            ),
+            LocalKind::LambdaParam => ("lambda parameter".to_string(), None),
+            LocalKind::Local => ("local".to_string(), None),
+        };
+        HoverInfo {
+            label: Some(label),
+            namespace: None,
+            signature,
+            doc,
+            span: self.range(span),
+        }
+    }
 }
 
 fn curr_callable_to_params(curr_callable: Option<&ast::CallableDecl>) -> Vec<&ast::Pat> {
@@ -307,51 +387,8 @@ fn is_param(param_pats: &[&ast::Pat], node_id: ast::NodeId) -> bool {
     param_pats.iter().any(|pat| find_in_pat(pat, node_id))
 }
 
-fn display_local(
-    param_kind: &LocalKind,
-    markdown: &String,
-    local_name: &str,
-    callable_name: &str,
-    callable_doc: &str,
-) -> String {
-    match param_kind {
-        LocalKind::Param => {
-            let param_doc = parse_doc_for_param(callable_doc, local_name);
-            with_doc(
-                &param_doc,
-                format!("parameter of `{callable_name}`\n{markdown}",),
-            )
-        }
-        LocalKind::TypeParam => {
-            let param_doc = parse_doc_for_param(callable_doc, local_name);
-            with_doc(
-                &param_doc,
-                format!("type parameter of `{callable_name}`\n{markdown}",),
-            )
-        }
-        LocalKind::LambdaParam => format!("lambda parameter\n{markdown}"),
-        LocalKind::Local => format!("local\n{markdown}"),
-    }
-}
-
-fn display_callable(doc: &str, namespace: &str, code: impl Display) -> String {
-    let summary = parse_doc_for_summary(doc);
-
-    let mut code = if namespace.is_empty() {
-        code.to_string()
-    } else {
-        format!("{namespace}\n{code}")
-    };
-    code = markdown_fenced_block(code);
-    with_doc(&summary, code)
-}
-
-fn with_doc(doc: &String, code: impl Display) -> String {
-    if doc.is_empty() {
-        code.to_string()
-    } else {
-        format!("{code}---\n{doc}\n")
-    }
+fn non_empty(doc: String) -> Option<String> {
+    (!doc.is_empty()).then_some(doc)
 }
 
 fn markdown_fenced_block(code: impl Display) -> String {
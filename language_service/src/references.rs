@@ -4,7 +4,7 @@
 #[cfg(test)]
 mod tests;
 
-use std::rc::Rc;
+use std::sync::Arc;
 
 use crate::compilation::Compilation;
 use crate::name_locator::{Handler, Locator, LocatorContext};
@@ -217,7 +217,7 @@ impl<'a> ReferenceFinder<'a> {
         locations
     }
 
-    pub fn for_field(&self, ty_item_id: &hir::ItemId, field_name: Rc<str>) -> Vec<Location> {
+    pub fn for_field(&self, ty_item_id: &hir::ItemId, field_name: Arc<str>) -> Vec<Location> {
         let mut locations = vec![];
 
         let (ty_def, _, resolved_ty_item_id) = self
@@ -346,7 +346,7 @@ impl<'a> FindItemRefs<'a> {
 
 struct FindFieldRefs<'a> {
     ty_item_id: &'a hir::ItemId,
-    field_name: Rc<str>,
+    field_name: Arc<str>,
     compilation: &'a Compilation,
     locations: Vec<Span>,
 }
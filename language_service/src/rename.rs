@@ -43,12 +43,24 @@ pub(crate) fn get_rename(
 ) -> Vec<Location> {
     let offset =
         compilation.source_position_to_package_offset(source_name, position, position_encoding);
+    let (locations, _) = get_rename_at_offset(compilation, offset, position_encoding);
+    locations
+}
+
+/// Returns the locations to edit to rename the symbol at the given package offset,
+/// and whether the symbol is defined outside the user package, in which case
+/// there are no locations.
+pub(crate) fn get_rename_at_offset(
+    compilation: &Compilation,
+    offset: u32,
+    position_encoding: Encoding,
+) -> (Vec<Location>, bool) {
     let user_ast_package = &compilation.user_unit().ast.package;
 
     let mut rename = Rename::new(position_encoding, compilation, false);
     let mut locator = Locator::new(&mut rename, offset, compilation);
     locator.visit_package(user_ast_package);
-    rename.locations
+    (rename.locations, rename.is_library_symbol)
 }
 
 fn remove_leading_quote_from_type_param_span(span: Span) -> Span {
@@ -72,6 +84,7 @@ struct Rename<'a> {
     locations: Vec<Location>,
     is_prepare: bool,
     prepare: Option<(Span, String)>,
+    is_library_symbol: bool,
 }
 
 impl<'a> Rename<'a> {
@@ -82,6 +95,7 @@ impl<'a> Rename<'a> {
             locations: vec![],
             is_prepare,
             prepare: None,
+            is_library_symbol: false,
         }
     }
 
@@ -94,6 +108,8 @@ impl<'a> Rename<'a> {
             } else {
                 self.locations = self.reference_finder.for_item(item_id);
             }
+        } else {
+            self.is_library_symbol = true;
         }
     }
 
@@ -108,6 +124,8 @@ impl<'a> Rename<'a> {
                     .reference_finder
                     .for_field(item_id, ast_name.name.clone());
             }
+        } else {
+            self.is_library_symbol = true;
         }
     }
 
@@ -331,24 +331,34 @@ async fn rca_errors_are_reported_when_compilation_succeeds() {
                     ),
                     [
                         Pass(
-                            CapabilitiesCk(
-                                UseOfDynamicDouble(
-                                    Span {
-                                        lo: 106,
-                                        hi: 117,
-                                    },
+                            PassError {
+                                error: CapabilitiesCk(
+                                    UseOfDynamicDouble(
+                                        Span {
+                                            lo: 106,
+                                            hi: 117,
+                                        },
+                                    ),
                                 ),
-                            ),
+                                required_profile: Some(
+                                    Unrestricted,
+                                ),
+                            },
                         ),
                         Pass(
-                            CapabilitiesCk(
-                                UseOfDynamicDouble(
-                                    Span {
-                                        lo: 121,
-                                        hi: 122,
-                                    },
+                            PassError {
+                                error: CapabilitiesCk(
+                                    UseOfDynamicDouble(
+                                        Span {
+                                            lo: 121,
+                                            hi: 122,
+                                        },
+                                    ),
                                 ),
-                            ),
+                                required_profile: Some(
+                                    Unrestricted,
+                                ),
+                            },
                         ),
                     ],
                 ),
@@ -398,9 +408,12 @@ async fn package_type_update_causes_error() {
                     ),
                     [
                         Pass(
-                            EntryPoint(
-                                NotFound,
-                            ),
+                            PassError {
+                                error: EntryPoint(
+                                    NotFound,
+                                ),
+                                required_profile: None,
+                            },
                         ),
                     ],
                 ),
@@ -438,34 +451,49 @@ async fn target_profile_update_fixes_error() {
                     ),
                     [
                         Pass(
-                            BaseProfCk(
-                                ResultComparison(
-                                    Span {
-                                        lo: 45,
-                                        hi: 57,
-                                    },
+                            PassError {
+                                error: BaseProfCk(
+                                    ResultComparison(
+                                        Span {
+                                            lo: 45,
+                                            hi: 57,
+                                        },
+                                    ),
                                 ),
-                            ),
+                                required_profile: Some(
+                                    Quantinuum,
+                                ),
+                            },
                         ),
                         Pass(
-                            BaseProfCk(
-                                ResultLiteral(
-                                    Span {
-                                        lo: 45,
-                                        hi: 49,
-                                    },
+                            PassError {
+                                error: BaseProfCk(
+                                    ResultLiteral(
+                                        Span {
+                                            lo: 45,
+                                            hi: 49,
+                                        },
+                                    ),
                                 ),
-                            ),
+                                required_profile: Some(
+                                    Quantinuum,
+                                ),
+                            },
                         ),
                         Pass(
-                            BaseProfCk(
-                                ResultLiteral(
-                                    Span {
-                                        lo: 53,
-                                        hi: 57,
-                                    },
+                            PassError {
+                                error: BaseProfCk(
+                                    ResultLiteral(
+                                        Span {
+                                            lo: 53,
+                                            hi: 57,
+                                        },
+                                    ),
                                 ),
-                            ),
+                                required_profile: Some(
+                                    Quantinuum,
+                                ),
+                            },
                         ),
                     ],
                 ),
@@ -671,6 +699,9 @@ fn notebook_document_lints() {
                     [
                         Lint(
                             Lint {
+                                kind: Ast(
+                                    RedundantSemicolons,
+                                ),
                                 span: Span {
                                     lo: 35,
                                     hi: 38,
@@ -678,6 +709,15 @@ fn notebook_document_lints() {
                                 level: Warn,
                                 message: "redundant semicolons",
                                 help: "remove the redundant semicolons",
+                                code_action_edits: [
+                                    (
+                                        Span {
+                                            lo: 35,
+                                            hi: 38,
+                                        },
+                                        "",
+                                    ),
+                                ],
                             },
                         ),
                     ],
@@ -690,6 +730,9 @@ fn notebook_document_lints() {
                     [
                         Lint(
                             Lint {
+                                kind: Ast(
+                                    DivisionByZero,
+                                ),
                                 span: Span {
                                     lo: 74,
                                     hi: 79,
@@ -697,6 +740,7 @@ fn notebook_document_lints() {
                                 level: Warn,
                                 message: "attempt to divide by zero",
                                 help: "division by zero is not allowed",
+                                code_action_edits: [],
                             },
                         ),
                     ],
@@ -1404,12 +1448,14 @@ async fn loading_lints_config_from_manifest() {
                         DivisionByZero,
                     ),
                     level: Error,
+                    max_params: None,
                 },
                 LintConfig {
                     kind: Ast(
                         NeedlessParens,
                     ),
                     level: Error,
+                    max_params: None,
                 },
             ]"#]],
     )
@@ -1417,6 +1463,7 @@ async fn loading_lints_config_from_manifest() {
 }
 
 #[tokio::test]
+#[allow(clippy::too_many_lines)]
 async fn lints_update_after_manifest_change() {
     let this_file_qs = "namespace Foo { operation Main() : Unit { let x = 5 / 0 + (2 ^ 4); } }";
     let fs = FsNode::Dir(
@@ -1454,30 +1501,53 @@ async fn lints_update_after_manifest_change() {
     check_lints(
         lints,
         &expect![[r#"
-        [
-            Lint(
-                Lint {
-                    span: Span {
-                        lo: 58,
-                        hi: 65,
+            [
+                Lint(
+                    Lint {
+                        kind: Ast(
+                            DivisionByZero,
+                        ),
+                        span: Span {
+                            lo: 50,
+                            hi: 55,
+                        },
+                        level: Error,
+                        message: "attempt to divide by zero",
+                        help: "division by zero is not allowed",
+                        code_action_edits: [],
                     },
-                    level: Error,
-                    message: "unnecessary parentheses",
-                    help: "remove the extra parentheses for clarity",
-                },
-            ),
-            Lint(
-                Lint {
-                    span: Span {
-                        lo: 50,
-                        hi: 55,
+                ),
+                Lint(
+                    Lint {
+                        kind: Ast(
+                            NeedlessParens,
+                        ),
+                        span: Span {
+                            lo: 58,
+                            hi: 65,
+                        },
+                        level: Error,
+                        message: "unnecessary parentheses",
+                        help: "remove the extra parentheses for clarity",
+                        code_action_edits: [
+                            (
+                                Span {
+                                    lo: 58,
+                                    hi: 59,
+                                },
+                                "",
+                            ),
+                            (
+                                Span {
+                                    lo: 64,
+                                    hi: 65,
+                                },
+                                "",
+                            ),
+                        ],
                     },
-                    level: Error,
-                    message: "attempt to divide by zero",
-                    help: "division by zero is not allowed",
-                },
-            ),
-        ]"#]],
+                ),
+            ]"#]],
     );
 
     // Modify the manifest.
@@ -1496,30 +1566,53 @@ async fn lints_update_after_manifest_change() {
     check_lints(
         lints,
         &expect![[r#"
-        [
-            Lint(
-                Lint {
-                    span: Span {
-                        lo: 58,
-                        hi: 65,
+            [
+                Lint(
+                    Lint {
+                        kind: Ast(
+                            DivisionByZero,
+                        ),
+                        span: Span {
+                            lo: 50,
+                            hi: 55,
+                        },
+                        level: Warn,
+                        message: "attempt to divide by zero",
+                        help: "division by zero is not allowed",
+                        code_action_edits: [],
                     },
-                    level: Warn,
-                    message: "unnecessary parentheses",
-                    help: "remove the extra parentheses for clarity",
-                },
-            ),
-            Lint(
-                Lint {
-                    span: Span {
-                        lo: 50,
-                        hi: 55,
+                ),
+                Lint(
+                    Lint {
+                        kind: Ast(
+                            NeedlessParens,
+                        ),
+                        span: Span {
+                            lo: 58,
+                            hi: 65,
+                        },
+                        level: Warn,
+                        message: "unnecessary parentheses",
+                        help: "remove the extra parentheses for clarity",
+                        code_action_edits: [
+                            (
+                                Span {
+                                    lo: 58,
+                                    hi: 59,
+                                },
+                                "",
+                            ),
+                            (
+                                Span {
+                                    lo: 64,
+                                    hi: 65,
+                                },
+                                "",
+                            ),
+                        ],
                     },
-                    level: Warn,
-                    message: "attempt to divide by zero",
-                    help: "division by zero is not allowed",
-                },
-            ),
-        ]"#]],
+                ),
+            ]"#]],
     );
 }
 
@@ -1,9 +1,9 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 
-use crate::compilation::{Compilation, CompilationKind};
+use crate::compilation::{Compilation, CompilationKind, LintCache};
 use qsc::{
     compile,
     hir::PackageId,
@@ -86,6 +86,14 @@ fn compile_project_with_fake_stdlib_and_markers_cursor_optional(
             user_package_id: package_id,
             kind: CompilationKind::OpenProject,
             errors,
+            target_profile: Profile::Unrestricted,
+            language_features: LanguageFeatures::default(),
+            package_type: PackageType::Exe,
+            lints_config: Vec::new(),
+            fir: OnceLock::new(),
+            std_sources: None,
+            incremental_state: None,
+            lint_cache: LintCache::default(),
         },
         cursor_location,
         target_spans,
@@ -143,13 +151,21 @@ where
         compiler.update(increment);
     }
 
-    let (package_store, package_id) = compiler.into_package_store();
+    let (package_store, package_id, incremental_state) = compiler.into_package_store_and_state();
 
     Compilation {
         package_store,
         user_package_id: package_id,
         errors,
         kind: CompilationKind::Notebook,
+        target_profile: Profile::Unrestricted,
+        language_features: LanguageFeatures::default(),
+        package_type: PackageType::Lib,
+        lints_config: Vec::new(),
+        fir: OnceLock::new(),
+        std_sources: None,
+        incremental_state: Some(incremental_state),
+        lint_cache: LintCache::default(),
     }
 }
 
@@ -35,9 +35,12 @@ async fn single_document() {
                     ),
                     [
                         Pass(
-                            EntryPoint(
-                                NotFound,
-                            ),
+                            PassError {
+                                error: EntryPoint(
+                                    NotFound,
+                                ),
+                                required_profile: None,
+                            },
                         ),
                     ],
                 ),
@@ -82,9 +85,12 @@ async fn single_document_update() {
                     ),
                     [
                         Pass(
-                            EntryPoint(
-                                NotFound,
-                            ),
+                            PassError {
+                                error: EntryPoint(
+                                    NotFound,
+                                ),
+                                required_profile: None,
+                            },
                         ),
                     ],
                 ),
@@ -175,9 +181,12 @@ async fn document_in_project() {
                     None,
                     [
                         Pass(
-                            EntryPoint(
-                                NotFound,
-                            ),
+                            PassError {
+                                error: EntryPoint(
+                                    NotFound,
+                                ),
+                                required_profile: None,
+                            },
                         ),
                     ],
                 ),
@@ -13,12 +13,14 @@ mod intrinsic;
 mod logical;
 mod math;
 mod measurement;
+mod random;
 mod state_preparation;
 mod table_lookup;
 
 use indoc::indoc;
 use qsc::{
-    interpret::{GenericReceiver, Interpreter, Result, Value},
+    fir::Pauli,
+    interpret::{self, GenericReceiver, Interpreter, Result, Value},
     target::Profile,
     Backend, LanguageFeatures, PackageType, SourceMap, SparseSim,
 };
@@ -36,6 +38,10 @@ pub fn test_expression_with_lib(expr: &str, lib: &str, expected: &Value) -> Stri
     test_expression_with_lib_and_profile(expr, lib, Profile::Unrestricted, expected)
 }
 
+pub fn test_expression_with_profile(expr: &str, profile: Profile, expected: &Value) -> String {
+    test_expression_with_lib_and_profile(expr, "", profile, expected)
+}
+
 pub fn test_expression_with_lib_and_profile(
     expr: &str,
     lib: &str,
@@ -53,25 +59,56 @@ pub fn test_expression_with_lib_and_profile_and_sim(
     sim: &mut impl Backend<ResultType = impl Into<Result>>,
     expected: &Value,
 ) -> String {
-    let mut stdout = vec![];
-    let mut out = GenericReceiver::new(&mut stdout);
+    let mut interpreter = compile_expression(expr, lib, profile).expect("test should compile");
+    let (result, output) = eval_entry(&mut interpreter, sim);
+    assert_same_value(expected, result);
+    output
+}
 
+/// Compiles `lib` as the test source with `expr` as the entry expression.
+fn compile_expression(
+    expr: &str,
+    lib: &str,
+    profile: Profile,
+) -> std::result::Result<Interpreter, Vec<interpret::Error>> {
     let sources = SourceMap::new([("test".into(), lib.into())], Some(expr.into()));
-
-    let mut interpreter = Interpreter::new(
+    Interpreter::new(
         true,
         sources,
         PackageType::Exe,
         profile.into(),
         LanguageFeatures::default(),
     )
-    .expect("test should compile");
+}
+
+/// Evaluates the entry expression of `interpreter` on `sim`, returning its
+/// result along with the captured output.
+///
+/// # Panics
+///
+/// Will panic if evaluation fails.
+fn eval_entry(
+    interpreter: &mut Interpreter,
+    sim: &mut impl Backend<ResultType = impl Into<Result>>,
+) -> (Value, String) {
+    let mut stdout = vec![];
+    let mut out = GenericReceiver::new(&mut stdout);
     let result = interpreter
         .eval_entry_with_sim(sim, &mut out)
         .expect("test should run successfully");
+    (
+        result,
+        String::from_utf8(stdout).expect("stdout should be valid utf8"),
+    )
+}
 
-    match (&expected, result) {
-        (&Value::Tuple(tup1), Value::Tuple(tup2)) if tup1.len() == tup2.len() => {
+/// # Panics
+///
+/// Will panic if the result is not the same as expected.
+/// Floating point numbers, including those in tuples, are compared taking precision into account.
+fn assert_same_value(expected: &Value, result: Value) {
+    match (expected, result) {
+        (Value::Tuple(tup1), Value::Tuple(tup2)) if tup1.len() == tup2.len() => {
             // If both values are tuples of the same length, we crack them open and compare elements
             for (value1, value2) in tup1.iter().zip(tup2.iter()) {
                 if let (Value::Double(double1), Value::Double(double2)) = (value1, value2) {
@@ -82,13 +119,133 @@ pub fn test_expression_with_lib_and_profile_and_sim(
                 }
             }
         }
-        (&Value::Double(double1), Value::Double(double2)) => {
+        (Value::Double(double1), Value::Double(double2)) => {
             assert_doubles_almost_equal(*double1, double2);
         }
-        (&expected, result) => assert_eq!(expected, &result),
+        (expected, result) => assert_eq!(expected, &result),
     }
+}
+
+/// # Panics
+///
+/// Will panic if compilation fails, the result is not the same as expected, or the
+/// captured output of `Message` and `DumpMachine` calls differs from `expected_output`.
+/// A single trailing newline is ignored on both sides of the output comparison.
+pub fn test_expression_with_output(expr: &str, expected: &Value, expected_output: &str) {
+    let output = test_expression(expr, expected);
+    assert_eq!(
+        expected_output
+            .strip_suffix('\n')
+            .unwrap_or(expected_output),
+        output.strip_suffix('\n').unwrap_or(&output),
+        "captured output does not match"
+    );
+}
+
+/// Evaluates `expr` with both the quantum and classical seeds set to `seed`,
+/// so that random draws and measurements are reproducible.
+///
+/// # Panics
+///
+/// Will panic if compilation or evaluation fails.
+pub fn eval_expression_with_seed(expr: &str, seed: u64) -> Value {
+    let mut interpreter = compile_expression(expr, "", Profile::Unrestricted)
+        .expect("test should compile")
+        .with_seed(seed);
+    eval_entry(&mut interpreter, &mut SparseSim::default()).0
+}
+
+/// # Panics
+///
+/// Will panic if compilation fails or the result is not equal to the expected value,
+/// where `Double` values anywhere in the result are compared within `tolerance`
+/// and `NaN` is considered equal to itself.
+pub fn test_expression_approx(expr: &str, expected: &Value, tolerance: f64) -> String {
+    let mut interpreter =
+        compile_expression(expr, "", Profile::Unrestricted).expect("test should compile");
+    let (result, output) = eval_entry(&mut interpreter, &mut SparseSim::default());
+
+    // Values are shown in debug form, since `Result` values that carry a
+    // measurement id rather than a bit cannot be displayed.
+    assert!(
+        expected.approx_eq(&result, tolerance),
+        "expected {expected:?} but got {result:?} (tolerance {tolerance})"
+    );
+
+    output
+}
+
+/// Calls the callable named `callable` with `args` and checks the result against `expected`,
+/// using the same comparison rules as [`test_expression`]. Multiple arguments are passed
+/// as a tuple, matching the arity of the callable's input.
+///
+/// # Panics
+///
+/// Will panic if an argument cannot be expressed as a Q# literal, if the call does not compile
+/// (for example because the argument count or types do not match the callable's signature),
+/// or if the result is not the same as expected.
+pub fn test_callable_with_args(callable: &str, args: &[Value], expected: &Value) -> String {
+    let args = args
+        .iter()
+        .map(value_as_literal)
+        .collect::<Vec<_>>()
+        .join(", ");
+    let expr = format!("{callable}({args})");
 
-    String::from_utf8(stdout).expect("stdout should be valid utf8")
+    let mut interpreter = match compile_expression(&expr, "", Profile::Unrestricted) {
+        Ok(interpreter) => interpreter,
+        Err(errors) => {
+            let errors = errors
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("\n");
+            panic!("call `{expr}` does not match the signature of `{callable}`:\n{errors}");
+        }
+    };
+    let (result, output) = eval_entry(&mut interpreter, &mut SparseSim::default());
+    assert_same_value(expected, result);
+    output
+}
+
+/// Renders a value as a Q# literal expression that evaluates back to the same value.
+fn value_as_literal(value: &Value) -> String {
+    match value {
+        Value::Array(arr) => format!(
+            "[{}]",
+            arr.iter()
+                .map(value_as_literal)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        Value::BigInt(v) => format!("{v}L"),
+        Value::Double(v) if v.is_nan() => "(0.0 / 0.0)".to_string(),
+        Value::Double(v) if v.is_infinite() => {
+            let sign = if v.is_sign_negative() { "-" } else { "" };
+            format!("({sign}1.0 / 0.0)")
+        }
+        Value::Double(v) => format!("{v:?}"),
+        Value::String(v) => format!("{:?}", v.as_ref()),
+        Value::Tuple(tup) if tup.len() == 1 => format!("({},)", value_as_literal(&tup[0])),
+        Value::Tuple(tup) => format!(
+            "({})",
+            tup.iter()
+                .map(value_as_literal)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        Value::Result(Result::Val(bit)) => if *bit { "One" } else { "Zero" }.to_string(),
+        Value::Result(Result::Id(_)) => {
+            panic!("measurement result ids cannot be passed as test arguments")
+        }
+        Value::Bool(_) | Value::Int(_) | Value::Pauli(_) | Value::Range(_) => value.to_string(),
+        Value::Closure(..) | Value::Global(..) | Value::Qubit(_) | Value::Var(_) => {
+            panic!(
+                "{} values cannot be passed as test arguments",
+                value.type_name()
+            )
+        }
+    }
 }
 
 /// # Panics
@@ -127,6 +284,14 @@ fn check_repeated() {
     );
 }
 
+#[test]
+fn check_classical_results_match_across_profiles() {
+    let expr = "Microsoft.Quantum.Math.MaxI(Microsoft.Quantum.Math.AbsI(-5), 3) + Length([1, 2])";
+    for profile in [Profile::Unrestricted, Profile::Quantinuum] {
+        test_expression_with_profile(expr, profile, &Value::Int(7));
+    }
+}
+
 #[test]
 fn check_exp_with_cnot() {
     // This decomposition only holds if the magnitude of the angle used in Exp is correct and if the
@@ -201,3 +366,79 @@ fn check_base_profile_measure_resets_aux_qubits() {
         &Value::RESULT_ONE,
     );
 }
+
+#[test]
+fn check_result_literals() {
+    test_expression("Zero", &Value::RESULT_ZERO);
+    test_expression("One", &Value::RESULT_ONE);
+    test_expression(
+        "[One, Zero, One]",
+        &Value::Array(vec![Value::RESULT_ONE, Value::RESULT_ZERO, Value::RESULT_ONE].into()),
+    );
+    test_expression(
+        "(One, 1.0)",
+        &Value::Tuple(vec![Value::RESULT_ONE, Value::Double(1.0)].into()),
+    );
+}
+
+#[test]
+fn check_measured_results() {
+    test_expression(
+        indoc! {"{
+            use qs = Qubit[3];
+            X(qs[1]);
+            MResetEachZ(qs)
+        }"},
+        &Value::Array(vec![Value::RESULT_ZERO, Value::RESULT_ONE, Value::RESULT_ZERO].into()),
+    );
+}
+
+#[test]
+#[should_panic(expected = "assertion `left == right` failed")]
+fn check_mismatched_result_fails() {
+    test_expression("One", &Value::RESULT_ZERO);
+}
+
+#[test]
+fn check_pauli_literals() {
+    test_expression("PauliI", &Value::Pauli(Pauli::I));
+    test_expression("PauliX", &Value::Pauli(Pauli::X));
+    test_expression("PauliY", &Value::Pauli(Pauli::Y));
+    test_expression("PauliZ", &Value::Pauli(Pauli::Z));
+    test_expression(
+        "[PauliZ, PauliX]",
+        &Value::Array(vec![Value::Pauli(Pauli::Z), Value::Pauli(Pauli::X)].into()),
+    );
+}
+
+#[test]
+fn check_result_and_pauli_arguments() {
+    test_callable_with_args(
+        "Microsoft.Quantum.Arrays.Reversed",
+        &[Value::Array(
+            vec![Value::RESULT_ONE, Value::RESULT_ZERO, Value::RESULT_ZERO].into(),
+        )],
+        &Value::Array(vec![Value::RESULT_ZERO, Value::RESULT_ZERO, Value::RESULT_ONE].into()),
+    );
+    test_callable_with_args(
+        "Microsoft.Quantum.Arrays.Head",
+        &[Value::Array(
+            vec![Value::Pauli(Pauli::Y), Value::Pauli(Pauli::I)].into(),
+        )],
+        &Value::Pauli(Pauli::Y),
+    );
+}
+
+#[test]
+fn check_approx_result_mismatch_message() {
+    let message = std::panic::catch_unwind(|| {
+        test_expression_approx("[One]", &Value::Array(vec![Value::RESULT_ZERO].into()), 0.0)
+    })
+    .expect_err("mismatched results should fail")
+    .downcast::<String>()
+    .expect("panic payload should be a string");
+    assert_eq!(
+        *message,
+        "expected Array([Result(Val(false))]) but got Array([Result(Val(true))]) (tolerance 0)"
+    );
+}
@@ -25,6 +25,35 @@ fn check_all() {
         "Microsoft.Quantum.Arrays.All(x -> x == One, [One, One, Zero])",
         &Value::Bool(false),
     );
+    test_expression(
+        "Microsoft.Quantum.Arrays.All(x -> x > 0, [3, 1, 4])",
+        &Value::Bool(true),
+    );
+    test_expression(
+        "Microsoft.Quantum.Arrays.All(x -> x > 0, [3, -1, 4])",
+        &Value::Bool(false),
+    );
+    test_expression(
+        "Microsoft.Quantum.Arrays.All(x -> x > 0, [])",
+        &Value::Bool(true),
+    );
+}
+
+#[test]
+fn check_all_equal_i() {
+    test_expression(
+        "Microsoft.Quantum.Arrays.AllEqualI([7, 7, 7])",
+        &Value::Bool(true),
+    );
+    test_expression(
+        "Microsoft.Quantum.Arrays.AllEqualI([7, 7, 8])",
+        &Value::Bool(false),
+    );
+    test_expression(
+        "Microsoft.Quantum.Arrays.AllEqualI([7])",
+        &Value::Bool(true),
+    );
+    test_expression("Microsoft.Quantum.Arrays.AllEqualI([])", &Value::Bool(true));
 }
 
 #[test]
@@ -37,6 +66,14 @@ fn check_any() {
         "Microsoft.Quantum.Arrays.Any(x -> x % 2 == 0, [1, 3, 5, 7, 9])",
         &Value::Bool(false),
     );
+    test_expression(
+        "Microsoft.Quantum.Arrays.Any(x -> x > 0, [-3, 1, -4])",
+        &Value::Bool(true),
+    );
+    test_expression(
+        "Microsoft.Quantum.Arrays.Any(x -> x > 0, [])",
+        &Value::Bool(false),
+    );
 }
 
 #[test]
@@ -131,6 +168,22 @@ fn check_circularly_shifted() {
     );
 }
 
+#[test]
+fn check_coalesced() {
+    test_expression(
+        "Microsoft.Quantum.Arrays.Coalesced([(true, 1), (false, 9), (true, 3)])",
+        &Value::Array(vec![Value::Int(1), Value::Int(3)].into()),
+    );
+    test_expression(
+        "Microsoft.Quantum.Arrays.Coalesced([(false, 1), (false, 2)])",
+        &Value::Array(vec![].into()),
+    );
+    test_expression(
+        "{ let empty : (Bool, Int)[] = []; Microsoft.Quantum.Arrays.Coalesced(empty) }",
+        &Value::Array(vec![].into()),
+    );
+}
+
 #[test]
 fn check_column_at() {
     test_expression(
@@ -147,6 +200,100 @@ fn check_column_at() {
     );
 }
 
+#[test]
+fn check_conjugate_transposed_complex() {
+    test_expression(
+        "{
+            open Microsoft.Quantum.Math;
+            Microsoft.Quantum.Arrays.ConjugateTransposedComplex([[Complex(1.0, 1.0)]])
+        }",
+        &Value::Array(
+            vec![Value::Array(
+                vec![Value::Tuple(
+                    vec![Value::Double(1.0), Value::Double(-1.0)].into(),
+                )]
+                .into(),
+            )]
+            .into(),
+        ),
+    );
+    test_expression(
+        "{
+            open Microsoft.Quantum.Math;
+            Microsoft.Quantum.Arrays.ConjugateTransposedComplex(
+                [[Complex(1.0, 2.0), Complex(3.0, -4.0)]]
+            )
+        }",
+        &Value::Array(
+            vec![
+                Value::Array(
+                    vec![Value::Tuple(
+                        vec![Value::Double(1.0), Value::Double(-2.0)].into(),
+                    )]
+                    .into(),
+                ),
+                Value::Array(
+                    vec![Value::Tuple(
+                        vec![Value::Double(3.0), Value::Double(4.0)].into(),
+                    )]
+                    .into(),
+                ),
+            ]
+            .into(),
+        ),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Matrix is not a rectangular array")]
+fn check_conjugate_transposed_complex_ragged() {
+    test_expression(
+        "{
+            open Microsoft.Quantum.Math;
+            Microsoft.Quantum.Arrays.ConjugateTransposedComplex(
+                [[Complex(1.0, 0.0), Complex(2.0, 0.0)], [Complex(3.0, 0.0)]]
+            )
+        }",
+        &Value::Array(vec![].into()),
+    );
+}
+
+#[test]
+fn check_contains() {
+    test_expression(
+        "Microsoft.Quantum.Arrays.Contains((x, y) -> x == y, 5, [1, 3, 5, 7])",
+        &Value::Bool(true),
+    );
+    test_expression(
+        "Microsoft.Quantum.Arrays.Contains((x, y) -> x == y, 4, [1, 3, 5, 7])",
+        &Value::Bool(false),
+    );
+    test_expression(
+        "Microsoft.Quantum.Arrays.Contains((x, y) -> x % 10 == y % 10, 13, [1, 23, 5])",
+        &Value::Bool(true),
+    );
+    test_expression(
+        "Microsoft.Quantum.Arrays.Contains((x, y) -> x == y, One, [])",
+        &Value::Bool(false),
+    );
+}
+
+#[test]
+fn check_contains_i() {
+    test_expression(
+        "Microsoft.Quantum.Arrays.ContainsI(3, [1, 2, 3])",
+        &Value::Bool(true),
+    );
+    test_expression(
+        "Microsoft.Quantum.Arrays.ContainsI(4, [1, 2, 3])",
+        &Value::Bool(false),
+    );
+    test_expression(
+        "Microsoft.Quantum.Arrays.ContainsI(0, [])",
+        &Value::Bool(false),
+    );
+}
+
 #[test]
 fn check_count() {
     test_expression(
@@ -157,6 +304,50 @@ fn check_count() {
         "Microsoft.Quantum.Arrays.Count(x -> x % 2 == 0, [1, 3, 6, 7, 9])",
         &Value::Int(1),
     );
+    test_expression(
+        "Microsoft.Quantum.Arrays.Count(x -> x % 2 == 0, [2, 4, 6])",
+        &Value::Int(3),
+    );
+    test_expression(
+        "Microsoft.Quantum.Arrays.Count(x -> x % 2 == 0, [1, 3, 5])",
+        &Value::Int(0),
+    );
+    test_expression(
+        "Microsoft.Quantum.Arrays.Count(x -> x % 2 == 0, [])",
+        &Value::Int(0),
+    );
+}
+
+#[test]
+fn check_cumulative_folded() {
+    test_expression(
+        "Microsoft.Quantum.Arrays.CumulativeFolded((x, y) -> x + y, 0, [1, 2, 3, 4, 5])",
+        &Value::Array(
+            vec![
+                Value::Int(1),
+                Value::Int(3),
+                Value::Int(6),
+                Value::Int(10),
+                Value::Int(15),
+            ]
+            .into(),
+        ),
+    );
+    test_expression(
+        "Microsoft.Quantum.Arrays.CumulativeFolded((x, y) -> x + y, 10, [1, 2, 3])[2]",
+        &Value::Int(16),
+    );
+    test_expression(
+        "Microsoft.Quantum.Arrays.CumulativeFolded((x, y) -> x * y, 1, [2, 3, 4])[1]",
+        &Value::Int(6),
+    );
+    test_expression(
+        "{
+            let empty: Int[] = [];
+            Microsoft.Quantum.Arrays.CumulativeFolded((x, y) -> x + y, 0, empty)
+        }",
+        &Value::Array(vec![].into()),
+    );
 }
 
 #[test]
@@ -230,6 +421,56 @@ fn check_excluding() {
         "Microsoft.Quantum.Arrays.Excluding([3, 1, 4, 1], [10, 11, 12, 13, 14, 15])",
         &Value::Array(vec![Value::Int(10), Value::Int(12), Value::Int(15)].into()),
     );
+    test_expression(
+        "Microsoft.Quantum.Arrays.Excluding([5, 0], [10, 11, 12, 13, 14, 15])",
+        &Value::Array(
+            vec![
+                Value::Int(11),
+                Value::Int(12),
+                Value::Int(13),
+                Value::Int(14),
+            ]
+            .into(),
+        ),
+    );
+}
+
+#[test]
+fn check_elements_at() {
+    test_expression(
+        "Microsoft.Quantum.Arrays.ElementsAt([2, 0, 2, 1], [10, 11, 12])",
+        &Value::Array(
+            vec![
+                Value::Int(12),
+                Value::Int(10),
+                Value::Int(12),
+                Value::Int(11),
+            ]
+            .into(),
+        ),
+    );
+    test_expression(
+        "Microsoft.Quantum.Arrays.ElementsAt([], [10, 11, 12])",
+        &Value::Array(vec![].into()),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Index is out of range of the array")]
+fn check_elements_at_out_of_range() {
+    test_expression(
+        "Microsoft.Quantum.Arrays.ElementsAt([0, 3], [10, 11, 12])",
+        &Value::Array(vec![].into()),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Index is out of range of the array")]
+fn check_elements_at_negative_index() {
+    test_expression(
+        "Microsoft.Quantum.Arrays.ElementsAt([-1], [10, 11, 12])",
+        &Value::Array(vec![].into()),
+    );
 }
 
 #[test]
@@ -247,6 +488,70 @@ fn check_enumerated() {
     );
 }
 
+#[test]
+fn check_equal_a() {
+    test_expression(
+        "Microsoft.Quantum.Arrays.EqualA((x, y) -> x == y, [true, false], [true, false])",
+        &Value::Bool(true),
+    );
+    test_expression(
+        "Microsoft.Quantum.Arrays.EqualA(Microsoft.Quantum.Arrays.EqualI, [[1, 2], [3]], [[1, 2], [3]])",
+        &Value::Bool(true),
+    );
+    test_expression(
+        "Microsoft.Quantum.Arrays.EqualA(Microsoft.Quantum.Arrays.EqualI, [[1, 2], [3]], [[1, 2], [4]])",
+        &Value::Bool(false),
+    );
+    test_expression(
+        "{
+            let empty: Int[] = [];
+            Microsoft.Quantum.Arrays.EqualA((x, y) -> x == y, empty, empty)
+        }",
+        &Value::Bool(true),
+    );
+}
+
+#[test]
+fn check_equal_a_stops_at_first_mismatch() {
+    test_expression(
+        "Microsoft.Quantum.Arrays.EqualA((x, y) -> {
+            if x < 0 {
+                fail \"compared an element after a mismatch\";
+            }
+            x == y
+        }, [1, 2, -1], [1, 3, -1])",
+        &Value::Bool(false),
+    );
+}
+
+#[test]
+fn check_equal_d() {
+    test_expression(
+        "Microsoft.Quantum.Arrays.EqualD([0.5, -1.0], [0.5, -1.0])",
+        &Value::Bool(true),
+    );
+    test_expression(
+        "Microsoft.Quantum.Arrays.EqualD([0.5, -1.0], [0.5, -1.5])",
+        &Value::Bool(false),
+    );
+}
+
+#[test]
+fn check_equal_i() {
+    test_expression(
+        "Microsoft.Quantum.Arrays.EqualI([1, 2, 3], [1, 2, 3])",
+        &Value::Bool(true),
+    );
+    test_expression(
+        "Microsoft.Quantum.Arrays.EqualI([1, 2, 3], [1, 2])",
+        &Value::Bool(false),
+    );
+    test_expression(
+        "Microsoft.Quantum.Arrays.EqualI([1, 2, 3], [1, 2, 4])",
+        &Value::Bool(false),
+    );
+}
+
 #[test]
 fn check_filtered() {
     test_expression(
@@ -257,6 +562,22 @@ fn check_filtered() {
         "Microsoft.Quantum.Arrays.Filtered(x -> x % 2 != 0, [1, 2, 3, 4, 5])",
         &Value::Array(vec![Value::Int(1), Value::Int(3), Value::Int(5)].into()),
     );
+    test_expression(
+        "Microsoft.Quantum.Arrays.Filtered(x -> x > 0, [3, -1, 0, 5, -2, 1])",
+        &Value::Array(vec![Value::Int(3), Value::Int(5), Value::Int(1)].into()),
+    );
+    test_expression(
+        "Microsoft.Quantum.Arrays.Filtered(x -> x > 0, [1, 2, 3])",
+        &Value::Array(vec![Value::Int(1), Value::Int(2), Value::Int(3)].into()),
+    );
+    test_expression(
+        "Microsoft.Quantum.Arrays.Filtered(x -> x > 0, [-1, -2, 0])",
+        &Value::Array(vec![].into()),
+    );
+    test_expression(
+        "Microsoft.Quantum.Arrays.Filtered(x -> x > 0, [])",
+        &Value::Array(vec![].into()),
+    );
 }
 
 #[test]
@@ -311,6 +632,91 @@ fn check_fold() {
     );
 }
 
+#[test]
+fn check_fold_right() {
+    test_expression(
+        "Microsoft.Quantum.Arrays.FoldRight((x, y) -> x + y, 0, [1, 2, 3, 4, 5])",
+        &Value::Int(15),
+    );
+    test_expression(
+        "Microsoft.Quantum.Arrays.FoldRight((x, y) -> [x] + y, [], [1, 2, 3])",
+        &Value::Array(vec![Value::Int(1), Value::Int(2), Value::Int(3)].into()),
+    );
+    test_expression(
+        "{
+            let empty: Int[] = [];
+            Microsoft.Quantum.Arrays.FoldRight((x, y) -> x + y, 3, empty)
+        }",
+        &Value::Int(3),
+    );
+}
+
+#[test]
+fn check_fold_and_fold_right_subtraction_order() {
+    // ((0 - 1) - 2) - 3
+    test_expression(
+        "Microsoft.Quantum.Arrays.Fold((x, y) -> x - y, 0, [1, 2, 3])",
+        &Value::Int(-6),
+    );
+    // 1 - (2 - (3 - 0))
+    test_expression(
+        "Microsoft.Quantum.Arrays.FoldRight((x, y) -> x - y, 0, [1, 2, 3])",
+        &Value::Int(2),
+    );
+}
+
+#[test]
+fn check_reduced() {
+    test_expression(
+        "Microsoft.Quantum.Arrays.Reduced(Microsoft.Quantum.Math.MinI, [4, 2, 7, 3])",
+        &Value::Int(2),
+    );
+    // (10 - 1) - 2
+    test_expression(
+        "Microsoft.Quantum.Arrays.Reduced((x, y) -> x - y, [10, 1, 2])",
+        &Value::Int(7),
+    );
+    test_expression(
+        "Microsoft.Quantum.Arrays.Reduced((x, y) -> x + y, [5])",
+        &Value::Int(5),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Array must have at least 1 element")]
+fn check_reduced_empty() {
+    test_expression(
+        "{
+            let empty: Int[] = [];
+            Microsoft.Quantum.Arrays.Reduced((x, y) -> x + y, empty)
+        }",
+        &Value::Int(0),
+    );
+}
+
+#[test]
+fn check_fold_until() {
+    test_expression(
+        "Microsoft.Quantum.Arrays.FoldUntil((x, y) -> x + y, x -> x > 5, 0, [1, 2, 3, 4, 5])",
+        &Value::Int(6),
+    );
+    test_expression(
+        "Microsoft.Quantum.Arrays.FoldUntil((x, y) -> x + y, x -> x > 100, 0, [1, 2, 3, 4, 5])",
+        &Value::Int(15),
+    );
+    test_expression(
+        "Microsoft.Quantum.Arrays.FoldUntil((x, y) -> x + y, x -> x > 5, 10, [1, 2, 3])",
+        &Value::Int(10),
+    );
+    test_expression(
+        "{
+            let empty: Int[] = [];
+            Microsoft.Quantum.Arrays.FoldUntil((x, y) -> x + y, x -> x > 5, 3, empty)
+        }",
+        &Value::Int(3),
+    );
+}
+
 #[test]
 fn check_for_each() {
     test_expression(
@@ -359,6 +765,28 @@ fn check_index_of() {
     );
 }
 
+#[test]
+fn check_index_of_short_circuits() {
+    // Applying the predicate to the second element would divide by zero.
+    test_expression(
+        "Microsoft.Quantum.Arrays.IndexOf(x -> 10 / x > 1, [5, 0])",
+        &Value::Int(0),
+    );
+}
+
+#[test]
+fn check_index_of_i() {
+    test_expression(
+        "Microsoft.Quantum.Arrays.IndexOfI(5, [1, 5, 7, 5])",
+        &Value::Int(1),
+    );
+    test_expression(
+        "Microsoft.Quantum.Arrays.IndexOfI(2, [1, 5, 7])",
+        &Value::Int(-1),
+    );
+    test_expression("Microsoft.Quantum.Arrays.IndexOfI(0, [])", &Value::Int(-1));
+}
+
 #[test]
 fn check_index_range() {
     test_expression(
@@ -395,6 +823,24 @@ fn check_interleaved() {
         "Microsoft.Quantum.Arrays.Interleaved([true, true], [false])",
         &Value::Array(vec![Value::Bool(true), Value::Bool(false), Value::Bool(true)].into()),
     );
+    test_expression(
+        "{
+            let empty: Int[] = [];
+            Microsoft.Quantum.Arrays.Interleaved(empty, empty)
+        }",
+        &Value::Array(vec![].into()),
+    );
+}
+
+#[test]
+#[should_panic(
+    expected = "Array `first` must either be of same size as `second` or have one more element"
+)]
+fn check_interleaved_length_mismatch() {
+    test_expression(
+        "Microsoft.Quantum.Arrays.Interleaved([1], [2, 3])",
+        &Value::Array(vec![].into()),
+    );
 }
 
 #[test]
@@ -514,6 +960,39 @@ fn check_mapped_by_index() {
     );
 }
 
+#[test]
+fn check_mapped_by_index_multiplies_by_index() {
+    test_expression(
+        "Microsoft.Quantum.Arrays.MappedByIndex((index, element) -> element * index, [5, 6, 7])",
+        &Value::Array(vec![Value::Int(0), Value::Int(6), Value::Int(14)].into()),
+    );
+}
+
+#[test]
+fn check_mapped_by_index_passes_indices_in_order() {
+    test_expression(
+        "Microsoft.Quantum.Arrays.MappedByIndex((index, element) -> (index, element), [true, false])",
+        &Value::Array(
+            vec![
+                Value::Tuple(vec![Value::Int(0), Value::Bool(true)].into()),
+                Value::Tuple(vec![Value::Int(1), Value::Bool(false)].into()),
+            ]
+            .into(),
+        ),
+    );
+}
+
+#[test]
+fn check_mapped_by_index_empty() {
+    test_expression(
+        "{
+            let empty : Int[] = [];
+            Microsoft.Quantum.Arrays.MappedByIndex((index, element) -> index + element, empty)
+        }",
+        &Value::Array(vec![].into()),
+    );
+}
+
 #[test]
 fn check_mapped_over_range() {
     test_expression(
@@ -642,6 +1121,43 @@ fn check_sequence_i() {
     );
 }
 
+#[test]
+fn check_sequence_i_length_and_endpoints() {
+    test_expression(
+        "{
+            let sequence = Microsoft.Quantum.Arrays.SequenceI(1, 5);
+            (Length(sequence), sequence[0], sequence[Length(sequence) - 1])
+        }",
+        &Value::Tuple(vec![Value::Int(5), Value::Int(1), Value::Int(5)].into()),
+    );
+}
+
+#[test]
+fn check_sequence_i_reversed_range_is_empty() {
+    test_expression(
+        "Microsoft.Quantum.Arrays.SequenceI(5, 1)",
+        &Value::Array(vec![].into()),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Sequence is too long: it can have at most 16777216 elements")]
+fn check_sequence_i_too_long() {
+    test_expression(
+        "Microsoft.Quantum.Arrays.SequenceI(0, 1 <<< 24)",
+        &Value::Array(vec![].into()),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Sequence is too long: it can have at most 16777216 elements")]
+fn check_sequence_i_length_overflow() {
+    test_expression(
+        "Microsoft.Quantum.Arrays.SequenceI(-0x7FFFFFFFFFFFFFFF, 0x7FFFFFFFFFFFFFFF)",
+        &Value::Array(vec![].into()),
+    );
+}
+
 #[test]
 fn check_sequence_l() {
     test_expression(
@@ -657,16 +1173,83 @@ fn check_sequence_l() {
         ),
     );
     test_expression(
-        "Microsoft.Quantum.Arrays.SequenceL(-5L, -2L)",
-        &Value::Array(
-            vec![
-                Value::BigInt(BigInt::from(-5)),
-                Value::BigInt(BigInt::from(-4)),
-                Value::BigInt(BigInt::from(-3)),
-                Value::BigInt(BigInt::from(-2)),
-            ]
-            .into(),
-        ),
+        "Microsoft.Quantum.Arrays.SequenceL(-5L, -2L)",
+        &Value::Array(
+            vec![
+                Value::BigInt(BigInt::from(-5)),
+                Value::BigInt(BigInt::from(-4)),
+                Value::BigInt(BigInt::from(-3)),
+                Value::BigInt(BigInt::from(-2)),
+            ]
+            .into(),
+        ),
+    );
+}
+
+#[test]
+fn check_sequence_l_reversed_range_is_empty() {
+    test_expression(
+        "Microsoft.Quantum.Arrays.SequenceL(5L, 1L)",
+        &Value::Array(vec![].into()),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Sequence is too long: it can have at most 16777216 elements")]
+fn check_sequence_l_too_long() {
+    test_expression(
+        "Microsoft.Quantum.Arrays.SequenceL(0L, 1L <<< 24)",
+        &Value::Array(vec![].into()),
+    );
+}
+
+#[test]
+fn check_range_as_int_array_stepped() {
+    test_expression(
+        "Microsoft.Quantum.Arrays.RangeAsIntArray(0..2..7)",
+        &Value::Array(vec![Value::Int(0), Value::Int(2), Value::Int(4), Value::Int(6)].into()),
+    );
+    test_expression(
+        "Microsoft.Quantum.Arrays.RangeAsIntArray(3..3)",
+        &Value::Array(vec![Value::Int(3)].into()),
+    );
+}
+
+#[test]
+fn check_range_as_int_array_negative_step() {
+    test_expression(
+        "Microsoft.Quantum.Arrays.RangeAsIntArray(5..-2..0)",
+        &Value::Array(vec![Value::Int(5), Value::Int(3), Value::Int(1)].into()),
+    );
+}
+
+#[test]
+fn check_range_as_int_array_empty() {
+    test_expression(
+        "Microsoft.Quantum.Arrays.RangeAsIntArray(5..1)",
+        &Value::Array(vec![].into()),
+    );
+    test_expression(
+        "Microsoft.Quantum.Arrays.RangeAsIntArray(1..-1..5)",
+        &Value::Array(vec![].into()),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Range is too long: it can have at most 16777216 elements")]
+fn check_range_as_int_array_too_long() {
+    test_expression(
+        "Microsoft.Quantum.Arrays.RangeAsIntArray(0..1 <<< 24)",
+        &Value::Array(vec![].into()),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Range is too long: it can have at most 16777216 elements")]
+fn check_range_as_int_array_length_overflow() {
+    test_expression(
+        "Microsoft.Quantum.Arrays.RangeAsIntArray(0x7FFFFFFFFFFFFFFF..-1..-0x7FFFFFFFFFFFFFFF)",
+        &Value::Array(vec![].into()),
     );
 }
 
@@ -724,6 +1307,90 @@ fn check_sorted() {
     );
 }
 
+#[test]
+fn check_sorted_is_stable() {
+    test_expression(
+        "{
+            function KeyLessOrEqual(a : (Int, Int), b : (Int, Int)) : Bool {
+                let (keyA, _) = a;
+                let (keyB, _) = b;
+                keyA <= keyB
+            }
+            Microsoft.Quantum.Arrays.Sorted(KeyLessOrEqual, [(2, 0), (1, 1), (2, 2), (1, 3), (0, 4), (2, 5)])
+        }",
+        &Value::Array(
+            vec![
+                Value::Tuple(vec![Value::Int(0), Value::Int(4)].into()),
+                Value::Tuple(vec![Value::Int(1), Value::Int(1)].into()),
+                Value::Tuple(vec![Value::Int(1), Value::Int(3)].into()),
+                Value::Tuple(vec![Value::Int(2), Value::Int(0)].into()),
+                Value::Tuple(vec![Value::Int(2), Value::Int(2)].into()),
+                Value::Tuple(vec![Value::Int(2), Value::Int(5)].into()),
+            ]
+            .into(),
+        ),
+    );
+    test_expression(
+        "{
+            function KeyGreaterOrEqual(a : (Int, Int), b : (Int, Int)) : Bool {
+                let (keyA, _) = a;
+                let (keyB, _) = b;
+                keyA >= keyB
+            }
+            Microsoft.Quantum.Arrays.Sorted(KeyGreaterOrEqual, [(2, 0), (1, 1), (2, 2), (1, 3)])
+        }",
+        &Value::Array(
+            vec![
+                Value::Tuple(vec![Value::Int(2), Value::Int(0)].into()),
+                Value::Tuple(vec![Value::Int(2), Value::Int(2)].into()),
+                Value::Tuple(vec![Value::Int(1), Value::Int(1)].into()),
+                Value::Tuple(vec![Value::Int(1), Value::Int(3)].into()),
+            ]
+            .into(),
+        ),
+    );
+}
+
+#[test]
+fn check_sorted_d() {
+    test_expression(
+        "Microsoft.Quantum.Arrays.SortedD([0.5, -1.0, 0.25, 0.5])",
+        &Value::Array(
+            vec![
+                Value::Double(-1.0),
+                Value::Double(0.25),
+                Value::Double(0.5),
+                Value::Double(0.5),
+            ]
+            .into(),
+        ),
+    );
+}
+
+#[test]
+fn check_sorted_i() {
+    test_expression(
+        "Microsoft.Quantum.Arrays.SortedI([3, 17, 11, -201, -11])",
+        &Value::Array(
+            vec![
+                Value::Int(-201),
+                Value::Int(-11),
+                Value::Int(3),
+                Value::Int(11),
+                Value::Int(17),
+            ]
+            .into(),
+        ),
+    );
+    test_expression(
+        "{
+            let empty: Int[] = [];
+            Microsoft.Quantum.Arrays.SortedI(empty)
+        }",
+        &Value::Array(vec![].into()),
+    );
+}
+
 #[test]
 fn check_rest() {
     test_expression(
@@ -740,6 +1407,57 @@ fn check_reversed() {
     );
 }
 
+#[test]
+fn check_rotate_to_front() {
+    test_expression(
+        "Microsoft.Quantum.Arrays.RotateToFront((a, b) -> a == b, 3, [1, 2, 3, 4])",
+        &Value::Array(vec![Value::Int(3), Value::Int(1), Value::Int(2), Value::Int(4)].into()),
+    );
+    test_expression(
+        "Microsoft.Quantum.Arrays.RotateToFront((a, b) -> a == b, 1, [1, 2, 1])",
+        &Value::Array(vec![Value::Int(1), Value::Int(2), Value::Int(1)].into()),
+    );
+    test_expression(
+        "Microsoft.Quantum.Arrays.RotateToFront((a, b) -> a == b, 4, [1, 2, 4])",
+        &Value::Array(vec![Value::Int(4), Value::Int(1), Value::Int(2)].into()),
+    );
+    test_expression(
+        "Microsoft.Quantum.Arrays.RotateToFront((a, b) -> a == b, 5, [1, 2, 3])",
+        &Value::Array(vec![Value::Int(1), Value::Int(2), Value::Int(3)].into()),
+    );
+}
+
+#[test]
+fn check_rotated() {
+    test_expression(
+        "Microsoft.Quantum.Arrays.Rotated(1, [1, 2, 3, 4])",
+        &Value::Array(vec![Value::Int(2), Value::Int(3), Value::Int(4), Value::Int(1)].into()),
+    );
+    test_expression(
+        "Microsoft.Quantum.Arrays.Rotated(-1, [1, 2, 3, 4])",
+        &Value::Array(vec![Value::Int(4), Value::Int(1), Value::Int(2), Value::Int(3)].into()),
+    );
+    test_expression(
+        "Microsoft.Quantum.Arrays.Rotated(0, [1, 2, 3])",
+        &Value::Array(vec![Value::Int(1), Value::Int(2), Value::Int(3)].into()),
+    );
+    test_expression(
+        "Microsoft.Quantum.Arrays.Rotated(7, [1, 2, 3])",
+        &Value::Array(vec![Value::Int(2), Value::Int(3), Value::Int(1)].into()),
+    );
+    test_expression(
+        "Microsoft.Quantum.Arrays.Rotated(-7, [1, 2, 3])",
+        &Value::Array(vec![Value::Int(3), Value::Int(1), Value::Int(2)].into()),
+    );
+    test_expression(
+        "{
+            let empty: Int[] = [];
+            Microsoft.Quantum.Arrays.Rotated(3, empty)
+        }",
+        &Value::Array(vec![].into()),
+    );
+}
+
 #[test]
 fn check_subarray() {
     test_expression(
@@ -812,6 +1530,140 @@ fn check_transposed() {
     );
 }
 
+#[test]
+fn check_transposed_elements() {
+    test_expression(
+        "{
+            let transposed = Microsoft.Quantum.Arrays.Transposed([[1, 2, 3], [4, 5, 6]]);
+            (Length(transposed), Length(transposed[0]), transposed[2][0], transposed[0][1])
+        }",
+        &Value::Tuple(vec![Value::Int(3), Value::Int(2), Value::Int(3), Value::Int(4)].into()),
+    );
+}
+
+#[test]
+fn check_transposed_without_columns() {
+    test_expression(
+        "{
+            let empty: Int[][] = [];
+            Microsoft.Quantum.Arrays.Transposed(empty)
+        }",
+        &Value::Array(vec![].into()),
+    );
+    test_expression(
+        "{
+            let rows: Int[][] = [[], []];
+            Microsoft.Quantum.Arrays.Transposed(rows)
+        }",
+        &Value::Array(vec![].into()),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Matrix is not a rectangular array: all rows must have the same length")]
+fn check_transposed_ragged() {
+    test_expression(
+        "Microsoft.Quantum.Arrays.Transposed([[1, 2, 3], [4, 5]])",
+        &Value::Array(vec![].into()),
+    );
+}
+
+#[test]
+fn check_transposed_complex() {
+    test_expression(
+        "{
+            open Microsoft.Quantum.Math;
+            Microsoft.Quantum.Arrays.TransposedComplex(
+                [[Complex(1.0, 2.0), Complex(3.0, -4.0)]]
+            )
+        }",
+        &Value::Array(
+            vec![
+                Value::Array(
+                    vec![Value::Tuple(
+                        vec![Value::Double(1.0), Value::Double(2.0)].into(),
+                    )]
+                    .into(),
+                ),
+                Value::Array(
+                    vec![Value::Tuple(
+                        vec![Value::Double(3.0), Value::Double(-4.0)].into(),
+                    )]
+                    .into(),
+                ),
+            ]
+            .into(),
+        ),
+    );
+}
+
+#[test]
+fn check_transposed_padded() {
+    test_expression(
+        "Microsoft.Quantum.Arrays.TransposedPadded(0, [[1, 2, 3], [4]])",
+        &Value::Array(
+            vec![
+                Value::Array(vec![Value::Int(1), Value::Int(4)].into()),
+                Value::Array(vec![Value::Int(2), Value::Int(0)].into()),
+                Value::Array(vec![Value::Int(3), Value::Int(0)].into()),
+            ]
+            .into(),
+        ),
+    );
+    test_expression(
+        "Microsoft.Quantum.Arrays.TransposedPadded(0, [[1], [], [2, 3]])",
+        &Value::Array(
+            vec![
+                Value::Array(vec![Value::Int(1), Value::Int(0), Value::Int(2)].into()),
+                Value::Array(vec![Value::Int(0), Value::Int(0), Value::Int(3)].into()),
+            ]
+            .into(),
+        ),
+    );
+    test_expression(
+        "Microsoft.Quantum.Arrays.TransposedPadded(0, [[1, 2], [3, 4]])",
+        &Value::Array(
+            vec![
+                Value::Array(vec![Value::Int(1), Value::Int(3)].into()),
+                Value::Array(vec![Value::Int(2), Value::Int(4)].into()),
+            ]
+            .into(),
+        ),
+    );
+}
+
+#[test]
+fn check_unzip_complex() {
+    test_expression(
+        "Microsoft.Quantum.Arrays.UnzipComplex([Microsoft.Quantum.Math.Complex(1.0, 3.0), Microsoft.Quantum.Math.Complex(2.0, 4.0)])",
+        &Value::Tuple(
+            vec![
+                Value::Array(vec![Value::Double(1.0), Value::Double(2.0)].into()),
+                Value::Array(vec![Value::Double(3.0), Value::Double(4.0)].into()),
+            ]
+            .into(),
+        ),
+    );
+    test_expression(
+        "Microsoft.Quantum.Arrays.UnzipComplex([])",
+        &Value::Tuple(vec![Value::Array(vec![].into()), Value::Array(vec![].into())].into()),
+    );
+    test_expression(
+        "{
+            open Microsoft.Quantum.Arrays;
+            let (reals, imags) = UnzipComplex(ZipToComplex([0.5, -1.0], [2.0, 0.0]));
+            ZipToComplex(reals, imags)
+        }",
+        &Value::Array(
+            vec![
+                Value::Tuple(vec![Value::Double(0.5), Value::Double(2.0)].into()),
+                Value::Tuple(vec![Value::Double(-1.0), Value::Double(0.0)].into()),
+            ]
+            .into(),
+        ),
+    );
+}
+
 #[test]
 fn check_unzipped() {
     test_expression(
@@ -917,6 +1769,89 @@ fn check_windows() {
     );
 }
 
+#[test]
+fn check_with_replaced() {
+    test_expression(
+        "Microsoft.Quantum.Arrays.WithReplaced([0, 2], [10, 30], [1, 2, 3, 4])",
+        &Value::Array(vec![Value::Int(10), Value::Int(2), Value::Int(30), Value::Int(4)].into()),
+    );
+    test_expression(
+        "Microsoft.Quantum.Arrays.WithReplaced([], [], [1, 2, 3])",
+        &Value::Array(vec![Value::Int(1), Value::Int(2), Value::Int(3)].into()),
+    );
+}
+
+#[test]
+fn check_with_replaced_last_write_wins() {
+    test_expression(
+        "Microsoft.Quantum.Arrays.WithReplaced([1, 0, 1], [5, 7, 6], [1, 2, 3])",
+        &Value::Array(vec![Value::Int(7), Value::Int(6), Value::Int(3)].into()),
+    );
+}
+
+#[test]
+fn check_with_replaced_leaves_input_unchanged() {
+    test_expression(
+        "{
+            let array = [1, 2, 3];
+            let replaced = Microsoft.Quantum.Arrays.WithReplaced([0], [9], array);
+            (array, replaced)
+        }",
+        &Value::Tuple(
+            vec![
+                Value::Array(vec![Value::Int(1), Value::Int(2), Value::Int(3)].into()),
+                Value::Array(vec![Value::Int(9), Value::Int(2), Value::Int(3)].into()),
+            ]
+            .into(),
+        ),
+    );
+}
+
+#[test]
+#[should_panic(expected = "`indices` and `values` must have the same length.")]
+fn check_with_replaced_length_mismatch() {
+    test_expression(
+        "Microsoft.Quantum.Arrays.WithReplaced([0, 1], [5], [1, 2, 3])",
+        &Value::Array(vec![].into()),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Index 3 is out of range for an array of length 3.")]
+fn check_with_replaced_out_of_range() {
+    test_expression(
+        "Microsoft.Quantum.Arrays.WithReplaced([0, 3], [5, 6], [1, 2, 3])",
+        &Value::Array(vec![].into()),
+    );
+}
+
+#[test]
+fn check_zip_to_complex() {
+    test_expression(
+        "Microsoft.Quantum.Arrays.ZipToComplex([1.0, 2.0], [3.0, 4.0])",
+        &Value::Array(
+            vec![
+                Value::Tuple(vec![Value::Double(1.0), Value::Double(3.0)].into()),
+                Value::Tuple(vec![Value::Double(2.0), Value::Double(4.0)].into()),
+            ]
+            .into(),
+        ),
+    );
+    test_expression(
+        "Microsoft.Quantum.Arrays.ZipToComplex([], [])",
+        &Value::Array(vec![].into()),
+    );
+}
+
+#[test]
+#[should_panic(expected = "`reals` and `imags` must have the same length.")]
+fn check_zip_to_complex_length_mismatch() {
+    test_expression(
+        "Microsoft.Quantum.Arrays.ZipToComplex([1.0], [])",
+        &Value::Array(vec![].into()),
+    );
+}
+
 #[test]
 fn check_zipped() {
     test_expression(
@@ -989,3 +1924,24 @@ fn check_zipped() {
         ),
     );
 }
+
+#[test]
+fn check_zipped3() {
+    test_expression(
+        "{
+            let empty: Int[] = [];
+            Microsoft.Quantum.Arrays.Zipped3(empty, [true], [1.0])
+        }",
+        &Value::Array(vec![].into()),
+    );
+    test_expression(
+        "Microsoft.Quantum.Arrays.Zipped3([1, 2, 3], [true, false], [0.5, 1.5, 2.5])",
+        &Value::Array(
+            vec![
+                Value::Tuple(vec![Value::Int(1), Value::Bool(true), Value::Double(0.5)].into()),
+                Value::Tuple(vec![Value::Int(2), Value::Bool(false), Value::Double(1.5)].into()),
+            ]
+            .into(),
+        ),
+    );
+}
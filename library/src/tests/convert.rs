@@ -1,8 +1,9 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 #![allow(clippy::too_many_lines)]
-use super::test_expression;
+use super::{test_expression, test_expression_approx};
 use qsc::interpret::Value;
+use std::f64::consts::{FRAC_PI_4, PI, SQRT_2};
 
 // Tests for Microsoft.Quantum.Convert namespace
 
@@ -277,3 +278,135 @@ fn test_complex_polar_as_complex() {
         &Value::Tuple(vec![Value::Double(2.0), Value::Double(1.0)].into()),
     );
 }
+
+#[test]
+fn test_complex_as_complex_polar_one_plus_i() {
+    test_expression_approx(
+        "Microsoft.Quantum.Convert.ComplexAsComplexPolar(Microsoft.Quantum.Math.Complex(1.0, 1.0))",
+        &Value::Tuple(vec![Value::Double(SQRT_2), Value::Double(FRAC_PI_4)].into()),
+        1e-12,
+    );
+}
+
+#[test]
+fn test_complex_as_complex_polar_round_trip() {
+    test_expression_approx(
+        "{
+            open Microsoft.Quantum.Convert;
+            open Microsoft.Quantum.Math;
+            ComplexPolarAsComplex(ComplexAsComplexPolar(Complex(1.0, 1.0)))
+        }",
+        &Value::Tuple(vec![Value::Double(1.0), Value::Double(1.0)].into()),
+        1e-12,
+    );
+    test_expression_approx(
+        "{
+            open Microsoft.Quantum.Convert;
+            open Microsoft.Quantum.Math;
+            ComplexPolarAsComplex(ComplexAsComplexPolar(Complex(-3.0, -0.5)))
+        }",
+        &Value::Tuple(vec![Value::Double(-3.0), Value::Double(-0.5)].into()),
+        1e-12,
+    );
+}
+
+#[test]
+fn test_complex_as_complex_polar_negative_real_and_zero() {
+    test_expression_approx(
+        "Microsoft.Quantum.Convert.ComplexAsComplexPolar(Microsoft.Quantum.Math.Complex(-2.0, 0.0))",
+        &Value::Tuple(vec![Value::Double(2.0), Value::Double(PI)].into()),
+        1e-12,
+    );
+    test_expression(
+        "Microsoft.Quantum.Convert.ComplexAsComplexPolar(Microsoft.Quantum.Math.Complex(0.0, 0.0))",
+        &Value::Tuple(vec![Value::Double(0.0), Value::Double(0.0)].into()),
+    );
+    test_expression(
+        "Microsoft.Quantum.Convert.ComplexAsComplexPolar(Microsoft.Quantum.Math.Complex(-0.0, -0.0))",
+        &Value::Tuple(vec![Value::Double(0.0), Value::Double(0.0)].into()),
+    );
+}
+
+#[test]
+fn test_real_array_as_complex_array() {
+    test_expression(
+        "Microsoft.Quantum.Convert.RealArrayAsComplexArray([0.6, -0.8])",
+        &Value::Array(
+            vec![
+                Value::Tuple(vec![Value::Double(0.6), Value::Double(0.0)].into()),
+                Value::Tuple(vec![Value::Double(-0.8), Value::Double(0.0)].into()),
+            ]
+            .into(),
+        ),
+    );
+    test_expression(
+        "Microsoft.Quantum.Convert.RealArrayAsComplexArray([])",
+        &Value::Array(vec![].into()),
+    );
+}
+
+#[test]
+fn test_double_as_string_with_precision() {
+    for (expr, expected) in [
+        ("3.14159, 2", "3.14"),
+        ("3.14159, 0", "3"),
+        ("2.5, 0", "3"),
+        ("-2.5, 0", "-3"),
+        ("0.0, 3", "0.000"),
+        ("-0.001, 2", "0.00"),
+        ("-1.25, 1", "-1.3"),
+        ("9.999, 2", "10.00"),
+        ("0.05, 4", "0.0500"),
+        ("1e20, 1", "100000000000000000000.0"),
+        ("0.5, 20", "0.50000000000000000000"),
+        ("0.0 / 0.0, 2", "NaN"),
+        ("1.0 / 0.0, 2", "Infinity"),
+        ("-1.0 / 0.0, 2", "-Infinity"),
+    ] {
+        test_expression(
+            &format!("Microsoft.Quantum.Convert.DoubleAsStringWithPrecision({expr})"),
+            &Value::String(expected.into()),
+        );
+    }
+}
+
+#[test]
+#[should_panic(expected = "DoubleAsStringWithPrecision: precision must be non-negative")]
+fn test_double_as_string_with_negative_precision() {
+    test_expression(
+        "Microsoft.Quantum.Convert.DoubleAsStringWithPrecision(1.0, -1)",
+        &Value::String("".into()),
+    );
+}
+
+#[test]
+fn test_double_as_scientific() {
+    for (expr, expected) in [
+        ("12345.678, 3", "1.23e4"),
+        ("-0.00012345, 2", "-1.2e-4"),
+        ("6.02214076e23, 3", "6.02e23"),
+        ("1000.0, 1", "1e3"),
+        ("9.999, 2", "1.0e1"),
+        ("1.0, 4", "1.000e0"),
+        ("0.0, 3", "0.00e0"),
+        ("1e-300, 2", "1.0e-300"),
+        ("1e300, 2", "1.0e300"),
+        ("0.0 / 0.0, 2", "NaN"),
+        ("1.0 / 0.0, 2", "Infinity"),
+        ("-1.0 / 0.0, 2", "-Infinity"),
+    ] {
+        test_expression(
+            &format!("Microsoft.Quantum.Convert.DoubleAsScientific({expr})"),
+            &Value::String(expected.into()),
+        );
+    }
+}
+
+#[test]
+#[should_panic(expected = "DoubleAsScientific: sigFigs must be positive")]
+fn test_double_as_scientific_with_zero_sig_figs() {
+    test_expression(
+        "Microsoft.Quantum.Convert.DoubleAsScientific(1.0, 0)",
+        &Value::String("".into()),
+    );
+}
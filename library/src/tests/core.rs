@@ -71,6 +71,16 @@ fn check_range_empty_n2_n1_n3() {
     test_expression("IsRangeEmpty(-2..-1..-3)", &Value::Bool(false));
 }
 
+#[test]
+fn check_range_empty_5_1() {
+    test_expression("IsRangeEmpty(5..1)", &Value::Bool(true));
+}
+
+#[test]
+fn check_range_empty_5_n1_1() {
+    test_expression("IsRangeEmpty(5..-1..1)", &Value::Bool(false));
+}
+
 #[test]
 fn check_range_reverse_1_5() {
     test_expression(
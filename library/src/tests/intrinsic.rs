@@ -5,7 +5,7 @@ use expect_test::expect;
 use indoc::indoc;
 use qsc::{interpret::Value, target::Profile, SparseSim};
 
-use super::test_expression_with_lib_and_profile_and_sim;
+use super::{test_expression_with_lib_and_profile_and_sim, test_expression_with_output};
 
 // These tests verify multi-controlled decomposition logic for gate operations. Each test
 // manually allocates 2N qubits, performs the decomposed operation from the library on the first N,
@@ -2569,3 +2569,13 @@ fn test_base_mcz_4_control() {
         assert!(sim.sim.qubit_is_zero(i), "qubit {i} is not zero");
     }
 }
+
+#[test]
+fn check_message_output_is_captured() {
+    test_expression_with_output(r#"Message("hello")"#, &Value::unit(), "hello");
+    test_expression_with_output(
+        r#"{ Message("hello"); Message("world"); 3 }"#,
+        &Value::Int(3),
+        "hello\nworld\n",
+    );
+}
@@ -1,7 +1,7 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
-use super::test_expression;
+use super::{test_callable_with_args, test_expression, test_expression_approx};
 use core::f64::consts::E;
 use num_bigint::BigInt;
 use qsc::interpret::Value;
@@ -48,6 +48,15 @@ fn check_is_nan() {
     );
 }
 
+#[test]
+fn check_nan_in_tuple() {
+    test_expression_approx(
+        "(0.0 / 0.0, 1.0 / 3.0)",
+        &Value::Tuple(vec![Value::Double(f64::NAN), Value::Double(0.333_333_333_333)].into()),
+        1e-12,
+    );
+}
+
 #[test]
 fn check_is_infinite() {
     test_expression(
@@ -64,6 +73,24 @@ fn check_is_infinite() {
     );
 }
 
+#[test]
+fn check_is_finite() {
+    test_expression("Microsoft.Quantum.Math.IsFinite(1.0)", &Value::Bool(true));
+    test_expression("Microsoft.Quantum.Math.IsFinite(-0.0)", &Value::Bool(true));
+    test_expression(
+        "Microsoft.Quantum.Math.IsFinite(1.0 / 0.0)",
+        &Value::Bool(false),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.IsFinite(-1.0 / 0.0)",
+        &Value::Bool(false),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.IsFinite(0.0 / 0.0)",
+        &Value::Bool(false),
+    );
+}
+
 //
 // Sign, Abs, Min, Max, etc.
 //
@@ -95,6 +122,32 @@ fn check_sign_l() {
     );
 }
 
+#[test]
+fn check_sign_of_product_d() {
+    test_expression(
+        "Microsoft.Quantum.Math.SignOfProductD([-1.0, -2.0, 3.0])",
+        &Value::Int(1),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.SignOfProductD([-1.0, 2.0])",
+        &Value::Int(-1),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.SignOfProductD([0.0, -1.0])",
+        &Value::Int(0),
+    );
+    test_expression("Microsoft.Quantum.Math.SignOfProductD([])", &Value::Int(1));
+}
+
+#[test]
+fn check_is_even_odd_i() {
+    test_expression("Microsoft.Quantum.Math.IsEvenI(4)", &Value::Bool(true));
+    test_expression("Microsoft.Quantum.Math.IsEvenI(0)", &Value::Bool(true));
+    test_expression("Microsoft.Quantum.Math.IsEvenI(-3)", &Value::Bool(false));
+    test_expression("Microsoft.Quantum.Math.IsOddI(-3)", &Value::Bool(true));
+    test_expression("Microsoft.Quantum.Math.IsOddI(4)", &Value::Bool(false));
+}
+
 #[test]
 fn check_abs_i() {
     test_expression("Microsoft.Quantum.Math.AbsI(0)", &Value::Int(0));
@@ -203,6 +256,104 @@ fn check_max() {
     test_expression("Microsoft.Quantum.Math.Max([1])", &Value::Int(1));
 }
 
+#[test]
+fn check_max_with_args() {
+    test_callable_with_args(
+        "Microsoft.Quantum.Math.Max",
+        &[Value::Array(
+            vec![Value::Int(10), Value::Int(7), Value::Int(-20)].into(),
+        )],
+        &Value::Int(10),
+    );
+    test_callable_with_args(
+        "Microsoft.Quantum.Math.MaxI",
+        &[Value::Int(-5), Value::Int(7)],
+        &Value::Int(7),
+    );
+}
+
+#[test]
+#[should_panic(expected = "does not match the signature of `Microsoft.Quantum.Math.MaxI`")]
+fn check_max_with_mismatched_args() {
+    test_callable_with_args(
+        "Microsoft.Quantum.Math.MaxI",
+        &[Value::Int(-5), Value::Double(7.0), Value::Int(1)],
+        &Value::Int(7),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Array must contain at least one element.")]
+fn check_max_empty() {
+    test_expression("Microsoft.Quantum.Math.Max([])", &Value::Int(0));
+}
+
+#[test]
+fn check_max_of_d() {
+    test_expression(
+        "Microsoft.Quantum.Math.MaxOfD([1.5, -2.0, 7.25, 3.0])",
+        &Value::Double(7.25),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.MaxOfD([-1.0])",
+        &Value::Double(-1.0),
+    );
+}
+
+#[test]
+fn check_min_of_d() {
+    test_expression(
+        "Microsoft.Quantum.Math.MinOfD([1.5, -2.0, 7.25, 3.0])",
+        &Value::Double(-2.0),
+    );
+}
+
+#[test]
+fn check_arg_max() {
+    test_expression("Microsoft.Quantum.Math.ArgMax([3, 9, 1])", &Value::Int(1));
+    test_expression(
+        "Microsoft.Quantum.Math.ArgMax([1, 5, 2, 5])",
+        &Value::Int(1),
+    );
+    test_expression("Microsoft.Quantum.Math.ArgMax([4])", &Value::Int(0));
+}
+
+#[test]
+fn check_arg_min() {
+    test_expression("Microsoft.Quantum.Math.ArgMin([3, 9, 1])", &Value::Int(2));
+    test_expression(
+        "Microsoft.Quantum.Math.ArgMin([3, 0, 2, 0])",
+        &Value::Int(1),
+    );
+}
+
+#[test]
+fn check_arg_max_d_and_arg_min_d() {
+    test_expression(
+        "Microsoft.Quantum.Math.ArgMaxD([0.5, 2.5, 2.5, -1.0])",
+        &Value::Int(1),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.ArgMinD([0.5, -1.0, 2.5, -1.0])",
+        &Value::Int(1),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Array must contain at least one element.")]
+fn check_arg_min_empty() {
+    test_expression("Microsoft.Quantum.Math.ArgMin([])", &Value::Int(0));
+}
+
+#[test]
+#[should_panic(expected = "Array must not contain NaN.")]
+fn check_arg_max_d_nan() {
+    test_expression(
+        "Microsoft.Quantum.Math.ArgMaxD([1.0, 0.0 / 0.0])",
+        &Value::Int(0),
+    );
+}
+
 //
 // Trigonometric functions
 //
@@ -323,6 +474,83 @@ fn check_sqrt() {
     );
 }
 
+#[test]
+fn check_nth_root_d() {
+    test_expression(
+        "Microsoft.Quantum.Math.NthRootD(3, 27.0)",
+        &Value::Double(3.0),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.NthRootD(3, -8.0)",
+        &Value::Double(-2.0),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.NthRootD(2, 16.0)",
+        &Value::Double(4.0),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.NthRootD(3, 1000.0)",
+        &Value::Double(10.0),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.NthRootD(1, -2.5)",
+        &Value::Double(-2.5),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.NthRootD(4, 0.0)",
+        &Value::Double(0.0),
+    );
+    test_expression_approx(
+        "Microsoft.Quantum.Math.NthRootD(7, 2.0)",
+        &Value::Double(2.0_f64.powf(1.0 / 7.0)),
+        1e-12,
+    );
+}
+
+#[test]
+fn check_nth_root_d_infinite() {
+    // The approximate comparison of doubles cannot handle infinities,
+    // so compare within Q# instead.
+    test_expression(
+        "Microsoft.Quantum.Math.NthRootD(2, 1.0 / 0.0) == 1.0 / 0.0",
+        &Value::Bool(true),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.NthRootD(3, -1.0 / 0.0) == -1.0 / 0.0",
+        &Value::Bool(true),
+    );
+}
+
+#[test]
+fn check_nth_root_d_zero() {
+    test_expression(
+        "Microsoft.Quantum.Math.NthRootD(3, -0.0)",
+        &Value::Double(-0.0),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.NthRootD(1, 0.0)",
+        &Value::Double(0.0),
+    );
+}
+
+#[test]
+#[should_panic(expected = "`value` must not be negative when `n` is even")]
+fn check_nth_root_d_even_root_of_negative() {
+    test_expression(
+        "Microsoft.Quantum.Math.NthRootD(2, -4.0)",
+        &Value::Double(0.0),
+    );
+}
+
+#[test]
+#[should_panic(expected = "`n` must be positive")]
+fn check_nth_root_d_non_positive_degree() {
+    test_expression(
+        "Microsoft.Quantum.Math.NthRootD(0, 4.0)",
+        &Value::Double(0.0),
+    );
+}
+
 #[test]
 fn check_log() {
     test_expression(
@@ -343,6 +571,76 @@ fn check_lg() {
     test_expression("Microsoft.Quantum.Math.Lg(2.0)", &Value::Double(1.0));
 }
 
+#[test]
+fn check_log2() {
+    test_expression("Microsoft.Quantum.Math.Log2(1.0)", &Value::Double(0.0));
+    test_expression("Microsoft.Quantum.Math.Log2(8.0)", &Value::Double(3.0));
+}
+
+#[test]
+fn check_log_base() {
+    test_expression(
+        "Microsoft.Quantum.Math.LogBase(8.0, 2.0)",
+        &Value::Double(3.0),
+    );
+    test_expression_approx(
+        "Microsoft.Quantum.Math.LogBase(1000.0, 10.0)",
+        &Value::Double(3.0),
+        1e-12,
+    );
+    test_expression_approx(
+        "Microsoft.Quantum.Math.LogBase(10.0, 3.0)",
+        &Value::Double(10.0_f64.ln() / 3.0_f64.ln()),
+        1e-12,
+    );
+    test_expression_approx(
+        "Microsoft.Quantum.Math.LogBase(0.25, 0.5)",
+        &Value::Double(2.0),
+        1e-12,
+    );
+}
+
+#[test]
+#[should_panic(expected = "`input` must be positive")]
+fn check_log_base_non_positive_input() {
+    test_expression(
+        "Microsoft.Quantum.Math.LogBase(0.0, 2.0)",
+        &Value::Double(0.0),
+    );
+}
+
+#[test]
+#[should_panic(expected = "`base` must not be 1.0")]
+fn check_log_base_one() {
+    test_expression(
+        "Microsoft.Quantum.Math.LogBase(2.0, 1.0)",
+        &Value::Double(0.0),
+    );
+}
+
+#[test]
+fn check_evaluate_polynomial_d() {
+    test_expression_approx(
+        "Microsoft.Quantum.Math.EvaluatePolynomialD([1.0, 2.0, 3.0], 2.0)",
+        &Value::Double(17.0),
+        1e-12,
+    );
+    test_expression_approx(
+        "Microsoft.Quantum.Math.EvaluatePolynomialD([0.5, -1.0, 0.0, 0.25], -1.5)",
+        &Value::Double(1.15625),
+        1e-12,
+    );
+    test_expression_approx(
+        "Microsoft.Quantum.Math.EvaluatePolynomialD([4.0], 100.0)",
+        &Value::Double(4.0),
+        1e-12,
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.EvaluatePolynomialD([], 3.0)",
+        &Value::Double(0.0),
+    );
+}
+
 //
 // Truncation and Rounding
 //
@@ -353,6 +651,36 @@ fn check_truncate() {
     test_expression("Microsoft.Quantum.Math.Truncate(-3.7)", &Value::Int(-3));
 }
 
+#[test]
+fn check_truncate_to_bigint() {
+    test_expression(
+        "Microsoft.Quantum.Convert.IntAsBigInt(5)",
+        &Value::BigInt(BigInt::from(5)),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.TruncateToBigInt(3.9)",
+        &Value::BigInt(BigInt::from(3)),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.TruncateToBigInt(-3.9)",
+        &Value::BigInt(BigInt::from(-3)),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.TruncateToBigInt(1.0e20)",
+        &Value::BigInt(
+            BigInt::from_str("100000000000000000000")
+                .expect("Cannot parse static BigInt in Rust code."),
+        ),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.TruncateToBigInt(-1.0e20)",
+        &Value::BigInt(
+            BigInt::from_str("-100000000000000000000")
+                .expect("Cannot parse static BigInt in Rust code."),
+        ),
+    );
+}
+
 #[test]
 fn check_ceiling() {
     test_expression("Microsoft.Quantum.Math.Ceiling(3.1)", &Value::Int(4));
@@ -365,12 +693,209 @@ fn check_floor() {
     test_expression("Microsoft.Quantum.Math.Floor(-3.1)", &Value::Int(-4));
 }
 
+#[test]
+fn check_truncate_to_int() {
+    test_expression("Microsoft.Quantum.Math.TruncateToInt(1.5)", &Value::Int(1));
+    test_expression(
+        "Microsoft.Quantum.Math.TruncateToInt(-1.5)",
+        &Value::Int(-1),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.TruncateToInt(-9.223372036854775808e18)",
+        &Value::Int(i64::MIN),
+    );
+}
+
+#[test]
+fn check_floor_to_int() {
+    test_expression("Microsoft.Quantum.Math.FloorToInt(1.5)", &Value::Int(1));
+    test_expression("Microsoft.Quantum.Math.FloorToInt(-1.5)", &Value::Int(-2));
+    test_expression("Microsoft.Quantum.Math.FloorToInt(-2.0)", &Value::Int(-2));
+    test_expression("Microsoft.Quantum.Math.FloorToInt(-1e-16)", &Value::Int(-1));
+}
+
+#[test]
+fn check_ceiling_to_int() {
+    test_expression("Microsoft.Quantum.Math.CeilingToInt(1.5)", &Value::Int(2));
+    test_expression("Microsoft.Quantum.Math.CeilingToInt(-1.5)", &Value::Int(-1));
+    test_expression("Microsoft.Quantum.Math.CeilingToInt(2.0)", &Value::Int(2));
+    test_expression("Microsoft.Quantum.Math.CeilingToInt(1e-16)", &Value::Int(1));
+}
+
+#[test]
+#[should_panic(expected = "`value` is out of the range of `Int`")]
+fn check_floor_to_int_out_of_range() {
+    test_expression("Microsoft.Quantum.Math.FloorToInt(1e19)", &Value::Int(0));
+}
+
+#[test]
+#[should_panic(expected = "`value` must not be NaN")]
+fn check_ceiling_to_int_nan() {
+    test_expression(
+        "Microsoft.Quantum.Math.CeilingToInt(0.0 / 0.0)",
+        &Value::Int(0),
+    );
+}
+
+#[test]
+#[should_panic(expected = "`value` must be finite")]
+fn check_truncate_to_int_infinite() {
+    test_expression(
+        "Microsoft.Quantum.Math.TruncateToInt(1.0 / 0.0)",
+        &Value::Int(0),
+    );
+}
+
+#[test]
+fn check_floor_to_bigint() {
+    test_expression(
+        "Microsoft.Quantum.Math.FloorToBigInt(1.5)",
+        &Value::BigInt(BigInt::from(1)),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.FloorToBigInt(-1.5)",
+        &Value::BigInt(BigInt::from(-2)),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.FloorToBigInt(-4503599627370495.5)",
+        &Value::BigInt(BigInt::from(-4_503_599_627_370_496_i64)),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.FloorToBigInt(1.0e20)",
+        &Value::BigInt(
+            BigInt::from_str("100000000000000000000")
+                .expect("Cannot parse static BigInt in Rust code."),
+        ),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.FloorToBigInt(-1.0e20)",
+        &Value::BigInt(
+            BigInt::from_str("-100000000000000000000")
+                .expect("Cannot parse static BigInt in Rust code."),
+        ),
+    );
+}
+
+#[test]
+fn check_ceiling_to_bigint() {
+    test_expression(
+        "Microsoft.Quantum.Math.CeilingToBigInt(1.5)",
+        &Value::BigInt(BigInt::from(2)),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.CeilingToBigInt(-1.5)",
+        &Value::BigInt(BigInt::from(-1)),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.CeilingToBigInt(-4503599627370495.5)",
+        &Value::BigInt(BigInt::from(-4_503_599_627_370_495_i64)),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.CeilingToBigInt(1.0e20)",
+        &Value::BigInt(
+            BigInt::from_str("100000000000000000000")
+                .expect("Cannot parse static BigInt in Rust code."),
+        ),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.CeilingToBigInt(-1.0e20)",
+        &Value::BigInt(
+            BigInt::from_str("-100000000000000000000")
+                .expect("Cannot parse static BigInt in Rust code."),
+        ),
+    );
+}
+
+#[test]
+#[should_panic(expected = "`value` must not be NaN")]
+fn check_floor_to_bigint_nan() {
+    test_expression(
+        "Microsoft.Quantum.Math.FloorToBigInt(0.0 / 0.0)",
+        &Value::BigInt(BigInt::from(0)),
+    );
+}
+
+#[test]
+#[should_panic(expected = "`value` must be finite")]
+fn check_ceiling_to_bigint_infinite() {
+    test_expression(
+        "Microsoft.Quantum.Math.CeilingToBigInt(-1.0 / 0.0)",
+        &Value::BigInt(BigInt::from(0)),
+    );
+}
+
 #[test]
 fn check_round() {
     test_expression("Microsoft.Quantum.Math.Round(3.1)", &Value::Int(3));
     test_expression("Microsoft.Quantum.Math.Round(-3.7)", &Value::Int(-4));
 }
 
+#[test]
+fn check_round_half_to_even() {
+    test_expression(
+        "Microsoft.Quantum.Math.RoundHalfToEven(2.5)",
+        &Value::Int(2),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.RoundHalfToEven(3.5)",
+        &Value::Int(4),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.RoundHalfToEven(-2.5)",
+        &Value::Int(-2),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.RoundHalfToEven(-3.5)",
+        &Value::Int(-4),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.RoundHalfToEven(2.6)",
+        &Value::Int(3),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.RoundHalfToEven(-2.4)",
+        &Value::Int(-2),
+    );
+}
+
+fn check_round_with_mode(mode: &str, expected: [i64; 4]) {
+    for (value, expected) in ["0.5", "1.5", "2.5", "-2.5"].iter().zip(expected) {
+        test_expression(
+            &format!(
+                "{{
+                open Microsoft.Quantum.Math;
+                RoundWithMode({value}, {mode}())}}"
+            ),
+            &Value::Int(expected),
+        );
+    }
+}
+
+#[test]
+fn check_round_with_mode_toward_zero() {
+    check_round_with_mode("TowardZero", [0, 1, 2, -2]);
+}
+
+#[test]
+fn check_round_with_mode_away_from_zero() {
+    check_round_with_mode("AwayFromZero", [1, 2, 3, -3]);
+}
+
+#[test]
+fn check_round_with_mode_half_to_even() {
+    check_round_with_mode("HalfToEven", [0, 2, 2, -2]);
+}
+
+#[test]
+fn check_round_with_mode_up() {
+    check_round_with_mode("Up", [1, 2, 3, -2]);
+}
+
+#[test]
+fn check_round_with_mode_down() {
+    check_round_with_mode("Down", [0, 1, 2, -3]);
+}
+
 //
 // Modular arithmetic
 //
@@ -429,6 +954,44 @@ fn check_modulus_l() {
     );
 }
 
+#[test]
+fn check_modulus_d() {
+    test_expression_approx(
+        "Microsoft.Quantum.Math.ModulusD(5.5, 2.0)",
+        &Value::Double(1.5),
+        1e-12,
+    );
+    test_expression_approx(
+        "Microsoft.Quantum.Math.ModulusD(-1.5, 2.0)",
+        &Value::Double(0.5),
+        1e-12,
+    );
+    test_expression_approx(
+        "Microsoft.Quantum.Math.ModulusD(-4.0, 2.0)",
+        &Value::Double(0.0),
+        1e-12,
+    );
+    test_expression_approx(
+        "Microsoft.Quantum.Math.ModulusD(1.5, -2.0)",
+        &Value::Double(-0.5),
+        1e-12,
+    );
+    test_expression_approx(
+        "Microsoft.Quantum.Math.ModulusD(-1e-20, 2.0)",
+        &Value::Double(0.0),
+        1e-12,
+    );
+}
+
+#[test]
+#[should_panic(expected = "`modulus` must be non-zero")]
+fn check_modulus_d_zero_modulus() {
+    test_expression(
+        "Microsoft.Quantum.Math.ModulusD(1.0, 0.0)",
+        &Value::Double(0.0),
+    );
+}
+
 #[test]
 fn check_exp_mod_i() {
     test_expression("Microsoft.Quantum.Math.ExpModI(1,10,10)", &Value::Int(1));
@@ -452,6 +1015,33 @@ fn check_exp_mod_l() {
     );
 }
 
+#[test]
+fn check_pow_l() {
+    test_expression(
+        "Microsoft.Quantum.Math.PowL(2L, 100)",
+        &Value::BigInt(
+            BigInt::from_str("1267650600228229401496703205376")
+                .expect("Cannot parse static BigInt in Rust code."),
+        ),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.PowL(0L, 0)",
+        &Value::BigInt(BigInt::from(1)),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.PowL(5L, 0)",
+        &Value::BigInt(BigInt::from(1)),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.PowL(-3L, 3)",
+        &Value::BigInt(BigInt::from(-27)),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.PowL(-3L, 4)",
+        &Value::BigInt(BigInt::from(81)),
+    );
+}
+
 #[test]
 fn check_inverse_mod_i() {
     test_expression("Microsoft.Quantum.Math.InverseModI(2,5)", &Value::Int(3));
@@ -475,6 +1065,30 @@ fn check_inverse_mod_l() {
     );
 }
 
+#[test]
+fn check_multiplicative_order_i() {
+    test_expression(
+        "Microsoft.Quantum.Math.MultiplicativeOrderI(2, 7)",
+        &Value::Int(3),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.MultiplicativeOrderI(3, 7)",
+        &Value::Int(6),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.MultiplicativeOrderI(7, 15)",
+        &Value::Int(4),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.MultiplicativeOrderI(-1, 5)",
+        &Value::Int(2),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.MultiplicativeOrderI(5, 1)",
+        &Value::Int(1),
+    );
+}
+
 //
 // GCD, etc.
 //
@@ -567,6 +1181,110 @@ fn check_cfc_i() {
     );
 }
 
+#[test]
+fn check_cfc_i_denominator_bounds() {
+    // The convergents of 2/7 = [0; 3, 2] are 0/1, 1/3 and 2/7.
+    test_expression(
+        "Microsoft.Quantum.Math.ContinuedFractionConvergentI((2,7), 1)",
+        &Value::Tuple(vec![Value::Int(0), Value::Int(1)].into()),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.ContinuedFractionConvergentI((2,7), 3)",
+        &Value::Tuple(vec![Value::Int(-1), Value::Int(-3)].into()),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.ContinuedFractionConvergentI((2,7), 6)",
+        &Value::Tuple(vec![Value::Int(-1), Value::Int(-3)].into()),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.ContinuedFractionConvergentI((2,7), 7)",
+        &Value::Tuple(vec![Value::Int(2), Value::Int(7)].into()),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.ContinuedFractionConvergentI((2,7), 100)",
+        &Value::Tuple(vec![Value::Int(2), Value::Int(7)].into()),
+    );
+}
+
+#[test]
+fn check_cfc_zero_numerator() {
+    test_expression(
+        "Microsoft.Quantum.Math.ContinuedFractionConvergentI((0,7), 5)",
+        &Value::Tuple(vec![Value::Int(0), Value::Int(1)].into()),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.ContinuedFractionConvergentL((0L,7L), 5L)",
+        &Value::Tuple(
+            vec![
+                Value::BigInt(BigInt::from(0)),
+                Value::BigInt(BigInt::from(1)),
+            ]
+            .into(),
+        ),
+    );
+}
+
+#[test]
+fn check_reduced_fraction() {
+    test_expression(
+        "{
+            open Microsoft.Quantum.Math;
+            let f = ReducedFraction(Fraction(6, 8));
+            (f::Numerator, f::Denominator)
+        }",
+        &Value::Tuple(vec![Value::Int(3), Value::Int(4)].into()),
+    );
+    test_expression(
+        "{
+            open Microsoft.Quantum.Math;
+            let f = ReducedFraction(Fraction(6, -8));
+            (f::Numerator, f::Denominator)
+        }",
+        &Value::Tuple(vec![Value::Int(-3), Value::Int(4)].into()),
+    );
+    test_expression(
+        "{
+            open Microsoft.Quantum.Math;
+            let f = ReducedFraction(Fraction(0, -5));
+            (f::Numerator, f::Denominator)
+        }",
+        &Value::Tuple(vec![Value::Int(0), Value::Int(1)].into()),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Denominator must not be zero")]
+fn check_reduced_fraction_zero_denominator() {
+    test_expression(
+        "Microsoft.Quantum.Math.ReducedFraction(Microsoft.Quantum.Math.Fraction(1, 0))",
+        &Value::unit(),
+    );
+}
+
+#[test]
+fn check_add_fractions() {
+    test_expression(
+        "{
+            open Microsoft.Quantum.Math;
+            let f = AddFractions(Fraction(1, 6), Fraction(1, -3));
+            (f::Numerator, f::Denominator)
+        }",
+        &Value::Tuple(vec![Value::Int(-1), Value::Int(6)].into()),
+    );
+}
+
+#[test]
+fn check_multiply_fractions() {
+    test_expression(
+        "{
+            open Microsoft.Quantum.Math;
+            let f = MultiplyFractions(Fraction(2, 3), Fraction(9, 4));
+            (f::Numerator, f::Denominator)
+        }",
+        &Value::Tuple(vec![Value::Int(3), Value::Int(2)].into()),
+    );
+}
+
 #[test]
 fn check_cfc_l() {
     // NOTE: It is not important if the function returns -3/-4 or 3/4,
@@ -660,6 +1378,22 @@ fn check_bitsize_l() {
         "Microsoft.Quantum.Math.BitSizeL(0x8FFFFFFFFFFFFFFFL)",
         &Value::Int(64),
     );
+    test_expression("Microsoft.Quantum.Math.BitSizeL(2L^200)", &Value::Int(201));
+    test_expression(
+        "Microsoft.Quantum.Math.BitSizeL(2L^200 - 1L)",
+        &Value::Int(200),
+    );
+}
+
+#[test]
+fn check_bitsize_l_negative_uses_magnitude() {
+    test_expression("Microsoft.Quantum.Math.BitSizeL(-1L)", &Value::Int(1));
+    test_expression("Microsoft.Quantum.Math.BitSizeL(-3L)", &Value::Int(2));
+    test_expression("Microsoft.Quantum.Math.BitSizeL(-4L)", &Value::Int(3));
+    test_expression(
+        "Microsoft.Quantum.Math.BitSizeL(-(2L^200))",
+        &Value::Int(201),
+    );
 }
 
 #[test]
@@ -676,6 +1410,46 @@ fn check_trailing_zero_count_i() {
         "Microsoft.Quantum.Math.TrailingZeroCountI(7616)",
         &Value::Int(6),
     );
+    test_expression(
+        "Microsoft.Quantum.Math.TrailingZeroCountI(8)",
+        &Value::Int(3),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.TrailingZeroCountI(0)",
+        &Value::Int(64),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.TrailingZeroCountI(-8)",
+        &Value::Int(3),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.TrailingZeroCountI(-0x8000000000000000)",
+        &Value::Int(63),
+    );
+}
+
+#[test]
+fn check_leading_zero_count_i() {
+    test_expression(
+        "Microsoft.Quantum.Math.LeadingZeroCountI(1)",
+        &Value::Int(63),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.LeadingZeroCountI(0)",
+        &Value::Int(64),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.LeadingZeroCountI(255)",
+        &Value::Int(56),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.LeadingZeroCountI(0x7FFFFFFFFFFFFFFF)",
+        &Value::Int(1),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.LeadingZeroCountI(-1)",
+        &Value::Int(0),
+    );
 }
 
 #[test]
@@ -799,6 +1573,18 @@ fn check_binom() {
     test_expression("Microsoft.Quantum.Math.Binom(4, 4)", &Value::Int(1));
 }
 
+#[test]
+fn check_partition_count() {
+    test_expression("Microsoft.Quantum.Math.PartitionCount(0)", &Value::Int(1));
+    test_expression("Microsoft.Quantum.Math.PartitionCount(1)", &Value::Int(1));
+    test_expression("Microsoft.Quantum.Math.PartitionCount(4)", &Value::Int(5));
+    test_expression("Microsoft.Quantum.Math.PartitionCount(5)", &Value::Int(7));
+    test_expression(
+        "Microsoft.Quantum.Math.PartitionCount(100)",
+        &Value::Int(190_569_292),
+    );
+}
+
 #[test]
 fn check_square_norm() {
     test_expression(
@@ -856,6 +1642,89 @@ fn check_p_normalized() {
         "Microsoft.Quantum.Math.PNormalized(3.0, [0.0, 0.0])",
         &Value::Array(vec![Value::Double(0.0), Value::Double(0.0)].into()),
     );
+    test_expression_approx(
+        "Microsoft.Quantum.Math.PNormalized(2.0, [1.0, -1.0])",
+        &Value::Array(
+            vec![
+                Value::Double(std::f64::consts::FRAC_1_SQRT_2),
+                Value::Double(-std::f64::consts::FRAC_1_SQRT_2),
+            ]
+            .into(),
+        ),
+        1e-12,
+    );
+}
+
+#[test]
+fn check_p_norm_two_norm() {
+    test_expression_approx(
+        "Microsoft.Quantum.Math.PNorm(2.0, [3.0, 4.0])",
+        &Value::Double(5.0),
+        1e-12,
+    );
+}
+
+#[test]
+fn check_p_norm_large_exponents() {
+    // Without rescaling, 3.0^1000.0 overflows and 0.5^2000.0 underflows.
+    test_expression_approx(
+        "Microsoft.Quantum.Math.PNorm(1000.0, [3.0, -2.0, 1.0])",
+        &Value::Double(3.0),
+        1e-12,
+    );
+    test_expression_approx(
+        "Microsoft.Quantum.Math.PNorm(2000.0, [0.5, 0.25])",
+        &Value::Double(0.5),
+        1e-12,
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.PNorm(1.0 / 0.0, [3.0, -7.0, 1.0])",
+        &Value::Double(7.0),
+    );
+}
+
+#[test]
+fn check_p_norm_infinite_element() {
+    // The approximate comparison of doubles cannot handle infinities,
+    // so compare within Q# instead.
+    test_expression(
+        "Microsoft.Quantum.Math.PNorm(2.0, [1.0, -1.0 / 0.0, 3.0]) == 1.0 / 0.0",
+        &Value::Bool(true),
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.PNorm(1.0 / 0.0, [1.0 / 0.0, 3.0]) == 1.0 / 0.0",
+        &Value::Bool(true),
+    );
+}
+
+#[test]
+#[should_panic(expected = "p must be >= 1.0")]
+fn check_p_norm_exponent_below_one() {
+    test_expression(
+        "Microsoft.Quantum.Math.PNorm(0.5, [1.0, 1.0])",
+        &Value::Double(0.0),
+    );
+}
+
+#[test]
+#[should_panic(expected = "p must be >= 1.0")]
+fn check_p_norm_zero_exponent() {
+    test_expression(
+        "Microsoft.Quantum.Math.PNorm(0.0, [1.0, 1.0])",
+        &Value::Double(0.0),
+    );
+}
+
+#[test]
+fn check_p_normalized_squares_sum_to_one() {
+    test_expression_approx(
+        "{
+            let normalized = Microsoft.Quantum.Math.PNormalized(2.0, [0.3, -1.2, 2.5, 0.7]);
+            Microsoft.Quantum.Math.SquaredNorm(normalized)
+        }",
+        &Value::Double(1.0),
+        1e-12,
+    );
 }
 
 //
@@ -1150,6 +2019,169 @@ fn check_devide_by_cp() {
     );
 }
 
+#[test]
+fn check_approximately_equal_c() {
+    test_expression(
+        "{
+        open Microsoft.Quantum.Math;
+        ApproximatelyEqualC(1e-9, Complex(1.0, 1.0), Complex(1.0 + 1e-12, 1.0))}",
+        &Value::Bool(true),
+    );
+    test_expression(
+        "{
+        open Microsoft.Quantum.Math;
+        ApproximatelyEqualC(1e-9, Complex(1.0, 1.0), Complex(1.0, 1.0 - 1e-6))}",
+        &Value::Bool(false),
+    );
+}
+
+#[test]
+fn check_round_complex() {
+    test_expression_approx(
+        "{
+        open Microsoft.Quantum.Math;
+        RoundComplex(Complex(1.23456, -0.98765), 2)}",
+        &Value::Tuple(vec![Value::Double(1.23), Value::Double(-0.99)].into()),
+        1e-12,
+    );
+    test_expression_approx(
+        "{
+        open Microsoft.Quantum.Math;
+        RoundComplex(Complex(1234.5678, 0.4), 0)}",
+        &Value::Tuple(vec![Value::Double(1235.0), Value::Double(0.0)].into()),
+        1e-12,
+    );
+    test_expression_approx(
+        "{
+        open Microsoft.Quantum.Math;
+        RoundComplex(Complex(1e300, -2.5e-7), 6)}",
+        &Value::Tuple(vec![Value::Double(1e300), Value::Double(-0.0)].into()),
+        1e-12,
+    );
+}
+
+#[test]
+fn check_probability_from_amplitude() {
+    test_expression_approx(
+        "{
+        open Microsoft.Quantum.Math;
+        ProbabilityFromAmplitude(Complex(0.6, -0.8))}",
+        &Value::Double(1.0),
+        1e-12,
+    );
+}
+
+#[test]
+fn check_probabilities_from_amplitudes() {
+    test_expression_approx(
+        "{
+        open Microsoft.Quantum.Math;
+        ProbabilitiesFromAmplitudes([Complex(1.0 / Sqrt(2.0), 0.0), Complex(0.0, 1.0 / Sqrt(2.0))])}",
+        &Value::Array(vec![Value::Double(0.5), Value::Double(0.5)].into()),
+        1e-12,
+    );
+    test_expression(
+        "Microsoft.Quantum.Math.ProbabilitiesFromAmplitudes([])",
+        &Value::Array(vec![].into()),
+    );
+}
+
+//
+// Statistics
+//
+
+#[test]
+fn check_online_statistics_match_batch() {
+    let data = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.5];
+    #[allow(clippy::cast_precision_loss)]
+    let n = data.len() as f64;
+    let mean = data.iter().sum::<f64>() / n;
+    let variance = data.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+
+    test_expression_approx(
+        "{
+            open Microsoft.Quantum.Math;
+            mutable acc = OnlineStatistics(0, 0.0, 0.0);
+            for x in [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.5] {
+                set acc = UpdateStatistics(acc, x);
+            }
+            (acc::Count, StatisticsMean(acc), StatisticsVariance(acc))
+        }",
+        &Value::Tuple(vec![Value::Int(8), Value::Double(mean), Value::Double(variance)].into()),
+        1e-12,
+    );
+}
+
+#[test]
+fn check_online_statistics_single_sample() {
+    test_expression(
+        "{
+            open Microsoft.Quantum.Math;
+            StatisticsMean(UpdateStatistics(OnlineStatistics(0, 0.0, 0.0), -3.5))
+        }",
+        &Value::Double(-3.5),
+    );
+}
+
+#[test]
+#[should_panic(expected = "StatisticsMean: at least one sample is required")]
+fn check_online_statistics_mean_without_samples() {
+    test_expression(
+        "Microsoft.Quantum.Math.StatisticsMean(Microsoft.Quantum.Math.OnlineStatistics(0, 0.0, 0.0))",
+        &Value::Double(0.0),
+    );
+}
+
+#[test]
+#[should_panic(expected = "StatisticsVariance: at least two samples are required")]
+fn check_online_statistics_variance_with_one_sample() {
+    test_expression(
+        "{
+            open Microsoft.Quantum.Math;
+            StatisticsVariance(UpdateStatistics(OnlineStatistics(0, 0.0, 0.0), 1.0))
+        }",
+        &Value::Double(0.0),
+    );
+}
+
+#[test]
+fn check_standard_error() {
+    // The sample variance of [1, 2, 3, 4] is 5/3, so the standard error is sqrt(5/12).
+    test_expression_approx(
+        "Microsoft.Quantum.Math.StandardError([1.0, 2.0, 3.0, 4.0])",
+        &Value::Double((5.0_f64 / 12.0).sqrt()),
+        1e-12,
+    );
+}
+
+#[test]
+fn check_confidence_interval_95() {
+    let margin = 1.96 * (5.0_f64 / 12.0).sqrt();
+    test_expression_approx(
+        "Microsoft.Quantum.Math.ConfidenceInterval95([1.0, 2.0, 3.0, 4.0])",
+        &Value::Tuple(vec![Value::Double(2.5 - margin), Value::Double(2.5 + margin)].into()),
+        1e-12,
+    );
+}
+
+#[test]
+#[should_panic(expected = "StandardError: at least two samples are required")]
+fn check_standard_error_with_one_sample() {
+    test_expression(
+        "Microsoft.Quantum.Math.StandardError([1.0])",
+        &Value::Double(0.0),
+    );
+}
+
+#[test]
+#[should_panic(expected = "ConfidenceInterval95: at least two samples are required")]
+fn check_confidence_interval_95_without_samples() {
+    test_expression(
+        "Microsoft.Quantum.Math.ConfidenceInterval95([])",
+        &Value::Tuple(vec![Value::Double(0.0), Value::Double(0.0)].into()),
+    );
+}
+
 //
 // Fixed point
 //
@@ -1168,6 +2200,10 @@ fn check_smallest_fixed_point() {
         "Microsoft.Quantum.Math.SmallestFixedPoint(10,5)",
         &Value::Double(-512.0),
     );
+    test_expression(
+        "Microsoft.Quantum.Math.SmallestFixedPoint(4, 4)",
+        &Value::Double(-8.0),
+    );
 }
 
 #[test]
@@ -1184,4 +2220,26 @@ fn check_largest_fixed_point() {
         "Microsoft.Quantum.Math.LargestFixedPoint(10,5)",
         &Value::Double(511.96875),
     );
+    test_expression(
+        "Microsoft.Quantum.Math.LargestFixedPoint(4, 4)",
+        &Value::Double(7.9375),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Fixed point numbers must have a positive number of bits")]
+fn check_smallest_fixed_point_without_bits() {
+    test_expression(
+        "Microsoft.Quantum.Math.SmallestFixedPoint(0, 0)",
+        &Value::Double(0.0),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Fixed point numbers must have a positive number of bits")]
+fn check_largest_fixed_point_without_bits() {
+    test_expression(
+        "Microsoft.Quantum.Math.LargestFixedPoint(2, -3)",
+        &Value::Double(0.0),
+    );
 }
@@ -0,0 +1,104 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use super::{eval_expression_with_seed, test_expression};
+use qsc::interpret::Value;
+
+// Tests for Microsoft.Quantum.Random namespace
+
+const DRAW_INTS: &str = "{
+    open Microsoft.Quantum.Random;
+    mutable draws = [];
+    for _ in 1..100 {
+        set draws += [DrawRandomInt(3, 7)];
+    }
+    draws
+}";
+
+const DRAW_DOUBLES: &str = "{
+    open Microsoft.Quantum.Random;
+    mutable draws = [];
+    for _ in 1..100 {
+        set draws += [DrawRandomDouble(-0.5, 0.25)];
+    }
+    draws
+}";
+
+#[test]
+fn check_draw_random_int_is_within_inclusive_bounds() {
+    let draws = eval_expression_with_seed(DRAW_INTS, 42).unwrap_array();
+    let draws: Vec<i64> = draws.iter().map(|v| v.clone().unwrap_int()).collect();
+    assert!(draws.iter().all(|d| (3..=7).contains(d)), "{draws:?}");
+    // With 100 draws from five values, both bounds show up.
+    assert!(draws.contains(&3) && draws.contains(&7), "{draws:?}");
+}
+
+#[test]
+fn check_draw_random_int_is_reproducible_with_seed() {
+    assert_eq!(
+        eval_expression_with_seed(DRAW_INTS, 42),
+        eval_expression_with_seed(DRAW_INTS, 42)
+    );
+    assert_ne!(
+        eval_expression_with_seed(DRAW_INTS, 42),
+        eval_expression_with_seed(DRAW_INTS, 43)
+    );
+}
+
+#[test]
+fn check_draw_random_int_with_equal_bounds() {
+    test_expression(
+        "Microsoft.Quantum.Random.DrawRandomInt(-4, -4)",
+        &Value::Int(-4),
+    );
+}
+
+#[test]
+#[should_panic(expected = "EmptyRange")]
+fn check_draw_random_int_with_inverted_bounds() {
+    test_expression(
+        "Microsoft.Quantum.Random.DrawRandomInt(7, 3)",
+        &Value::Int(0),
+    );
+}
+
+#[test]
+fn check_draw_random_double_is_within_inclusive_bounds() {
+    let draws = eval_expression_with_seed(DRAW_DOUBLES, 7).unwrap_array();
+    assert!(
+        draws
+            .iter()
+            .all(|v| (-0.5..=0.25).contains(&v.clone().unwrap_double())),
+        "{draws:?}"
+    );
+}
+
+#[test]
+fn check_draw_random_double_is_reproducible_with_seed() {
+    assert_eq!(
+        eval_expression_with_seed(DRAW_DOUBLES, 7),
+        eval_expression_with_seed(DRAW_DOUBLES, 7)
+    );
+    assert_ne!(
+        eval_expression_with_seed(DRAW_DOUBLES, 7),
+        eval_expression_with_seed(DRAW_DOUBLES, 8)
+    );
+}
+
+#[test]
+#[should_panic(expected = "EmptyRange")]
+fn check_draw_random_double_with_inverted_bounds() {
+    test_expression(
+        "Microsoft.Quantum.Random.DrawRandomDouble(1.0, 0.0)",
+        &Value::Double(0.0),
+    );
+}
+
+#[test]
+#[should_panic(expected = "the interval between `min` and `max` must be finite")]
+fn check_draw_random_double_with_infinite_bound() {
+    test_expression(
+        "Microsoft.Quantum.Random.DrawRandomDouble(0.0, 1.0 / 0.0)",
+        &Value::Double(0.0),
+    );
+}
@@ -36,8 +36,7 @@ impl DebugService {
         language_features: Vec<String>,
     ) -> String {
         let source_map = get_source_map(sources, &entry);
-        let target = Profile::from_str(target_profile)
-            .unwrap_or_else(|()| panic!("Invalid target : {target_profile}"));
+        let target = Profile::from_str(target_profile).unwrap_or_else(|e| panic!("{e}"));
         let features = LanguageFeatures::from_iter(language_features);
         match Debugger::new(source_map, target.into(), Encoding::Utf16, features) {
             Ok(debugger) => {